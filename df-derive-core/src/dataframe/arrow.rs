@@ -0,0 +1,269 @@
+//! Arrow schema export with column nullability.
+//!
+//! Polars' `DataType` has no notion of a required column, so a `DataFrame`
+//! written through Polars' own writers marks every column `OPTIONAL`.
+//! [`ToDataFrame::arrow_schema`] knows better: derived impls report which
+//! columns (and which list elements) can actually hold nulls. The writers in
+//! this module stamp that schema onto the file so non-`Option` fields land as
+//! `REQUIRED` columns.
+//!
+//! - `write_parquet` (feature `parquet`) writes a Parquet file.
+//! - `write_ipc` (feature `ipc`) writes an Arrow IPC file.
+//!
+//! Both writers check up front that the frame still matches `T::schema()`,
+//! and reject any batch whose non-nullable columns contain nulls. They write
+//! [`annotated_arrow_schema`], so column descriptions and units from
+//! [`ToDataFrame::column_metadata`] travel as Arrow field metadata.
+
+use polars::prelude::{
+    ArrowDataType, ArrowField, ArrowSchema, CompatLevel, DataType, PlSmallStr, PolarsResult,
+};
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+use polars::prelude::{DataFrame, polars_bail};
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+use polars_arrow::array::{Array, FixedSizeListArray, ListArray};
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+use polars_arrow::record_batch::RecordBatch;
+
+use super::ToDataFrame;
+
+#[cfg(feature = "parquet")]
+pub use polars_parquet::write::{
+    BrotliLevel, CompressionOptions as ParquetCompression, GzipLevel, ZstdLevel,
+};
+
+pub(super) const LIST_ITEM_NAME: &str = "item";
+
+/// Arrow field for every column of `schema`, all nullable. This is the
+/// fallback for `ToDataFrame` impls that do not track nullability.
+pub(super) fn nullable_arrow_schema(schema: Vec<(String, DataType)>) -> ArrowSchema {
+    schema
+        .into_iter()
+        .map(|(name, dtype)| {
+            let name = PlSmallStr::from_string(name);
+            let field = dtype.to_arrow_field(name.clone(), CompatLevel::newest());
+            (name, field)
+        })
+        .collect()
+}
+
+/// Non-nullable Arrow field for a primitive leaf dtype. The name is filled in
+/// by [`list_arrow_field`].
+#[doc(hidden)]
+#[must_use]
+pub fn required_arrow_field(dtype: &DataType) -> ArrowField {
+    ArrowField::new(
+        PlSmallStr::EMPTY,
+        dtype.to_arrow(CompatLevel::newest()),
+        false,
+    )
+}
+
+/// Re-root `leaf` as column `name`, wrapped in one `LargeList` per entry of
+/// `nullability` after the first.
+///
+/// `nullability[0]` is the column's own nullability and each following
+/// entry is the element nullability of the next list layer, outermost
+/// first. A leaf that is already nullable (a nested column reached through
+/// an `Option` inside the nested type) stays nullable.
+#[doc(hidden)]
+#[must_use]
+pub fn list_arrow_field(name: PlSmallStr, leaf: ArrowField, nullability: &[bool]) -> ArrowField {
+    array_arrow_field(name, leaf, nullability, &[])
+}
+
+/// [`list_arrow_field`] for a column with fixed-width `[T; N]` layers: each
+/// layer with a length in `fixed_lens` (outermost first) becomes a
+/// `FixedSizeList` instead of a `LargeList`.
+#[doc(hidden)]
+#[must_use]
+pub fn array_arrow_field(
+    name: PlSmallStr,
+    leaf: ArrowField,
+    nullability: &[bool],
+    fixed_lens: &[Option<usize>],
+) -> ArrowField {
+    let mut field = leaf;
+    if let Some((&innermost, list_layers)) = nullability.split_last() {
+        field.is_nullable |= innermost;
+        for (layer, &nullable) in list_layers.iter().enumerate().rev() {
+            field.name = PlSmallStr::from_static(LIST_ITEM_NAME);
+            let item = Box::new(field);
+            let dtype = match fixed_lens.get(layer).copied().flatten() {
+                Some(len) => ArrowDataType::FixedSizeList(item, len),
+                None => ArrowDataType::LargeList(item),
+            };
+            field = ArrowField::new(PlSmallStr::EMPTY, dtype, nullable);
+        }
+    }
+    field.name = name;
+    field
+}
+
+#[doc(hidden)]
+#[must_use]
+pub fn arrow_schema_from_fields(fields: Vec<ArrowField>) -> ArrowSchema {
+    fields
+        .into_iter()
+        .map(|field| (field.name.clone(), field))
+        .collect()
+}
+
+/// `T::arrow_schema()` with each field's metadata set from
+/// `T::column_metadata()`.
+///
+/// # Errors
+/// Returns an error if schema generation fails.
+pub fn annotated_arrow_schema<T: ToDataFrame>() -> PolarsResult<ArrowSchema> {
+    let mut schema = T::arrow_schema()?;
+    for (name, metadata) in T::column_metadata()? {
+        if let Some(field) = schema.get_mut(&name) {
+            let metadata = metadata
+                .into_iter()
+                .map(|(key, value)| (PlSmallStr::from_string(key), PlSmallStr::from_string(value)))
+                .collect();
+            field.metadata = Some(std::sync::Arc::new(metadata));
+        }
+    }
+    Ok(schema)
+}
+
+/// Check that `df` still has the columns `T` derives, then align its
+/// chunks so it can be sliced into record batches.
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+fn prepare_frame<T: ToDataFrame>(df: &mut DataFrame) -> PolarsResult<ArrowSchema> {
+    let expected = T::schema()?;
+    let columns = df.columns();
+    if columns.len() != expected.len() {
+        polars_bail!(
+            SchemaMismatch:
+            "df-derive: DataFrame has {} columns, expected {}",
+            columns.len(),
+            expected.len(),
+        );
+    }
+    for (column, (name, dtype)) in columns.iter().zip(&expected) {
+        if column.name().as_str() != name || column.dtype() != dtype {
+            polars_bail!(
+                SchemaMismatch:
+                "df-derive: DataFrame column `{}: {}` does not match expected `{}: {}`",
+                column.name(),
+                column.dtype(),
+                name,
+                dtype,
+            );
+        }
+    }
+    df.align_chunks_par();
+    annotated_arrow_schema::<T>()
+}
+
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+fn check_required(column: &str, array: &dyn Array, field: &ArrowField) -> PolarsResult<()> {
+    if !field.is_nullable && array.null_count() > 0 {
+        polars_bail!(
+            ComputeError:
+            "df-derive: column `{}` is declared non-nullable but contains nulls",
+            column,
+        );
+    }
+    if let ArrowDataType::LargeList(item) = &field.dtype
+        && let Some(list) = array.as_any().downcast_ref::<ListArray<i64>>()
+    {
+        check_required(column, list.values().as_ref(), item)?;
+    }
+    if let ArrowDataType::FixedSizeList(item, _) = &field.dtype
+        && let Some(list) = array.as_any().downcast_ref::<FixedSizeListArray>()
+    {
+        check_required(column, list.values().as_ref(), item)?;
+    }
+    Ok(())
+}
+
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+fn check_batch(batch: &RecordBatch, schema: &ArrowSchema) -> PolarsResult<()> {
+    for (array, field) in batch.arrays().iter().zip(schema.iter_values()) {
+        check_required(field.name.as_str(), array.as_ref(), field)?;
+    }
+    Ok(())
+}
+
+/// Write `df` as a Parquet file using [`annotated_arrow_schema`], so columns
+/// that cannot be null are written as `REQUIRED`.
+///
+/// `df` is typically the output of `T::to_dataframe` or
+/// `[T]::to_dataframe`; its chunks are aligned in place before writing.
+/// Returns the total size of the file in bytes.
+///
+/// # Errors
+/// Returns a `SchemaMismatch` error if `df` does not have `T`'s columns, a
+/// `ComputeError` if a non-nullable column contains nulls, and any error
+/// from the underlying writer.
+#[cfg(feature = "parquet")]
+pub fn write_parquet<T, W>(
+    df: &mut DataFrame,
+    writer: W,
+    compression: ParquetCompression,
+) -> PolarsResult<u64>
+where
+    T: ToDataFrame,
+    W: std::io::Write,
+{
+    use polars_parquet::write::{
+        FileWriter, RowGroupIterator, StatisticsOptions, Version, WriteOptions, get_dtype_encoding,
+    };
+
+    let schema = prepare_frame::<T>(df)?;
+    let options = WriteOptions {
+        statistics: StatisticsOptions::default(),
+        version: Version::V1,
+        compression,
+        data_page_size: None,
+    };
+    let encodings = schema
+        .iter_values()
+        .map(|field| get_dtype_encoding(&field.dtype))
+        .collect();
+    let batches = df.iter_chunks(CompatLevel::newest(), false).map(|batch| {
+        check_batch(&batch, &schema)?;
+        Ok(batch)
+    });
+    let row_groups = RowGroupIterator::try_new(batches, &schema, options, encodings)?;
+    let mut file = FileWriter::try_new(writer, schema.clone(), options)?;
+    for row_group in row_groups {
+        file.write(u64::MAX, row_group?)?;
+    }
+    file.end(None)
+}
+
+/// Write `df` as an Arrow IPC file using [`annotated_arrow_schema`], so the
+/// file schema records which columns cannot be null.
+///
+/// `df` is typically the output of `T::to_dataframe` or
+/// `[T]::to_dataframe`; its chunks are aligned in place before writing.
+///
+/// # Errors
+/// Returns a `SchemaMismatch` error if `df` does not have `T`'s columns, a
+/// `ComputeError` if a non-nullable column contains nulls, and any error
+/// from the underlying writer.
+#[cfg(feature = "ipc")]
+pub fn write_ipc<T, W>(df: &mut DataFrame, writer: W) -> PolarsResult<()>
+where
+    T: ToDataFrame,
+    W: std::io::Write,
+{
+    use polars_arrow::io::ipc::write::{FileWriter, WriteOptions};
+
+    let schema = prepare_frame::<T>(df)?;
+    let mut file = FileWriter::try_new(
+        writer,
+        std::sync::Arc::new(schema.clone()),
+        None,
+        WriteOptions { compression: None },
+    )?;
+    for batch in df.iter_chunks(CompatLevel::newest(), false) {
+        check_batch(&batch, &schema)?;
+        file.write(&batch, None)?;
+    }
+    file.finish()
+}
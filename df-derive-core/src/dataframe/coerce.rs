@@ -0,0 +1,261 @@
+//! Normalize an arbitrary `DataFrame` into a derived schema.
+//!
+//! [`coerce_dataframe`] (also reachable as `T::coerce_dataframe`) reorders
+//! columns into `T::schema()` order, casts columns whose dtype converts to the
+//! derived one without loss, fills missing nullable columns with nulls, and
+//! drops or rejects columns `T` does not derive. Columns of non-`Option`
+//! fields must hold no nulls once cast. The returned [`Coerced`] frame lists
+//! every [`Adjustment`] made; its `Display` impl prints them one per line.
+//!
+//! Safe casts are the lossless widenings [`super::schema_diff`] classifies as
+//! compatible, plus integer timestamps read as the derived temporal dtype:
+//! `Int64` (or narrower) into `Datetime` or `Duration`, and `Int32` (or
+//! narrower) into `Date`. The integers are taken as counts of the target
+//! dtype's unit (days for `Date`).
+
+use std::any::type_name;
+use std::fmt;
+use std::fmt::Write as _;
+
+use polars::prelude::{Column, DataFrame, DataType, PolarsResult, polars_bail};
+
+use super::ToDataFrame;
+use super::schema_diff::is_widening;
+use super::schema_export::column_descriptors;
+
+/// What to do with input columns `T` does not derive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoercePolicy {
+    /// Fail, listing the extra columns with every other problem.
+    #[default]
+    RejectExtraColumns,
+    /// Drop them and report each as [`Adjustment::Dropped`].
+    DropExtraColumns,
+}
+
+/// One change made while coercing a frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Adjustment {
+    /// The kept columns were moved into schema order.
+    Reordered,
+    /// The column was cast to its derived dtype.
+    Cast {
+        name: String,
+        from: DataType,
+        to: DataType,
+    },
+    /// The column was missing and is nullable, so it was filled with nulls.
+    FilledNulls { name: String, dtype: DataType },
+    /// The column is not derived and was dropped.
+    Dropped { name: String, dtype: DataType },
+}
+
+impl fmt::Display for Adjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reordered => write!(f, "reordered columns into schema order"),
+            Self::Cast { name, from, to } => {
+                write!(f, "cast `{name}` from {from:?} to {to:?}")
+            }
+            Self::FilledNulls { name, dtype } => {
+                write!(f, "filled missing `{name}` ({dtype:?}) with nulls")
+            }
+            Self::Dropped { name, dtype } => write!(f, "dropped `{name}` ({dtype:?})"),
+        }
+    }
+}
+
+/// A frame matching `T::schema()` and the adjustments made to get there.
+#[derive(Clone, Debug)]
+pub struct Coerced {
+    frame: DataFrame,
+    adjustments: Vec<Adjustment>,
+}
+
+impl Coerced {
+    #[must_use]
+    pub const fn frame(&self) -> &DataFrame {
+        &self.frame
+    }
+
+    #[must_use]
+    pub fn into_frame(self) -> DataFrame {
+        self.frame
+    }
+
+    /// Adjustments in the order they were made: casts and null fills in
+    /// schema order, then dropped columns, then reordering.
+    #[must_use]
+    pub fn adjustments(&self) -> &[Adjustment] {
+        &self.adjustments
+    }
+
+    /// Whether the input already matched the schema.
+    #[must_use]
+    pub const fn is_unchanged(&self) -> bool {
+        self.adjustments.is_empty()
+    }
+}
+
+impl fmt::Display for Coerced {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.adjustments.is_empty() {
+            return write!(f, "coerce: no adjustments");
+        }
+        write!(f, "coerce: {} adjustments", self.adjustments.len())?;
+        for adjustment in &self.adjustments {
+            write!(f, "\n  {adjustment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Coerce `frame` into `T`'s derived schema.
+///
+/// # Errors
+/// Returns a `SchemaMismatch` error listing every missing required column,
+/// column without a safe cast to its derived dtype, column of a non-`Option`
+/// field (or list element) holding nulls, and (under
+/// [`CoercePolicy::RejectExtraColumns`]) extra column. Returns an error if
+/// schema generation or a cast fails.
+pub fn coerce_dataframe<T: ToDataFrame>(
+    frame: &DataFrame,
+    policy: CoercePolicy,
+) -> PolarsResult<Coerced> {
+    let descriptors = column_descriptors::<T>()?;
+    let height = frame.height();
+    let mut adjustments = Vec::new();
+    let mut problems = String::new();
+    let mut columns = Vec::with_capacity(descriptors.len());
+    for descriptor in &descriptors {
+        let name = descriptor.name.as_str();
+        let Some(column) = frame.column(name).ok() else {
+            if descriptor.is_nullable() {
+                adjustments.push(Adjustment::FilledNulls {
+                    name: descriptor.name.clone(),
+                    dtype: descriptor.dtype.clone(),
+                });
+                columns.push(Column::full_null(name.into(), height, &descriptor.dtype));
+            } else {
+                let _ = write!(
+                    problems,
+                    "\n  missing required column `{name}` ({:?})",
+                    descriptor.dtype
+                );
+            }
+            continue;
+        };
+        let from = column.dtype();
+        let column = if from == &descriptor.dtype {
+            column.clone()
+        } else if is_safe_cast(from, &descriptor.dtype) {
+            adjustments.push(Adjustment::Cast {
+                name: descriptor.name.clone(),
+                from: from.clone(),
+                to: descriptor.dtype.clone(),
+            });
+            column.strict_cast(&descriptor.dtype)?
+        } else {
+            let _ = write!(
+                problems,
+                "\n  column `{name}` is {from:?}, which does not cast safely to {:?}",
+                descriptor.dtype
+            );
+            continue;
+        };
+        let nulls = required_nulls(&column, &descriptor.nullability)?;
+        if nulls > 0 {
+            let _ = write!(
+                problems,
+                "\n  column `{name}` of a non-`Option` field has {nulls} null(s)"
+            );
+        }
+        columns.push(column);
+    }
+
+    for column in frame.columns() {
+        let name = column.name().as_str();
+        if descriptors.iter().any(|descriptor| descriptor.name == name) {
+            continue;
+        }
+        match policy {
+            CoercePolicy::RejectExtraColumns => {
+                let _ = write!(
+                    problems,
+                    "\n  unexpected column `{name}` ({:?})",
+                    column.dtype()
+                );
+            }
+            CoercePolicy::DropExtraColumns => adjustments.push(Adjustment::Dropped {
+                name: name.to_owned(),
+                dtype: column.dtype().clone(),
+            }),
+        }
+    }
+    if !problems.is_empty() {
+        polars_bail!(
+            SchemaMismatch: "DataFrame cannot be coerced into `{}`:{problems}",
+            type_name::<T>()
+        );
+    }
+
+    let kept: Vec<&str> = frame
+        .columns()
+        .iter()
+        .map(|column| column.name().as_str())
+        .filter(|name| {
+            descriptors
+                .iter()
+                .any(|descriptor| descriptor.name == *name)
+        })
+        .collect();
+    let in_order = descriptors
+        .iter()
+        .map(|descriptor| descriptor.name.as_str())
+        .filter(|name| kept.contains(name))
+        .eq(kept.iter().copied());
+    if !in_order {
+        adjustments.push(Adjustment::Reordered);
+    }
+
+    Ok(Coerced {
+        frame: DataFrame::new(height, columns)?,
+        adjustments,
+    })
+}
+
+/// Nulls in `column` where `nullability` (the column's own, then each list
+/// layer's elements) says there can be none.
+fn required_nulls(column: &Column, nullability: &[bool]) -> PolarsResult<usize> {
+    let mut values = column.as_materialized_series().clone();
+    let mut nulls = 0;
+    for (layer, &nullable) in nullability.iter().enumerate() {
+        if layer > 0 {
+            values = match values.dtype() {
+                DataType::List(_) => values.list()?.get_inner(),
+                DataType::Array(..) => values.array()?.get_inner(),
+                _ => break,
+            };
+        }
+        if !nullable {
+            nulls += values.null_count();
+        }
+    }
+    Ok(nulls)
+}
+
+/// Whether `from` converts to `to` without loss, counting integers as
+/// timestamps in `to`'s unit.
+fn is_safe_cast(from: &DataType, to: &DataType) -> bool {
+    match (from, to) {
+        (DataType::List(from), DataType::List(to)) => is_safe_cast(from, to),
+        (DataType::Array(from, from_len), DataType::Array(to, to_len)) => {
+            from_len == to_len && is_safe_cast(from, to)
+        }
+        (from, DataType::Datetime(_, _) | DataType::Duration(_)) if from.is_integer() => {
+            is_widening(from, &DataType::Int64)
+        }
+        (from, DataType::Date) if from.is_integer() => is_widening(from, &DataType::Int32),
+        _ => is_widening(from, to),
+    }
+}
@@ -0,0 +1,114 @@
+//! Typed column handles generated by the derive.
+//!
+//! Derives targeting this runtime implement [`ColumnHandles`] and add an
+//! inherent `T::cols()` returning one accessor per column. Nested fields
+//! return the nested type's handles, so `Trade::cols().quotes().close()`
+//! names the flattened `quotes.close` column and a renamed field becomes a
+//! compile error instead of a missing-column error at query time.
+
+use std::fmt;
+
+#[cfg(feature = "lazy")]
+use polars::prelude::{Expr, col};
+
+/// The flattened name of one derived column.
+///
+/// With the `lazy` feature, [`ColumnRef::expr`] and `Expr::from` turn it into
+/// a Polars `col(...)` expression.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColumnRef {
+    name: String,
+}
+
+impl ColumnRef {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[cfg(feature = "lazy")]
+    pub fn expr(&self) -> Expr {
+        col(self.name.as_str())
+    }
+}
+
+impl AsRef<str> for ColumnRef {
+    fn as_ref(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for ColumnRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+#[cfg(feature = "lazy")]
+impl From<ColumnRef> for Expr {
+    fn from(column: ColumnRef) -> Self {
+        column.expr()
+    }
+}
+
+/// Types with generated column handles.
+pub trait ColumnHandles {
+    /// The generated handle namespace, with one accessor per column.
+    type Columns;
+
+    /// Handles for columns flattened under `prefix`; the empty prefix names
+    /// top-level columns.
+    fn columns_at(prefix: String) -> Self::Columns;
+}
+
+/// `()` contributes no columns, so its handles have no accessors.
+impl ColumnHandles for () {
+    type Columns = ();
+
+    fn columns_at(_prefix: String) -> Self::Columns {}
+}
+
+/// [`ColumnHandles`] keyed by the generated namespace's marker parameter.
+///
+/// Nested accessors are bounded on this trait rather than on
+/// `ColumnHandles` so the bound mentions a generic parameter. A nested type
+/// without handles (for example a manual `ToDataFrame` impl) then only
+/// disables that accessor instead of failing the deriving struct.
+#[doc(hidden)]
+pub trait NestedColumnHandles<Marker> {
+    type Columns;
+
+    fn nested_columns(prefix: String) -> Self::Columns;
+}
+
+impl<T: ColumnHandles + ?Sized, Marker> NestedColumnHandles<Marker> for T {
+    type Columns = T::Columns;
+
+    fn nested_columns(prefix: String) -> Self::Columns {
+        T::columns_at(prefix)
+    }
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_owned()
+    } else if name.is_empty() {
+        prefix.to_owned()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
+
+#[doc(hidden)]
+#[must_use]
+pub fn column_ref(prefix: &str, name: &str) -> ColumnRef {
+    ColumnRef {
+        name: join(prefix, name),
+    }
+}
+
+#[doc(hidden)]
+#[must_use]
+pub fn nested_column_prefix(prefix: &str, name: &str) -> String {
+    join(prefix, name)
+}
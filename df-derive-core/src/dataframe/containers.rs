@@ -0,0 +1,154 @@
+//! `ToDataFrame` / `Columnar` impls for the std wrappers a row type commonly
+//! sits behind, so `Vec<Arc<Trade>>` or `Vec<Option<Trade>>` converts without
+//! copying rows into a wrapper struct.
+//!
+//! `Box<T>`, `Arc<T>`, `Rc<T>`, `Cow<'_, T>`, and `&T` produce exactly `T`'s
+//! columns, so `[Arc<T>]`, `[&T]`, and the like convert through
+//! [`ToDataFrameVec`](super::ToDataFrameVec). A `[&T]` slice is handed to
+//! `T` as is; slices of owning pointers are re-collected into a `Vec<&T>`.
+//! `Option<T>` keeps `T`'s columns but marks every one nullable, and a `None`
+//! row is a row of nulls.
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use polars::prelude::{ArrowSchema, DataFrame, DataType, IdxCa, IdxSize, PolarsResult};
+
+use super::arrow::arrow_schema_from_fields;
+use super::{ColumnMetadata, Columnar, ToDataFrame, zero_column_dataframe_with_height};
+
+/// Implement both traits for a pointer type by dereferencing to `T`. `Cow`
+/// additionally needs `T: ToOwned`.
+macro_rules! delegating_payload {
+    ($($ty:ty $(where T: $bound:path)?;)*) => {$(
+        impl<T: ToDataFrame + ?Sized $(+ $bound)?> ToDataFrame for $ty {
+            fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+                T::to_dataframe(self)
+            }
+
+            fn empty_dataframe() -> PolarsResult<DataFrame> {
+                T::empty_dataframe()
+            }
+
+            fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+                T::schema()
+            }
+
+            fn arrow_schema() -> PolarsResult<ArrowSchema> {
+                T::arrow_schema()
+            }
+
+            fn column_metadata() -> PolarsResult<Vec<(String, ColumnMetadata)>> {
+                T::column_metadata()
+            }
+        }
+    )*};
+}
+
+delegating_payload! {
+    &T;
+    Box<T>;
+    Arc<T>;
+    Rc<T>;
+    Cow<'_, T> where T: ToOwned;
+}
+
+/// A slice of references is already the `&[&T]` the derive consumes.
+impl<T: Columnar> Columnar for &T {
+    fn columnar_to_dataframe(items: &[Self]) -> PolarsResult<DataFrame> {
+        T::columnar_from_refs(items)
+    }
+
+    fn columnar_from_refs(items: &[&Self]) -> PolarsResult<DataFrame> {
+        let refs: Vec<&T> = items.iter().map(|item| **item).collect();
+        T::columnar_from_refs(&refs)
+    }
+}
+
+/// Owning pointers collect one `Vec<&T>` of the pointees, whether called on
+/// `&[Self]` or `&[&Self]`.
+macro_rules! delegating_columnar {
+    ($($ty:ty $(where T: $bound:path)?;)*) => {$(
+        impl<T: Columnar $(+ $bound)?> Columnar for $ty {
+            fn columnar_to_dataframe(items: &[Self]) -> PolarsResult<DataFrame> {
+                let refs: Vec<&T> = items.iter().map(|item| &**item).collect();
+                T::columnar_from_refs(&refs)
+            }
+
+            fn columnar_from_refs(items: &[&Self]) -> PolarsResult<DataFrame> {
+                let refs: Vec<&T> = items.iter().map(|item| &***item).collect();
+                T::columnar_from_refs(&refs)
+            }
+        }
+    )*};
+}
+
+delegating_columnar! {
+    Box<T>;
+    Arc<T>;
+    Rc<T>;
+    Cow<'_, T> where T: ToOwned;
+}
+
+impl<T: ToDataFrame + Columnar> ToDataFrame for Option<T> {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.as_ref().map_or_else(
+            || Self::columnar_from_refs(&[self]),
+            ToDataFrame::to_dataframe,
+        )
+    }
+
+    fn empty_dataframe() -> PolarsResult<DataFrame> {
+        T::empty_dataframe()
+    }
+
+    fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+        T::schema()
+    }
+
+    fn arrow_schema() -> PolarsResult<ArrowSchema> {
+        let fields = T::arrow_schema()?
+            .into_iter()
+            .map(|(_, mut field)| {
+                field.is_nullable = true;
+                field
+            })
+            .collect();
+        Ok(arrow_schema_from_fields(fields))
+    }
+
+    fn column_metadata() -> PolarsResult<Vec<(String, ColumnMetadata)>> {
+        T::column_metadata()
+    }
+}
+
+impl<T: ToDataFrame + Columnar> Columnar for Option<T> {
+    fn columnar_from_refs(items: &[&Self]) -> PolarsResult<DataFrame> {
+        let present: Vec<&T> = items.iter().filter_map(|item| item.as_ref()).collect();
+        if present.len() == items.len() {
+            return T::columnar_from_refs(&present);
+        }
+        let frame = if present.is_empty() {
+            T::empty_dataframe()?
+        } else {
+            T::columnar_from_refs(&present)?
+        };
+        if frame.width() == 0 {
+            return zero_column_dataframe_with_height(items.len());
+        }
+        // Row `i` of the output takes the next present row, or null for `None`.
+        let mut next: IdxSize = 0;
+        let indices: IdxCa = items
+            .iter()
+            .map(|item| {
+                item.as_ref().map(|_| {
+                    let index = next;
+                    next += 1;
+                    index
+                })
+            })
+            .collect();
+        frame.take(&indices)
+    }
+}
@@ -0,0 +1,133 @@
+//! User-defined scalar column types.
+//!
+//! A type implementing [`DfLeaf`] is written as one column, like the built-in
+//! leaf types, by fields marked `#[df_derive(leaf)]`. The field keeps full
+//! wrapper support: `Option<T>` rows become nulls, and `Vec<T>`, sets, and
+//! arrays become list or array columns of the leaf dtype.
+//!
+//! ```ignore
+//! use df_derive::prelude::*;
+//! use polars::prelude::{DataType, PolarsResult};
+//!
+//! struct Ticks(i64);
+//!
+//! impl DfLeaf for Ticks {
+//!     type Value = i64;
+//!
+//!     fn dtype() -> DataType {
+//!         DataType::Int64
+//!     }
+//!
+//!     fn encode(&self) -> PolarsResult<i64> {
+//!         Ok(self.0)
+//!     }
+//! }
+//!
+//! #[derive(ToDataFrame)]
+//! struct Quote {
+//!     #[df_derive(leaf)]
+//!     bid: Option<Ticks>,
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+use polars::prelude::{
+    ArrowSchema, DataFrame, DataType, NamedFrom, PlSmallStr, PolarsResult, Series,
+};
+
+use super::ToDataFrame;
+use super::arrow::{arrow_schema_from_fields, required_arrow_field};
+use super::columns::{ColumnHandles, ColumnRef, column_ref};
+
+/// A type written as a single column by `#[df_derive(leaf)]` fields.
+///
+/// Each value is encoded into a [`DfLeaf::Value`], the encoded values are
+/// collected into a `Series`, and the `Series` is cast to
+/// [`DfLeaf::dtype`] when its dtype differs. A cast that would lose a value
+/// is an error rather than a null.
+pub trait DfLeaf {
+    /// Value pushed into the column per row. Any type Polars builds a
+    /// `Series` from works: `bool`, the integer and float types, `String`,
+    /// and, with the matching Polars features, the chrono types.
+    type Value;
+
+    /// Dtype of the column.
+    fn dtype() -> DataType;
+
+    /// Encode one value.
+    ///
+    /// # Errors
+    /// Returns an error for a value the column cannot represent; the error
+    /// fails the whole conversion.
+    fn encode(&self) -> PolarsResult<Self::Value>;
+}
+
+/// Type-level description of a `#[df_derive(leaf)]` field's column. Never
+/// constructed; generated code only calls its associated functions.
+pub struct LeafColumns<T: ?Sized>(PhantomData<fn(&T)>);
+
+/// `Columnar` for [`LeafColumns`]: converts the leaf values rather than
+/// values of the adapter type.
+pub trait LeafColumnar {
+    type Leaf: ?Sized;
+
+    /// # Errors
+    /// Returns an error if encoding a value or building the column fails.
+    fn columnar_from_refs(items: &[&Self::Leaf]) -> PolarsResult<DataFrame>;
+}
+
+impl<T> ToDataFrame for LeafColumns<T>
+where
+    T: DfLeaf + ?Sized,
+    Series: NamedFrom<Vec<T::Value>, [T::Value]>,
+{
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        unreachable!("`LeafColumns` is a type-level adapter and is never constructed")
+    }
+
+    fn empty_dataframe() -> PolarsResult<DataFrame> {
+        <Self as LeafColumnar>::columnar_from_refs(&[])
+    }
+
+    fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+        Ok(vec![(String::new(), T::dtype())])
+    }
+
+    fn arrow_schema() -> PolarsResult<ArrowSchema> {
+        Ok(arrow_schema_from_fields(vec![required_arrow_field(
+            &T::dtype(),
+        )]))
+    }
+}
+
+impl<T> LeafColumnar for LeafColumns<T>
+where
+    T: DfLeaf + ?Sized,
+    Series: NamedFrom<Vec<T::Value>, [T::Value]>,
+{
+    type Leaf = T;
+
+    fn columnar_from_refs(items: &[&T]) -> PolarsResult<DataFrame> {
+        let values = items
+            .iter()
+            .map(|item| item.encode())
+            .collect::<PolarsResult<Vec<T::Value>>>()?;
+        let series = Series::new(PlSmallStr::EMPTY, values);
+        let dtype = T::dtype();
+        let series = if series.dtype() == &dtype {
+            series
+        } else {
+            series.strict_cast(&dtype)?
+        };
+        DataFrame::new(items.len(), vec![series.into()])
+    }
+}
+
+impl<T: ?Sized> ColumnHandles for LeafColumns<T> {
+    type Columns = ColumnRef;
+
+    fn columns_at(prefix: String) -> Self::Columns {
+        column_ref(&prefix, "")
+    }
+}
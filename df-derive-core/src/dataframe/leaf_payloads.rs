@@ -0,0 +1,229 @@
+//! `ToDataFrame` / `Columnar` impls for the primitive leaf types, so a
+//! generic field instantiated with one (`Wrapper<f64>`, `Tagged<String>`)
+//! needs no single-field wrapper struct.
+//!
+//! Each impl reports one column with an empty name. Generated code names a
+//! nested column after the outer field alone when the inner name is empty, so
+//! `payload: T` with `T = f64` becomes a `payload` column rather than
+//! `payload.<something>`. Dtypes and conversions match what the derive emits
+//! for a field of the same type without attributes: `Datetime(ms)` for
+//! `DateTime<Tz>` / `NaiveDateTime`, `Duration(ns)`, and `Decimal(38, 10)`.
+//! Their [`ColumnHandles`] are the `ColumnRef` of that column, so
+//! `Wrapper::<f64>::cols().payload()` names the `payload` column.
+
+use polars::prelude::{
+    ArrowSchema, DataFrame, DataType, Int128Chunked, IntoSeries, NamedFrom, PlSmallStr,
+    PolarsResult, Series, TimeUnit, polars_err,
+};
+
+use super::arrow::{arrow_schema_from_fields, required_arrow_field};
+use super::columns::{ColumnHandles, ColumnRef, column_ref};
+use super::{Columnar, ToDataFrame};
+
+const DURATION: DataType = DataType::Duration(TimeUnit::Nanoseconds);
+#[cfg(feature = "rust_decimal")]
+const DECIMAL_PRECISION: u8 = 38;
+#[cfg(feature = "rust_decimal")]
+const DECIMAL_SCALE: u8 = 10;
+
+fn leaf_schema(dtype: DataType) -> Vec<(String, DataType)> {
+    vec![(String::new(), dtype)]
+}
+
+fn leaf_arrow_schema(dtype: &DataType) -> ArrowSchema {
+    arrow_schema_from_fields(vec![required_arrow_field(dtype)])
+}
+
+fn leaf_frame(series: Series) -> PolarsResult<DataFrame> {
+    DataFrame::new(series.len(), vec![series.into()])
+}
+
+/// Implement `ToDataFrame`, `Columnar`, and `ColumnHandles` for `$ty`, whose
+/// column has dtype `$dtype` and is built from the rows by
+/// `$series: fn(&[&$ty]) -> PolarsResult<Series>`.
+macro_rules! leaf_payload {
+    ($(impl$([$($generics:tt)*])? for $ty:ty => $dtype:expr, $series:expr;)*) => {$(
+        impl$(<$($generics)*>)? ToDataFrame for $ty {
+            fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+                <Self as Columnar>::columnar_from_refs(&[self])
+            }
+
+            fn empty_dataframe() -> PolarsResult<DataFrame> {
+                <Self as Columnar>::columnar_from_refs(&[])
+            }
+
+            fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+                Ok(leaf_schema($dtype))
+            }
+
+            fn arrow_schema() -> PolarsResult<ArrowSchema> {
+                Ok(leaf_arrow_schema(&$dtype))
+            }
+        }
+
+        impl$(<$($generics)*>)? Columnar for $ty {
+            fn columnar_from_refs(items: &[&Self]) -> PolarsResult<DataFrame> {
+                let series: fn(&[&Self]) -> PolarsResult<Series> = $series;
+                leaf_frame(series(items)?)
+            }
+        }
+
+        impl$(<$($generics)*>)? ColumnHandles for $ty {
+            type Columns = ColumnRef;
+
+            fn columns_at(prefix: String) -> Self::Columns {
+                column_ref(&prefix, "")
+            }
+        }
+    )*};
+}
+
+/// Column of copied values for the primitives Polars stores natively.
+macro_rules! native_payloads {
+    ($($ty:ty => $dtype:expr;)*) => {
+        leaf_payload! {$(
+            impl for $ty => $dtype, |items| {
+                let values: Vec<$ty> = items.iter().map(|value| **value).collect();
+                Ok(Series::new(PlSmallStr::EMPTY, values))
+            };
+        )*}
+    };
+}
+
+native_payloads! {
+    bool => DataType::Boolean;
+    i8 => DataType::Int8;
+    i16 => DataType::Int16;
+    i32 => DataType::Int32;
+    i64 => DataType::Int64;
+    u8 => DataType::UInt8;
+    u16 => DataType::UInt16;
+    u32 => DataType::UInt32;
+    u64 => DataType::UInt64;
+    f32 => DataType::Float32;
+    f64 => DataType::Float64;
+}
+
+fn physical<T, P>(
+    items: &[&T],
+    dtype: &DataType,
+    convert: impl Fn(&T) -> PolarsResult<P>,
+) -> PolarsResult<Series>
+where
+    Series: NamedFrom<Vec<P>, [P]>,
+{
+    let values = items
+        .iter()
+        .map(|value| convert(value))
+        .collect::<PolarsResult<Vec<P>>>()?;
+    Series::new(PlSmallStr::EMPTY, values).cast(dtype)
+}
+
+leaf_payload! {
+    impl for i128 => DataType::Int128, |items| {
+        let values: Vec<i128> = items.iter().map(|value| **value).collect();
+        Ok(Int128Chunked::from_vec(PlSmallStr::EMPTY, values).into_series())
+    };
+    impl for u128 => DataType::UInt128, |items| {
+        let values: Vec<u128> = items.iter().map(|value| **value).collect();
+        Ok(Series::new(PlSmallStr::EMPTY, values))
+    };
+    impl for isize => DataType::Int64, |items| {
+        physical(items, &DataType::Int64, |value| {
+            i64::try_from(*value).map_err(|_| polars_err!(ComputeError: "df-derive: isize value out of i64 range"))
+        })
+    };
+    impl for usize => DataType::UInt64, |items| {
+        physical(items, &DataType::UInt64, |value| {
+            u64::try_from(*value).map_err(|_| polars_err!(ComputeError: "df-derive: usize value out of u64 range"))
+        })
+    };
+    impl for String => DataType::String, |items| {
+        let values: Vec<&str> = items.iter().map(|value| value.as_str()).collect();
+        Ok(Series::new(PlSmallStr::EMPTY, values))
+    };
+    impl for std::time::Duration => DURATION, |items| {
+        physical(items, &DURATION, |value| {
+            i64::try_from(value.as_nanos()).map_err(|_| {
+                polars_err!(ComputeError: "df-derive: std::time::Duration value out of i64 ns range")
+            })
+        })
+    };
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_payloads {
+    use chrono::{
+        DateTime, Datelike as _, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone,
+        Timelike as _,
+    };
+    use polars::prelude::{
+        ArrowSchema, DataFrame, DataType, PolarsResult, Series, TimeUnit, polars_err,
+    };
+
+    use super::super::columns::{ColumnHandles, ColumnRef, column_ref};
+    use super::super::{Columnar, ToDataFrame};
+    use super::{DURATION, leaf_arrow_schema, leaf_frame, leaf_schema, physical};
+
+    const DATETIME: DataType = DataType::Datetime(TimeUnit::Milliseconds, None);
+    const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+    leaf_payload! {
+        impl[Tz: TimeZone] for DateTime<Tz> => DATETIME, |items| {
+            physical(items, &DATETIME, |value| Ok(value.timestamp_millis()))
+        };
+        impl for NaiveDateTime => DATETIME, |items| {
+            physical(items, &DATETIME, |value| Ok(value.and_utc().timestamp_millis()))
+        };
+        impl for NaiveDate => DataType::Date, |items| {
+            physical(items, &DataType::Date, |value| {
+                Ok(value.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE)
+            })
+        };
+        impl for NaiveTime => DataType::Time, |items| {
+            physical(items, &DataType::Time, |value| {
+                Ok(i64::from(value.num_seconds_from_midnight()) * 1_000_000_000
+                    + i64::from(value.nanosecond()))
+            })
+        };
+        impl for TimeDelta => DURATION, |items| {
+            physical(items, &DURATION, |value| {
+                value.num_nanoseconds().ok_or_else(|| {
+                    polars_err!(ComputeError: "df-derive: chrono::Duration value out of i64 ns range")
+                })
+            })
+        };
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+leaf_payload! {
+    impl for rust_decimal::Decimal => DataType::Decimal(usize::from(DECIMAL_PRECISION), usize::from(DECIMAL_SCALE)), |items| {
+        let values = items
+            .iter()
+            .map(|value| {
+                let scale = u32::from(DECIMAL_SCALE);
+                let mantissa = super::Decimal128Encode::try_to_i128_mantissa(*value, scale)
+                    .ok_or_else(|| polars_err!(
+                        ComputeError:
+                        "df-derive: decimal mantissa rescale to scale {} failed (overflow or precision loss)",
+                        scale
+                    ))?;
+                if mantissa.unsigned_abs() >= 10u128.pow(u32::from(DECIMAL_PRECISION)) {
+                    return Err(polars_err!(
+                        ComputeError:
+                        "df-derive: decimal mantissa {} exceeds declared precision {} for Decimal({}, {})",
+                        mantissa,
+                        DECIMAL_PRECISION,
+                        DECIMAL_PRECISION,
+                        DECIMAL_SCALE,
+                    ));
+                }
+                Ok(mantissa)
+            })
+            .collect::<PolarsResult<Vec<i128>>>()?;
+        Ok(Int128Chunked::from_vec(PlSmallStr::EMPTY, values)
+            .into_decimal_unchecked(usize::from(DECIMAL_PRECISION), usize::from(DECIMAL_SCALE))
+            .into_series())
+    };
+}
@@ -0,0 +1,382 @@
+//! `HashMap` / `BTreeMap` fields as list columns.
+//!
+//! A map field becomes one `List(Struct{key, value})` column with one list
+//! element per entry, or, with `#[df_derive(split_map)]`, a pair of
+//! `field.keys` / `field.values` list columns. A key or value type that
+//! produces a single unnamed column (the primitive leaf types) keeps that
+//! column's dtype; a derived struct becomes a `Struct` of its columns.
+//!
+//! `BTreeMap` entries come out in key order. `HashMap` entries come out in
+//! iteration order, which differs between runs, unless the field carries
+//! `#[df_derive(sort_keys)]`.
+//!
+//! `HashMap` and `BTreeMap` also implement `ToDataFrame` / `Columnar` with
+//! the default single-column layout, so a generic `payload: T` field can hold
+//! a map.
+
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+
+use polars::prelude::{
+    ArrowDataType, ArrowField, ArrowSchema, Column, CompatLevel, DataFrame, DataType, Field,
+    IntoSeries, LargeListArray, PlSmallStr, PolarsResult, Series, StructChunked, polars_err,
+};
+use polars_arrow::offset::OffsetsBuffer;
+
+use super::arrow::{LIST_ITEM_NAME, arrow_schema_from_fields};
+use super::columns::{ColumnHandles, ColumnRef, column_ref};
+use super::{Columnar, ToDataFrame};
+
+const KEY: &str = "key";
+const VALUE: &str = "value";
+const KEYS: &str = "keys";
+const VALUES: &str = "values";
+
+/// A map whose entries can be listed.
+pub trait MapEntries {
+    type Key;
+    type Value;
+
+    /// Append the entries to `out` in iteration order.
+    fn extend_entries<'a>(&'a self, out: &mut Vec<(&'a Self::Key, &'a Self::Value)>);
+}
+
+impl<K, V, S> MapEntries for HashMap<K, V, S> {
+    type Key = K;
+    type Value = V;
+
+    fn extend_entries<'a>(&'a self, out: &mut Vec<(&'a K, &'a V)>) {
+        out.extend(self.iter());
+    }
+}
+
+impl<K, V> MapEntries for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn extend_entries<'a>(&'a self, out: &mut Vec<(&'a K, &'a V)>) {
+        out.extend(self.iter());
+    }
+}
+
+/// Order in which one map's entries are written.
+pub trait EntryOrder<K> {
+    fn order<V>(entries: &mut [(&K, &V)]);
+}
+
+/// The map's own iteration order.
+pub enum IterationOrder {}
+
+/// Ascending key order, for `#[df_derive(sort_keys)]`.
+pub enum KeyOrder {}
+
+impl<K> EntryOrder<K> for IterationOrder {
+    fn order<V>(_entries: &mut [(&K, &V)]) {}
+}
+
+impl<K: Ord> EntryOrder<K> for KeyOrder {
+    fn order<V>(entries: &mut [(&K, &V)]) {
+        entries.sort_unstable_by(|left, right| left.0.cmp(right.0));
+    }
+}
+
+/// Columns a map field is written as.
+pub trait EntryLayout {
+    fn schema(key: DataType, value: DataType) -> Vec<(String, DataType)>;
+    fn arrow_fields(key: ArrowField, value: ArrowField) -> Vec<ArrowField>;
+    fn columns(keys: Series, values: Series, offsets: Vec<i64>) -> PolarsResult<Vec<Column>>;
+}
+
+/// One unnamed `List(Struct{key, value})` column.
+pub enum StructEntries {}
+
+/// `keys` and `values` list columns, for `#[df_derive(split_map)]`.
+pub enum SplitEntries {}
+
+impl EntryLayout for StructEntries {
+    fn schema(key: DataType, value: DataType) -> Vec<(String, DataType)> {
+        let entry = DataType::Struct(vec![
+            Field::new(PlSmallStr::from_static(KEY), key),
+            Field::new(PlSmallStr::from_static(VALUE), value),
+        ]);
+        vec![(String::new(), DataType::List(Box::new(entry)))]
+    }
+
+    fn arrow_fields(key: ArrowField, value: ArrowField) -> Vec<ArrowField> {
+        let entry = ArrowDataType::Struct(vec![renamed(key, KEY), renamed(value, VALUE)]);
+        let entry = ArrowField::new(PlSmallStr::EMPTY, entry, false);
+        vec![list_arrow_field(PlSmallStr::EMPTY, entry)]
+    }
+
+    fn columns(keys: Series, values: Series, offsets: Vec<i64>) -> PolarsResult<Vec<Column>> {
+        let keys = keys.with_name(PlSmallStr::from_static(KEY));
+        let values = values.with_name(PlSmallStr::from_static(VALUE));
+        let entries = StructChunked::from_series(
+            PlSmallStr::EMPTY,
+            keys.len(),
+            [&keys, &values].into_iter(),
+        )?
+        .into_series();
+        Ok(vec![
+            list_series(PlSmallStr::EMPTY, &entries, offsets)?.into(),
+        ])
+    }
+}
+
+impl EntryLayout for SplitEntries {
+    fn schema(key: DataType, value: DataType) -> Vec<(String, DataType)> {
+        vec![
+            (KEYS.to_owned(), DataType::List(Box::new(key))),
+            (VALUES.to_owned(), DataType::List(Box::new(value))),
+        ]
+    }
+
+    fn arrow_fields(key: ArrowField, value: ArrowField) -> Vec<ArrowField> {
+        vec![
+            list_arrow_field(PlSmallStr::from_static(KEYS), key),
+            list_arrow_field(PlSmallStr::from_static(VALUES), value),
+        ]
+    }
+
+    fn columns(keys: Series, values: Series, offsets: Vec<i64>) -> PolarsResult<Vec<Column>> {
+        Ok(vec![
+            list_series(PlSmallStr::from_static(KEYS), &keys, offsets.clone())?.into(),
+            list_series(PlSmallStr::from_static(VALUES), &values, offsets)?.into(),
+        ])
+    }
+}
+
+/// Type-level description of how a map field is written: the map type, the
+/// entry order, and the column layout. Never constructed; generated code only
+/// calls its associated functions.
+pub struct MapColumns<M, O, L>(PhantomData<fn() -> M>, PhantomData<(O, L)>);
+
+/// `Columnar` for [`MapColumns`]: converts the maps themselves rather than
+/// values of the adapter type.
+pub trait MapColumnar {
+    type Map;
+
+    /// # Errors
+    /// Returns an error if `DataFrame` construction fails.
+    fn columnar_from_refs(items: &[&Self::Map]) -> PolarsResult<DataFrame>;
+}
+
+impl<M, O, L> ToDataFrame for MapColumns<M, O, L>
+where
+    M: MapEntries,
+    M::Key: ToDataFrame + Columnar,
+    M::Value: ToDataFrame + Columnar,
+    O: EntryOrder<M::Key>,
+    L: EntryLayout,
+{
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        unreachable!("`MapColumns` is a type-level adapter and is never constructed")
+    }
+
+    fn empty_dataframe() -> PolarsResult<DataFrame> {
+        <Self as MapColumnar>::columnar_from_refs(&[])
+    }
+
+    fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+        Ok(L::schema(
+            side_dtype::<M::Key>()?,
+            side_dtype::<M::Value>()?,
+        ))
+    }
+
+    fn arrow_schema() -> PolarsResult<ArrowSchema> {
+        Ok(arrow_schema_from_fields(L::arrow_fields(
+            side_arrow_field::<M::Key>()?,
+            side_arrow_field::<M::Value>()?,
+        )))
+    }
+}
+
+impl<M, O, L> MapColumnar for MapColumns<M, O, L>
+where
+    M: MapEntries,
+    M::Key: ToDataFrame + Columnar,
+    M::Value: ToDataFrame + Columnar,
+    O: EntryOrder<M::Key>,
+    L: EntryLayout,
+{
+    type Map = M;
+
+    fn columnar_from_refs(items: &[&M]) -> PolarsResult<DataFrame> {
+        let mut entries = Vec::new();
+        let mut offsets = Vec::with_capacity(items.len() + 1);
+        offsets.push(0);
+        for map in items {
+            let start = entries.len();
+            map.extend_entries(&mut entries);
+            O::order(&mut entries[start..]);
+            offsets.push(i64::try_from(entries.len()).map_err(
+                |_| polars_err!(ComputeError: "df-derive: map entry count overflows i64"),
+            )?);
+        }
+        let (keys, values): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+        let columns = L::columns(side_series(&keys)?, side_series(&values)?, offsets)?;
+        DataFrame::new(items.len(), columns)
+    }
+}
+
+impl<M, O> ColumnHandles for MapColumns<M, O, StructEntries> {
+    type Columns = ColumnRef;
+
+    fn columns_at(prefix: String) -> Self::Columns {
+        column_ref(&prefix, "")
+    }
+}
+
+impl<M, O> ColumnHandles for MapColumns<M, O, SplitEntries> {
+    type Columns = SplitMapColumns;
+
+    fn columns_at(prefix: String) -> Self::Columns {
+        SplitMapColumns { prefix }
+    }
+}
+
+/// Column handles of a `#[df_derive(split_map)]` field.
+pub struct SplitMapColumns {
+    prefix: String,
+}
+
+impl SplitMapColumns {
+    /// The `keys` list column.
+    #[must_use]
+    pub fn keys(&self) -> ColumnRef {
+        column_ref(&self.prefix, KEYS)
+    }
+
+    /// The `values` list column.
+    #[must_use]
+    pub fn values(&self) -> ColumnRef {
+        column_ref(&self.prefix, VALUES)
+    }
+}
+
+/// Dtype of one side of the entries: the column of a single-unnamed-column
+/// type, otherwise a `Struct` of the type's columns.
+fn side_dtype<T: ToDataFrame>() -> PolarsResult<DataType> {
+    let mut schema = T::schema()?;
+    if let [(name, _)] = schema.as_slice()
+        && name.is_empty()
+    {
+        return Ok(schema.remove(0).1);
+    }
+    Ok(DataType::Struct(
+        schema
+            .into_iter()
+            .map(|(name, dtype)| Field::new(name.into(), dtype))
+            .collect(),
+    ))
+}
+
+/// Arrow counterpart of [`side_dtype`], keeping the type's nullability.
+fn side_arrow_field<T: ToDataFrame>() -> PolarsResult<ArrowField> {
+    let schema = T::arrow_schema()?;
+    let mut fields: Vec<ArrowField> = schema.into_iter().map(|(_, field)| field).collect();
+    if let [field] = fields.as_slice()
+        && field.name.is_empty()
+    {
+        return Ok(fields.remove(0));
+    }
+    for field in &mut fields {
+        field.is_nullable = true;
+    }
+    Ok(ArrowField::new(
+        PlSmallStr::EMPTY,
+        ArrowDataType::Struct(fields),
+        false,
+    ))
+}
+
+fn side_series<T: ToDataFrame + Columnar>(items: &[&T]) -> PolarsResult<Series> {
+    let frame = if items.is_empty() {
+        T::empty_dataframe()?
+    } else {
+        T::columnar_from_refs(items)?
+    };
+    let height = frame.height();
+    let columns = frame.into_columns();
+    if let [column] = columns.as_slice()
+        && column.name().is_empty()
+    {
+        return Ok(column.as_materialized_series().clone());
+    }
+    Ok(StructChunked::from_columns(PlSmallStr::EMPTY, height, &columns)?.into_series())
+}
+
+/// Wrap `values` in a list column split at `offsets`.
+fn list_series(name: PlSmallStr, values: &Series, offsets: Vec<i64>) -> PolarsResult<Series> {
+    let dtype = DataType::List(Box::new(values.dtype().clone()));
+    let values = values.rechunk();
+    let array = values.to_arrow(0, CompatLevel::newest());
+    let list = LargeListArray::new(
+        LargeListArray::default_datatype(array.dtype().clone()),
+        OffsetsBuffer::try_from(offsets)?,
+        array,
+        None,
+    );
+    let series = Series::from_arrow(name, Box::new(list))?;
+    if series.dtype() == &dtype {
+        Ok(series)
+    } else {
+        series.cast(&dtype)
+    }
+}
+
+/// Required `LargeList` field named `name` whose elements are `item`.
+fn list_arrow_field(name: PlSmallStr, item: ArrowField) -> ArrowField {
+    let item = renamed(item, LIST_ITEM_NAME);
+    ArrowField::new(name, ArrowDataType::LargeList(Box::new(item)), false)
+}
+
+fn renamed(mut field: ArrowField, name: &'static str) -> ArrowField {
+    field.name = PlSmallStr::from_static(name);
+    field
+}
+
+/// Implement both traits for a map type with the default layout.
+macro_rules! map_payload {
+    ($(impl[$($generics:tt)*] for $ty:ty;)*) => {$(
+        impl<$($generics)*> ToDataFrame for $ty
+        where
+            K: ToDataFrame + Columnar,
+            V: ToDataFrame + Columnar,
+        {
+            fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+                <Self as Columnar>::columnar_from_refs(&[self])
+            }
+
+            fn empty_dataframe() -> PolarsResult<DataFrame> {
+                <Self as Columnar>::columnar_from_refs(&[])
+            }
+
+            fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+                DefaultColumns::<Self>::schema()
+            }
+
+            fn arrow_schema() -> PolarsResult<ArrowSchema> {
+                DefaultColumns::<Self>::arrow_schema()
+            }
+        }
+
+        impl<$($generics)*> Columnar for $ty
+        where
+            K: ToDataFrame + Columnar,
+            V: ToDataFrame + Columnar,
+        {
+            fn columnar_from_refs(items: &[&Self]) -> PolarsResult<DataFrame> {
+                <DefaultColumns<Self> as MapColumnar>::columnar_from_refs(items)
+            }
+        }
+    )*};
+}
+
+type DefaultColumns<M> = MapColumns<M, IterationOrder, StructEntries>;
+
+map_payload! {
+    impl[K, V, S] for HashMap<K, V, S>;
+    impl[K, V] for BTreeMap<K, V>;
+}
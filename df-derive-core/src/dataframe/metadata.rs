@@ -0,0 +1,51 @@
+//! Per-column metadata collected from the deriving struct's source.
+//!
+//! Derived impls report a field's `///` doc comment as `description`, plus
+//! any `#[df_derive(description = "...", unit = "...")]` or
+//! `#[df_derive(metadata(key = "value"))]` entries. Columns without metadata
+//! are omitted from [`ToDataFrame::column_metadata`](super::ToDataFrame::column_metadata).
+
+use std::collections::BTreeMap;
+
+use polars::prelude::DataType;
+
+use super::columns::nested_column_prefix;
+
+/// Metadata keys and values for one column.
+pub type ColumnMetadata = BTreeMap<String, String>;
+
+#[doc(hidden)]
+#[must_use]
+pub fn column_metadata_entry(name: &str, entries: &[(&str, &str)]) -> (String, ColumnMetadata) {
+    let metadata = entries
+        .iter()
+        .map(|&(key, value)| (key.to_owned(), value.to_owned()))
+        .collect();
+    (name.to_owned(), metadata)
+}
+
+/// Metadata for the flattened columns of a nested field.
+///
+/// Each inner column starts from the outer field's `entries` and is then
+/// overlaid with the inner type's own metadata for that column, so the
+/// innermost declaration wins on key collisions.
+#[doc(hidden)]
+#[must_use]
+pub fn nested_column_metadata(
+    prefix: &str,
+    entries: &[(&str, &str)],
+    inner_schema: Vec<(String, DataType)>,
+    inner_metadata: Vec<(String, ColumnMetadata)>,
+) -> Vec<(String, ColumnMetadata)> {
+    let mut inner_metadata: BTreeMap<String, ColumnMetadata> = inner_metadata.into_iter().collect();
+    inner_schema
+        .into_iter()
+        .filter_map(|(inner_name, _)| {
+            let (_, mut metadata) = column_metadata_entry(&inner_name, entries);
+            if let Some(inner) = inner_metadata.remove(&inner_name) {
+                metadata.extend(inner);
+            }
+            (!metadata.is_empty()).then(|| (nested_column_prefix(prefix, &inner_name), metadata))
+        })
+        .collect()
+}
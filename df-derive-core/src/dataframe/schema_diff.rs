@@ -0,0 +1,388 @@
+//! Compare a derived schema with a stored one.
+//!
+//! [`SchemaDiff::against`] compares `T::schema()` with an arbitrary Polars
+//! [`Schema`], such as one read from a file; [`SchemaDiff::between`] compares
+//! two derived types. Every [`ColumnChange`] is classified as
+//! [`Compatibility::Compatible`] when data stored under the old schema can
+//! still be read as the new one, or [`Compatibility::Breaking`] otherwise.
+//! The `Display` impl prints a one-line-per-change report.
+
+use std::fmt;
+
+use polars::prelude::{DataType, PolarsResult, Schema, TimeUnit, polars_bail};
+
+use super::ToDataFrame;
+use super::schema_export::column_descriptors;
+
+/// Whether a change keeps stored data readable under the new schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compatibility {
+    Compatible,
+    Breaking,
+}
+
+/// One difference between a stored (old) schema and a new one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColumnChange {
+    /// Present only in the new schema. Compatible when the column is
+    /// nullable, because readers can fill it with nulls.
+    Added {
+        name: String,
+        dtype: DataType,
+        nullable: bool,
+    },
+    /// Present only in the stored schema. Readers ignore it.
+    Removed { name: String, dtype: DataType },
+    /// A removed and an added column that share a dtype no other removed or
+    /// added column has, most likely a renamed field. Breaking, because the
+    /// stored values would not be read into the new column.
+    RenameCandidate {
+        from: String,
+        to: String,
+        dtype: DataType,
+    },
+    /// The dtype changed to one that holds every stored value.
+    Widened {
+        name: String,
+        from: DataType,
+        to: DataType,
+    },
+    /// The dtype changed in a way that can truncate or reject stored values.
+    DtypeChanged {
+        name: String,
+        from: DataType,
+        to: DataType,
+    },
+    /// A column that could hold nulls is now required.
+    NowRequired { name: String },
+    /// A required column may now hold nulls.
+    NowNullable { name: String },
+}
+
+impl ColumnChange {
+    #[must_use]
+    pub const fn compatibility(&self) -> Compatibility {
+        match self {
+            Self::Added { nullable: true, .. }
+            | Self::Removed { .. }
+            | Self::Widened { .. }
+            | Self::NowNullable { .. } => Compatibility::Compatible,
+            Self::Added {
+                nullable: false, ..
+            }
+            | Self::RenameCandidate { .. }
+            | Self::DtypeChanged { .. }
+            | Self::NowRequired { .. } => Compatibility::Breaking,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_breaking(&self) -> bool {
+        matches!(self.compatibility(), Compatibility::Breaking)
+    }
+}
+
+impl fmt::Display for ColumnChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added {
+                name,
+                dtype,
+                nullable,
+            } => {
+                let nullability = if *nullable { "nullable" } else { "required" };
+                write!(f, "added `{name}` ({dtype:?}, {nullability})")
+            }
+            Self::Removed { name, dtype } => write!(f, "removed `{name}` ({dtype:?})"),
+            Self::RenameCandidate { from, to, dtype } => {
+                write!(f, "possibly renamed `{from}` -> `{to}` ({dtype:?})")
+            }
+            Self::Widened { name, from, to } => {
+                write!(f, "widened `{name}` from {from:?} to {to:?}")
+            }
+            Self::DtypeChanged { name, from, to } => {
+                write!(f, "changed `{name}` from {from:?} to {to:?}")
+            }
+            Self::NowRequired { name } => write!(f, "`{name}` is now required"),
+            Self::NowNullable { name } => write!(f, "`{name}` is now nullable"),
+        }
+    }
+}
+
+/// The classified differences between a stored schema and a new one, in
+/// new-schema column order followed by removed columns.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    changes: Vec<ColumnChange>,
+}
+
+struct DiffColumn {
+    name: String,
+    dtype: DataType,
+    /// `None` when the schema cannot express nullability (a Polars `Schema`).
+    nullable: Option<bool>,
+}
+
+fn derived_columns<T: ToDataFrame>() -> PolarsResult<Vec<DiffColumn>> {
+    Ok(column_descriptors::<T>()?
+        .into_iter()
+        .map(|column| DiffColumn {
+            nullable: Some(column.is_nullable()),
+            name: column.name,
+            dtype: column.dtype,
+        })
+        .collect())
+}
+
+impl SchemaDiff {
+    /// Compare a stored `schema` with `T`'s derived schema.
+    ///
+    /// A Polars `Schema` carries no nullability, so only `T`'s side of
+    /// nullability is known: added columns are classified by it, and
+    /// [`ColumnChange::NowRequired`] / [`ColumnChange::NowNullable`] are
+    /// never reported.
+    ///
+    /// # Errors
+    /// Returns an error if `T`'s schema generation fails.
+    pub fn against<T: ToDataFrame>(stored: &Schema) -> PolarsResult<Self> {
+        let old: Vec<DiffColumn> = stored
+            .iter()
+            .map(|(name, dtype)| DiffColumn {
+                name: name.to_string(),
+                dtype: dtype.clone(),
+                nullable: None,
+            })
+            .collect();
+        Ok(Self::diff(&old, &derived_columns::<T>()?))
+    }
+
+    /// Compare the schema derived for `Old` with the one derived for `New`,
+    /// including column nullability.
+    ///
+    /// # Errors
+    /// Returns an error if either type's schema generation fails.
+    pub fn between<Old: ToDataFrame, New: ToDataFrame>() -> PolarsResult<Self> {
+        Ok(Self::diff(
+            &derived_columns::<Old>()?,
+            &derived_columns::<New>()?,
+        ))
+    }
+
+    fn diff(old: &[DiffColumn], new: &[DiffColumn]) -> Self {
+        let mut changes = Vec::new();
+        let mut added = Vec::new();
+        for column in new {
+            let Some(stored) = old.iter().find(|stored| stored.name == column.name) else {
+                added.push(column);
+                continue;
+            };
+            if stored.dtype != column.dtype {
+                let (name, from, to) = (
+                    column.name.clone(),
+                    stored.dtype.clone(),
+                    column.dtype.clone(),
+                );
+                changes.push(if is_widening(&from, &to) {
+                    ColumnChange::Widened { name, from, to }
+                } else {
+                    ColumnChange::DtypeChanged { name, from, to }
+                });
+            }
+            match (stored.nullable, column.nullable) {
+                (Some(true), Some(false)) => changes.push(ColumnChange::NowRequired {
+                    name: column.name.clone(),
+                }),
+                (Some(false), Some(true)) => changes.push(ColumnChange::NowNullable {
+                    name: column.name.clone(),
+                }),
+                _ => {}
+            }
+        }
+
+        let mut removed: Vec<&DiffColumn> = old
+            .iter()
+            .filter(|stored| new.iter().all(|column| column.name != stored.name))
+            .collect();
+        for column in &added {
+            let same_dtype_added = added
+                .iter()
+                .filter(|other| other.dtype == column.dtype)
+                .count();
+            let mut candidates = removed
+                .iter()
+                .enumerate()
+                .filter(|(_, stored)| stored.dtype == column.dtype)
+                .map(|(index, _)| index);
+            let rename = match (candidates.next(), candidates.next()) {
+                (Some(index), None) if same_dtype_added == 1 => Some(removed.remove(index)),
+                _ => None,
+            };
+            changes.push(rename.map_or_else(
+                || ColumnChange::Added {
+                    name: column.name.clone(),
+                    dtype: column.dtype.clone(),
+                    nullable: column.nullable.unwrap_or(true),
+                },
+                |stored| ColumnChange::RenameCandidate {
+                    from: stored.name.clone(),
+                    to: column.name.clone(),
+                    dtype: column.dtype.clone(),
+                },
+            ));
+        }
+        changes.extend(removed.into_iter().map(|stored| ColumnChange::Removed {
+            name: stored.name.clone(),
+            dtype: stored.dtype.clone(),
+        }));
+        Self { changes }
+    }
+
+    #[must_use]
+    pub fn changes(&self) -> &[ColumnChange] {
+        &self.changes
+    }
+
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &ColumnChange> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+
+    /// Whether the two schemas are identical.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether data stored under the old schema is readable as the new one.
+    #[must_use]
+    pub fn is_compatible(&self) -> bool {
+        self.breaking_changes().next().is_none()
+    }
+
+    /// Fail with the full report when any change is breaking.
+    ///
+    /// # Errors
+    /// Returns a `SchemaMismatch` error listing every change if at least one
+    /// of them is breaking.
+    pub fn ensure_compatible(&self) -> PolarsResult<()> {
+        if !self.is_compatible() {
+            polars_bail!(SchemaMismatch: "df-derive: incompatible schema change\n{}", self);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "schema diff: no changes");
+        }
+        let breaking = self.breaking_changes().count();
+        write!(
+            f,
+            "schema diff: {breaking} breaking, {} compatible",
+            self.changes.len() - breaking
+        )?;
+        for change in &self.changes {
+            let label = if change.is_breaking() {
+                "breaking  "
+            } else {
+                "compatible"
+            };
+            write!(f, "\n  [{label}] {change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `(signed, bits)` for integer dtypes.
+const fn integer_width(dtype: &DataType) -> Option<(bool, u32)> {
+    Some(match dtype {
+        DataType::Int8 => (true, 8),
+        DataType::Int16 => (true, 16),
+        DataType::Int32 => (true, 32),
+        DataType::Int64 => (true, 64),
+        DataType::Int128 => (true, 128),
+        DataType::UInt8 => (false, 8),
+        DataType::UInt16 => (false, 16),
+        DataType::UInt32 => (false, 32),
+        DataType::UInt64 => (false, 64),
+        DataType::UInt128 => (false, 128),
+        _ => return None,
+    })
+}
+
+/// Decimal digits needed for every value of an integer dtype.
+const fn integer_digits(signed: bool, bits: u32) -> usize {
+    match (signed, bits) {
+        (_, 8) => 3,
+        (_, 16) => 5,
+        (_, 32) => 10,
+        (true, 64) => 19,
+        (false, 64) => 20,
+        _ => 39,
+    }
+}
+
+/// Datetime units ordered from coarsest to finest. Nanoseconds are excluded
+/// from widening: their `i64` range ends in 2262, so converting coarser
+/// timestamps to them can overflow.
+const fn lossless_unit_rank(unit: TimeUnit) -> Option<u8> {
+    match unit {
+        TimeUnit::Milliseconds => Some(0),
+        TimeUnit::Microseconds => Some(1),
+        TimeUnit::Nanoseconds => None,
+    }
+}
+
+fn is_finer_unit(from: TimeUnit, to: TimeUnit) -> bool {
+    from == to
+        || matches!(
+            (lossless_unit_rank(from), lossless_unit_rank(to)),
+            (Some(from), Some(to)) if from <= to
+        )
+}
+
+/// Whether every value of `from` converts to `to` without loss.
+pub(super) fn is_widening(from: &DataType, to: &DataType) -> bool {
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (DataType::Null, _) | (DataType::Float32, DataType::Float64) => true,
+        (DataType::List(from), DataType::List(to)) => is_widening(from, to),
+        (DataType::Array(from, from_len), DataType::Array(to, to_len)) => {
+            from_len == to_len && is_widening(from, to)
+        }
+        (DataType::Datetime(from_unit, from_tz), DataType::Datetime(to_unit, to_tz)) => {
+            from_tz == to_tz && is_finer_unit(*from_unit, *to_unit)
+        }
+        (DataType::Duration(from_unit), DataType::Duration(to_unit)) => {
+            is_finer_unit(*from_unit, *to_unit)
+        }
+        (
+            DataType::Decimal(from_precision, from_scale),
+            DataType::Decimal(to_precision, to_scale),
+        ) => to_scale >= from_scale && to_precision - to_scale >= from_precision - from_scale,
+        _ => match (integer_width(from), integer_width(to)) {
+            (Some((from_signed, from_bits)), Some((to_signed, to_bits))) => {
+                match (from_signed, to_signed) {
+                    (true, true) | (false, false) => to_bits >= from_bits,
+                    (false, true) => to_bits > from_bits,
+                    (true, false) => false,
+                }
+            }
+            (Some((signed, bits)), None) => {
+                let value_bits = if signed { bits - 1 } else { bits };
+                match to {
+                    DataType::Float32 => value_bits <= 24,
+                    DataType::Float64 => value_bits <= 53,
+                    DataType::Decimal(precision, scale) => {
+                        integer_digits(signed, bits) <= precision - scale
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        },
+    }
+}
@@ -0,0 +1,547 @@
+//! Render a derived schema as SQL DDL, JSON Schema, or Avro.
+//!
+//! Every renderer works from [`column_descriptors`], which pairs each
+//! `T::schema()` entry with the nullability reported by
+//! `T::arrow_schema()` and the `description` from `T::column_metadata()`.
+//!
+//! - [`sql_ddl`] emits a `CREATE TABLE` statement for a [`SqlDialect`].
+//!   Columns keep their flattened names (`quotes.close`).
+//! - [`json_schema`] and [`avro_schema`] rebuild the nesting implied by the
+//!   dotted names, so `quotes.close` becomes property `close` of object
+//!   `quotes`.
+
+use std::fmt::Write as _;
+
+use polars::prelude::{ArrowDataType, DataType, PolarsResult, TimeUnit, polars_bail};
+
+use super::ToDataFrame;
+
+/// One generated column: its name, Polars dtype, nullability, and
+/// description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnDescriptor {
+    pub name: String,
+    pub dtype: DataType,
+    /// Whether the column itself can be null, followed by the element
+    /// nullability of each list layer, outermost first.
+    pub nullability: Vec<bool>,
+    pub description: Option<String>,
+}
+
+impl ColumnDescriptor {
+    /// Whether the column itself can be null.
+    #[must_use]
+    pub fn is_nullable(&self) -> bool {
+        self.nullability.first().copied().unwrap_or(true)
+    }
+}
+
+/// Column descriptors for `T`, in `schema()` order.
+///
+/// # Errors
+/// Returns an error if schema generation fails, or if `T::arrow_schema()`
+/// does not list the same columns as `T::schema()`.
+pub fn column_descriptors<T: ToDataFrame>() -> PolarsResult<Vec<ColumnDescriptor>> {
+    let schema = T::schema()?;
+    let arrow_schema = T::arrow_schema()?;
+    if arrow_schema.len() != schema.len() {
+        polars_bail!(
+            SchemaMismatch:
+            "df-derive: arrow_schema() has {} fields but schema() has {} columns",
+            arrow_schema.len(),
+            schema.len(),
+        );
+    }
+    let mut metadata = T::column_metadata()?;
+    schema
+        .into_iter()
+        .zip(arrow_schema.iter_values())
+        .map(|((name, dtype), field)| {
+            if field.name.as_str() != name {
+                polars_bail!(
+                    SchemaMismatch:
+                    "df-derive: arrow_schema() field `{}` does not match schema() column `{}`",
+                    field.name,
+                    name,
+                );
+            }
+            let mut nullability = vec![field.is_nullable];
+            let mut item_dtype = &field.dtype;
+            while let ArrowDataType::LargeList(item)
+            | ArrowDataType::List(item)
+            | ArrowDataType::FixedSizeList(item, _) = item_dtype
+            {
+                nullability.push(item.is_nullable);
+                item_dtype = &item.dtype;
+            }
+            let description = metadata
+                .iter_mut()
+                .find(|(column, _)| *column == name)
+                .and_then(|(_, entries)| entries.remove("description"));
+            Ok(ColumnDescriptor {
+                name,
+                dtype,
+                nullability,
+                description,
+            })
+        })
+        .collect()
+}
+
+/// SQL dialect targeted by [`sql_ddl`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    DuckDb,
+    ClickHouse,
+}
+
+impl SqlDialect {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Postgres => "Postgres",
+            Self::DuckDb => "DuckDB",
+            Self::ClickHouse => "ClickHouse",
+        }
+    }
+
+    fn quote_ident(self, ident: &str) -> String {
+        match self {
+            Self::Postgres | Self::DuckDb => format!("\"{}\"", ident.replace('"', "\"\"")),
+            Self::ClickHouse => format!("`{}`", ident.replace('\\', "\\\\").replace('`', "\\`")),
+        }
+    }
+
+    #[allow(clippy::match_same_arms)]
+    fn scalar_type(self, dtype: &DataType) -> PolarsResult<String> {
+        let ty = match (self, dtype) {
+            (Self::Postgres | Self::DuckDb, DataType::Boolean) => "BOOLEAN",
+            (Self::Postgres, DataType::Int8 | DataType::Int16 | DataType::UInt8) => "SMALLINT",
+            (Self::Postgres, DataType::Int32 | DataType::UInt16) => "INTEGER",
+            (Self::Postgres, DataType::Int64 | DataType::UInt32) => "BIGINT",
+            (Self::Postgres, DataType::UInt64) => "NUMERIC(20, 0)",
+            (Self::Postgres, DataType::Int128 | DataType::UInt128) => "NUMERIC(39, 0)",
+            (Self::Postgres, DataType::Float32) => "REAL",
+            (Self::Postgres, DataType::Float64) => "DOUBLE PRECISION",
+            (Self::Postgres, DataType::String) => "TEXT",
+            (Self::Postgres, DataType::Binary) => "BYTEA",
+            (Self::Postgres, DataType::Datetime(_, None)) => "TIMESTAMP",
+            (Self::Postgres, DataType::Datetime(_, Some(_))) => "TIMESTAMPTZ",
+            (Self::DuckDb, DataType::Int8) => "TINYINT",
+            (Self::DuckDb, DataType::Int16) => "SMALLINT",
+            (Self::DuckDb, DataType::Int32) => "INTEGER",
+            (Self::DuckDb, DataType::Int64) => "BIGINT",
+            (Self::DuckDb, DataType::Int128) => "HUGEINT",
+            (Self::DuckDb, DataType::UInt8) => "UTINYINT",
+            (Self::DuckDb, DataType::UInt16) => "USMALLINT",
+            (Self::DuckDb, DataType::UInt32) => "UINTEGER",
+            (Self::DuckDb, DataType::UInt64) => "UBIGINT",
+            (Self::DuckDb, DataType::UInt128) => "UHUGEINT",
+            (Self::DuckDb, DataType::Float32) => "FLOAT",
+            (Self::DuckDb, DataType::Float64) => "DOUBLE",
+            (Self::DuckDb, DataType::String) => "VARCHAR",
+            (Self::DuckDb, DataType::Binary) => "BLOB",
+            (Self::DuckDb, DataType::Datetime(TimeUnit::Milliseconds, None)) => "TIMESTAMP_MS",
+            (Self::DuckDb, DataType::Datetime(TimeUnit::Microseconds, None)) => "TIMESTAMP",
+            (Self::DuckDb, DataType::Datetime(TimeUnit::Nanoseconds, None)) => "TIMESTAMP_NS",
+            (Self::DuckDb, DataType::Datetime(_, Some(_))) => "TIMESTAMPTZ",
+            (Self::Postgres | Self::DuckDb, DataType::Date) => "DATE",
+            (Self::Postgres | Self::DuckDb, DataType::Time) => "TIME",
+            (Self::Postgres | Self::DuckDb, DataType::Duration(_)) => "INTERVAL",
+            (Self::Postgres, DataType::Decimal(precision, scale)) => {
+                return Ok(format!("NUMERIC({precision}, {scale})"));
+            }
+            (Self::DuckDb, DataType::Decimal(precision, scale)) => {
+                return Ok(format!("DECIMAL({precision}, {scale})"));
+            }
+            (Self::ClickHouse, DataType::Decimal(precision, scale)) => {
+                return Ok(format!("Decimal({precision}, {scale})"));
+            }
+            (Self::ClickHouse, DataType::Boolean) => "Bool",
+            (Self::ClickHouse, DataType::Int8) => "Int8",
+            (Self::ClickHouse, DataType::Int16) => "Int16",
+            (Self::ClickHouse, DataType::Int32) => "Int32",
+            (Self::ClickHouse, DataType::Int64 | DataType::Duration(_)) => "Int64",
+            (Self::ClickHouse, DataType::Int128) => "Int128",
+            (Self::ClickHouse, DataType::UInt8) => "UInt8",
+            (Self::ClickHouse, DataType::UInt16) => "UInt16",
+            (Self::ClickHouse, DataType::UInt32) => "UInt32",
+            (Self::ClickHouse, DataType::UInt64) => "UInt64",
+            (Self::ClickHouse, DataType::UInt128) => "UInt128",
+            (Self::ClickHouse, DataType::Float32) => "Float32",
+            (Self::ClickHouse, DataType::Float64) => "Float64",
+            (Self::ClickHouse, DataType::String | DataType::Binary) => "String",
+            (Self::ClickHouse, DataType::Date) => "Date32",
+            (Self::ClickHouse, DataType::Time) => "Time64(9)",
+            (Self::ClickHouse, DataType::Datetime(unit, tz)) => {
+                let precision = match unit {
+                    TimeUnit::Milliseconds => 3,
+                    TimeUnit::Microseconds => 6,
+                    TimeUnit::Nanoseconds => 9,
+                };
+                return Ok(tz.as_ref().map_or_else(
+                    || format!("DateTime64({precision})"),
+                    |tz| format!("DateTime64({precision}, {})", sql_string(tz)),
+                ));
+            }
+            (_, other) => polars_bail!(
+                ComputeError:
+                "df-derive: no {} column type for dtype {}",
+                self.name(),
+                other,
+            ),
+        };
+        Ok(ty.to_owned())
+    }
+
+    /// Column type with list layers and, for `ClickHouse`, element and column
+    /// `Nullable(...)` wrappers. Postgres and `DuckDB` express column
+    /// nullability with `NOT NULL` instead.
+    fn column_type(self, dtype: &DataType, nullability: &[bool]) -> PolarsResult<String> {
+        let nullable = nullability.first().copied().unwrap_or(true);
+        let rest = nullability.get(1..).unwrap_or_default();
+        match (self, dtype) {
+            (Self::Postgres | Self::DuckDb, DataType::List(inner)) => {
+                Ok(format!("{}[]", self.column_type(inner, rest)?))
+            }
+            (Self::Postgres | Self::DuckDb, DataType::Array(inner, len)) => {
+                Ok(format!("{}[{len}]", self.column_type(inner, rest)?))
+            }
+            // ClickHouse cannot wrap `Array` in `Nullable`; a null list
+            // column is stored as an empty array. Fixed widths are not
+            // enforced.
+            (Self::ClickHouse, DataType::List(inner) | DataType::Array(inner, _)) => {
+                Ok(format!("Array({})", self.column_type(inner, rest)?))
+            }
+            (Self::ClickHouse, _) if nullable => {
+                Ok(format!("Nullable({})", self.scalar_type(dtype)?))
+            }
+            _ => self.scalar_type(dtype),
+        }
+    }
+}
+
+fn sql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// `CREATE TABLE` statement for `T` in `dialect`.
+///
+/// Non-nullable columns get `NOT NULL` (Postgres, `DuckDB`) or a bare type
+/// (`ClickHouse`, where nullable columns are `Nullable(...)`). Column
+/// descriptions become `COMMENT ON COLUMN` statements (Postgres, `DuckDB`)
+/// or inline `COMMENT` clauses (`ClickHouse`). `ClickHouse` tables use
+/// `ENGINE = MergeTree ORDER BY tuple()`.
+///
+/// # Errors
+/// Returns an error if schema generation fails or a column has a dtype
+/// without a mapping in `dialect`.
+pub fn sql_ddl<T: ToDataFrame>(table: &str, dialect: SqlDialect) -> PolarsResult<String> {
+    let columns = column_descriptors::<T>()?;
+    let table_ident = dialect.quote_ident(table);
+    let mut out = format!("CREATE TABLE {table_ident} (");
+    let mut comments = String::new();
+    for (index, column) in columns.iter().enumerate() {
+        let separator = if index == 0 { "" } else { "," };
+        let ident = dialect.quote_ident(&column.name);
+        let ty = dialect.column_type(&column.dtype, &column.nullability)?;
+        let _ = write!(out, "{separator}\n    {ident} {ty}");
+        if dialect != SqlDialect::ClickHouse && !column.is_nullable() {
+            out.push_str(" NOT NULL");
+        }
+        if let Some(description) = &column.description {
+            let description = sql_string(description);
+            if dialect == SqlDialect::ClickHouse {
+                let _ = write!(out, " COMMENT {description}");
+            } else {
+                let _ = write!(
+                    comments,
+                    "\nCOMMENT ON COLUMN {table_ident}.{ident} IS {description};"
+                );
+            }
+        }
+    }
+    out.push_str("\n)");
+    if dialect == SqlDialect::ClickHouse {
+        out.push_str("\nENGINE = MergeTree\nORDER BY tuple()");
+    }
+    out.push(';');
+    out.push_str(&comments);
+    out.push('\n');
+    Ok(out)
+}
+
+/// Columns regrouped by the dotted prefixes of their names.
+enum Node<'a> {
+    Column(&'a ColumnDescriptor),
+    Group(Vec<(&'a str, Self)>),
+}
+
+fn column_tree(columns: &[ColumnDescriptor]) -> Vec<(&str, Node<'_>)> {
+    let mut root: Vec<(&str, Node<'_>)> = Vec::new();
+    for column in columns {
+        let mut level = &mut root;
+        let mut segments = column.name.split('.').peekable();
+        while let Some(segment) = segments.next() {
+            if segments.peek().is_none() {
+                level.push((segment, Node::Column(column)));
+                break;
+            }
+            let position = level
+                .iter()
+                .position(|(name, node)| *name == segment && matches!(node, Node::Group(_)));
+            let position = position.unwrap_or_else(|| {
+                level.push((segment, Node::Group(Vec::new())));
+                level.len() - 1
+            });
+            let Node::Group(children) = &mut level[position].1 else {
+                unreachable!("position always points at a group");
+            };
+            level = children;
+        }
+    }
+    root
+}
+
+/// JSON string literal for `value`, with the escapes JSON requires.
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if u32::from(ch) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(ch));
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// JSON Schema (draft 2020-12) for one row of `T`, as a JSON string.
+///
+/// Dotted column names become nested objects. Nullable columns and list
+/// elements allow `null`; every non-nullable property is listed in
+/// `required`. Descriptions carry over as `description`.
+///
+/// # Errors
+/// Returns an error if schema generation fails or a column has a dtype
+/// without a JSON Schema mapping.
+pub fn json_schema<T: ToDataFrame>(title: &str) -> PolarsResult<String> {
+    let columns = column_descriptors::<T>()?;
+    let tree = column_tree(&columns);
+    let mut out = String::from("{\"$schema\":\"https://json-schema.org/draft/2020-12/schema\"");
+    let _ = write!(out, ",\"title\":{}", json_string(title));
+    out.push(',');
+    json_object_body(&mut out, &tree)?;
+    out.push('}');
+    Ok(out)
+}
+
+fn json_object_body(out: &mut String, children: &[(&str, Node<'_>)]) -> PolarsResult<()> {
+    out.push_str("\"type\":\"object\",\"properties\":{");
+    let mut required = Vec::new();
+    for (index, (name, node)) in children.iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "{}:", json_string(name));
+        match node {
+            Node::Column(column) => {
+                if !column.is_nullable() {
+                    required.push(*name);
+                }
+                json_column(out, column)?;
+            }
+            Node::Group(grandchildren) => {
+                required.push(*name);
+                out.push('{');
+                json_object_body(out, grandchildren)?;
+                out.push('}');
+            }
+        }
+    }
+    out.push_str("},\"required\":[");
+    for (index, name) in required.iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        out.push_str(&json_string(name));
+    }
+    out.push_str("],\"additionalProperties\":false");
+    Ok(())
+}
+
+fn json_column(out: &mut String, column: &ColumnDescriptor) -> PolarsResult<()> {
+    out.push('{');
+    json_type(out, &column.dtype, &column.nullability)?;
+    if let Some(description) = &column.description {
+        let _ = write!(out, ",\"description\":{}", json_string(description));
+    }
+    out.push('}');
+    Ok(())
+}
+
+fn json_type(out: &mut String, dtype: &DataType, nullability: &[bool]) -> PolarsResult<()> {
+    let nullable = nullability.first().copied().unwrap_or(true);
+    let (ty, extra) = match dtype {
+        DataType::Boolean => ("boolean", None),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::Int128
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::UInt128 => ("integer", None),
+        DataType::Float32 | DataType::Float64 | DataType::Decimal(..) => ("number", None),
+        DataType::String => ("string", None),
+        DataType::Binary => ("string", Some("\"contentEncoding\":\"base64\"")),
+        DataType::Date => ("string", Some("\"format\":\"date\"")),
+        DataType::Time => ("string", Some("\"format\":\"time\"")),
+        DataType::Datetime(..) => ("string", Some("\"format\":\"date-time\"")),
+        DataType::Duration(_) => ("string", Some("\"format\":\"duration\"")),
+        DataType::List(_) | DataType::Array(..) => ("array", None),
+        other => polars_bail!(
+            ComputeError:
+            "df-derive: no JSON Schema type for dtype {}",
+            other,
+        ),
+    };
+    if nullable {
+        let _ = write!(out, "\"type\":[\"{ty}\",\"null\"]");
+    } else {
+        let _ = write!(out, "\"type\":\"{ty}\"");
+    }
+    if let Some(extra) = extra {
+        let _ = write!(out, ",{extra}");
+    }
+    if let DataType::Array(_, len) = dtype {
+        let _ = write!(out, ",\"minItems\":{len},\"maxItems\":{len}");
+    }
+    if let DataType::List(inner) | DataType::Array(inner, _) = dtype {
+        out.push_str(",\"items\":{");
+        json_type(out, inner, nullability.get(1..).unwrap_or_default())?;
+        out.push('}');
+    }
+    Ok(())
+}
+
+/// Avro record schema for one row of `T`, as a JSON string.
+///
+/// Dotted column names become nested records named after their path
+/// (`Trade_quotes`). Nullable columns and list elements are `["null", T]`
+/// unions, and nullable fields default to `null`. Temporal and decimal
+/// columns use Avro logical types; `u64`, `i128`, and `u128` are
+/// `decimal` over `bytes` with scale 0, and durations are plain `long`
+/// counts in the column's time unit.
+///
+/// # Errors
+/// Returns an error if schema generation fails or a column has a dtype
+/// without an Avro mapping.
+pub fn avro_schema<T: ToDataFrame>(name: &str) -> PolarsResult<String> {
+    let columns = column_descriptors::<T>()?;
+    let tree = column_tree(&columns);
+    let mut out = String::new();
+    avro_record(&mut out, name, &tree)?;
+    Ok(out)
+}
+
+fn avro_record(out: &mut String, name: &str, children: &[(&str, Node<'_>)]) -> PolarsResult<()> {
+    let _ = write!(
+        out,
+        "{{\"type\":\"record\",\"name\":{},\"fields\":[",
+        json_string(name)
+    );
+    for (index, (field_name, node)) in children.iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "{{\"name\":{},\"type\":", json_string(field_name));
+        match node {
+            Node::Column(column) => {
+                avro_type(out, &column.dtype, &column.nullability)?;
+                if column.is_nullable() {
+                    out.push_str(",\"default\":null");
+                }
+                if let Some(description) = &column.description {
+                    let _ = write!(out, ",\"doc\":{}", json_string(description));
+                }
+            }
+            Node::Group(grandchildren) => {
+                avro_record(out, &format!("{name}_{field_name}"), grandchildren)?;
+            }
+        }
+        out.push('}');
+    }
+    out.push_str("]}");
+    Ok(())
+}
+
+fn avro_type(out: &mut String, dtype: &DataType, nullability: &[bool]) -> PolarsResult<()> {
+    let nullable = nullability.first().copied().unwrap_or(true);
+    if nullable {
+        out.push_str("[\"null\",");
+    }
+    match dtype {
+        DataType::Boolean => out.push_str("\"boolean\""),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            out.push_str("\"int\"");
+        }
+        DataType::Int64 | DataType::UInt32 | DataType::Duration(_) => out.push_str("\"long\""),
+        DataType::UInt64 => avro_decimal(out, 20, 0),
+        DataType::Int128 | DataType::UInt128 => avro_decimal(out, 39, 0),
+        DataType::Decimal(precision, scale) => avro_decimal(out, *precision, *scale),
+        DataType::Float32 => out.push_str("\"float\""),
+        DataType::Float64 => out.push_str("\"double\""),
+        DataType::String => out.push_str("\"string\""),
+        DataType::Binary => out.push_str("\"bytes\""),
+        DataType::Date => out.push_str("{\"type\":\"int\",\"logicalType\":\"date\"}"),
+        DataType::Time => out.push_str("{\"type\":\"long\",\"logicalType\":\"time-micros\"}"),
+        DataType::Datetime(unit, tz) => {
+            let prefix = if tz.is_some() {
+                "timestamp"
+            } else {
+                "local-timestamp"
+            };
+            let suffix = match unit {
+                TimeUnit::Milliseconds => "millis",
+                TimeUnit::Microseconds => "micros",
+                TimeUnit::Nanoseconds => "nanos",
+            };
+            let _ = write!(
+                out,
+                "{{\"type\":\"long\",\"logicalType\":\"{prefix}-{suffix}\"}}"
+            );
+        }
+        DataType::List(inner) | DataType::Array(inner, _) => {
+            out.push_str("{\"type\":\"array\",\"items\":");
+            avro_type(out, inner, nullability.get(1..).unwrap_or_default())?;
+            out.push('}');
+        }
+        other => polars_bail!(
+            ComputeError:
+            "df-derive: no Avro type for dtype {}",
+            other,
+        ),
+    }
+    if nullable {
+        out.push(']');
+    }
+    Ok(())
+}
+
+fn avro_decimal(out: &mut String, precision: usize, scale: usize) {
+    let _ = write!(
+        out,
+        "{{\"type\":\"bytes\",\"logicalType\":\"decimal\",\"precision\":{precision},\"scale\":{scale}}}"
+    );
+}
@@ -0,0 +1,804 @@
+//! Deserialize `DataFrame` rows into `Deserialize` types.
+//!
+//! Column names are split on `.` into a tree, so `best.bid` is the `bid`
+//! field of the `best` record, and a record whose fields are named
+//! `field_0`, `field_1`, … also reads as a tuple. Each row becomes a tree of
+//! cells: leaf cells hold the column's value, and a record whose columns are
+//! lists reads as a sequence of records, element by element, the way the
+//! derive lays out `Vec<Struct>` fields. A record whose columns are all null
+//! reads as `None`, and a `List(Struct{key, value})` column reads as a map
+//! of its entries.
+
+use ::serde::de::value::StrDeserializer;
+use ::serde::de::{self, Deserialize, DeserializeSeed, Deserializer, IntoDeserializer, Visitor};
+use ::serde::forward_to_deserialize_any;
+use polars::prelude::{AnyValue, Column, DataFrame, DataType, PolarsResult, TimeUnit};
+use polars_arrow::temporal_conversions::{
+    date32_to_datetime_opt, time64ns_to_time_opt, timestamp_ms_to_datetime_opt,
+    timestamp_ns_to_datetime_opt, timestamp_us_to_datetime_opt,
+};
+
+use super::Error;
+
+/// Read the rows of `frame` as `T`s.
+///
+/// # Errors
+/// Returns an error if two column names conflict (`a` and `a.b`), or if a
+/// row does not deserialize as `T`. Deserialization errors name the row and,
+/// when known, the column.
+pub fn from_dataframe<'df, T: Deserialize<'df>>(frame: &'df DataFrame) -> PolarsResult<Vec<T>> {
+    Ok(Vec::deserialize(FrameDeserializer::new(frame)?)?)
+}
+
+/// A [`Deserializer`] reading a `DataFrame` as a sequence of rows.
+///
+/// String and binary values of top-level cells are borrowed from the frame,
+/// so rows may hold `&'df str` fields. Temporal values are read as the
+/// strings `chrono` serializes (`2023-11-14T22:13:20Z` for zoned datetimes,
+/// `2023-11-14T22:13:20` for naive ones), durations as their physical
+/// integer, and decimals as decimal strings.
+pub struct FrameDeserializer<'df> {
+    columns: &'df [Column],
+    height: usize,
+    root: Node,
+    naive_utc: bool,
+}
+
+impl<'df> FrameDeserializer<'df> {
+    /// # Errors
+    /// Returns an error if a column name is nested under another column's
+    /// name, such as `a.b` next to `a`.
+    pub fn new(frame: &'df DataFrame) -> Result<Self, Error> {
+        let columns = frame.columns();
+        let mut root = Node::group(String::new());
+        for (index, column) in columns.iter().enumerate() {
+            root.insert(column.name().as_str(), index, column.dtype())?;
+        }
+        Ok(Self {
+            columns,
+            height: frame.height(),
+            root,
+            naive_utc: false,
+        })
+    }
+
+    /// Read datetime columns without a time zone as UTC instants.
+    ///
+    /// The derive stores `chrono::DateTime<Tz>` fields as UTC instants in
+    /// such columns, so frames derived from them need this to deserialize
+    /// back into `DateTime<Utc>`. `NaiveDateTime` fields need it off.
+    #[must_use]
+    pub const fn naive_datetimes_as_utc(mut self) -> Self {
+        self.naive_utc = true;
+        self
+    }
+
+    fn row(&self, row: usize) -> Result<Cell<'_, 'df>, Error> {
+        self.root.cell(&|index| {
+            self.columns[index]
+                .get(row)
+                .map_err(|e| Error::new(e.to_string()))
+        })
+    }
+}
+
+impl<'df> Deserializer<'df> for FrameDeserializer<'df> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(RowsAccess {
+            frame: &self,
+            next: 0,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        <W: Visitor<'df>>
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct RowsAccess<'a, 'df> {
+    frame: &'a FrameDeserializer<'df>,
+    next: usize,
+}
+
+impl<'df> de::SeqAccess<'df> for RowsAccess<'_, 'df> {
+    type Error = Error;
+
+    fn next_element_seed<S: DeserializeSeed<'df>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Error> {
+        if self.next >= self.frame.height {
+            return Ok(None);
+        }
+        let row = self.next;
+        self.next += 1;
+        let cell = self.frame.row(row).map_err(|e| e.at_row(row))?;
+        seed.deserialize(CellDeserializer::new(cell, self.frame.naive_utc))
+            .map(Some)
+            .map_err(|e| e.at_row(row))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.frame.height - self.next)
+    }
+}
+
+/// One segment of the dotted column names.
+struct Node {
+    path: String,
+    column: Option<usize>,
+    children: Vec<(String, Self)>,
+    entries: Option<Box<Entries>>,
+}
+
+/// How to read the entries of a `List(Struct{key, value})` column, the
+/// layout of a derived map field.
+struct Entries {
+    key: Node,
+    value: Node,
+}
+
+impl Entries {
+    /// The entries of `dtype`, a map column possibly under more list layers.
+    fn of(path: &str, dtype: &DataType) -> Result<Option<Box<Self>>, Error> {
+        let DataType::List(inner) = dtype else {
+            return Ok(None);
+        };
+        match inner.as_ref() {
+            DataType::Struct(fields)
+                if fields.len() == 2 && fields[0].name == "key" && fields[1].name == "value" =>
+            {
+                Ok(Some(Box::new(Self {
+                    key: Node::side(format!("{path}.key"), &fields[0].dtype)?,
+                    value: Node::side(format!("{path}.value"), &fields[1].dtype)?,
+                })))
+            }
+            inner => Self::of(path, inner),
+        }
+    }
+}
+
+impl Node {
+    const fn group(path: String) -> Self {
+        Self {
+            path,
+            column: None,
+            children: Vec::new(),
+            entries: None,
+        }
+    }
+
+    /// The key or value of a map entry: one value, or a `Struct` whose
+    /// fields are that side's columns.
+    fn side(path: String, dtype: &DataType) -> Result<Self, Error> {
+        let mut node = Self::group(path);
+        if let DataType::Struct(fields) = dtype {
+            for (index, field) in fields.iter().enumerate() {
+                node.insert(&field.name, index, &field.dtype)?;
+            }
+        } else {
+            node.column = Some(0);
+            node.entries = Entries::of(&node.path, dtype)?;
+        }
+        Ok(node)
+    }
+
+    fn insert(&mut self, name: &str, column: usize, dtype: &DataType) -> Result<(), Error> {
+        let mut node = self;
+        for segment in name.split('.') {
+            if node.column.is_some() {
+                break;
+            }
+            let existing = node.children.iter().position(|(child, _)| child == segment);
+            let position = existing.unwrap_or_else(|| {
+                let path = if node.path.is_empty() {
+                    segment.to_owned()
+                } else {
+                    format!("{}.{segment}", node.path)
+                };
+                node.children.push((segment.to_owned(), Self::group(path)));
+                node.children.len() - 1
+            });
+            node = &mut node.children[position].1;
+        }
+        if node.column.is_some() {
+            return Err(Error::new(format!(
+                "column `{name}` is nested under column `{}`",
+                node.path
+            )));
+        }
+        if !node.children.is_empty() {
+            return Err(Error::new(format!(
+                "column `{name}` is also the parent of nested columns"
+            )));
+        }
+        node.column = Some(column);
+        node.entries = Entries::of(&node.path, dtype)?;
+        Ok(())
+    }
+
+    /// The cell under this node, reading each column's value with `value`.
+    fn cell<'t, 'df>(
+        &'t self,
+        value: &dyn Fn(usize) -> Result<AnyValue<'df>, Error>,
+    ) -> Result<Cell<'t, 'df>, Error> {
+        if let Some(index) = self.column {
+            return Ok(Cell::Leaf {
+                path: &self.path,
+                value: value(index).map_err(|e| e.at_column(&self.path))?,
+                entries: self.entries.as_deref(),
+            });
+        }
+        let fields = self
+            .children
+            .iter()
+            .map(|(name, child)| Ok((name.as_str(), child.cell(value)?)))
+            .collect::<Result<_, Error>>()?;
+        Ok(Cell::Record {
+            path: &self.path,
+            fields,
+        })
+    }
+
+    /// The cell for one side of a map entry built by [`Self::side`].
+    fn side_cell<'t, 'df>(&'t self, value: AnyValue<'df>) -> Result<Cell<'t, 'df>, Error> {
+        if self.column.is_some() {
+            return self.cell(&|_| Ok(value.clone()));
+        }
+        let fields = match value {
+            AnyValue::StructOwned(payload) => payload.0,
+            AnyValue::Null => Vec::new(),
+            other => {
+                return Err(
+                    Error::new(format!("expected a struct, found {}", other.dtype()))
+                        .at_column(&self.path),
+                );
+            }
+        };
+        self.cell(&|index| Ok(fields.get(index).cloned().unwrap_or(AnyValue::Null)))
+    }
+}
+
+/// The values of one row (or one list element) under a node.
+#[derive(Clone)]
+enum Cell<'t, 'df> {
+    Leaf {
+        path: &'t str,
+        value: AnyValue<'df>,
+        entries: Option<&'t Entries>,
+    },
+    Record {
+        path: &'t str,
+        fields: Vec<(&'t str, Self)>,
+    },
+}
+
+impl<'t> Cell<'t, '_> {
+    const fn path(&self) -> &'t str {
+        match self {
+            Self::Leaf { path, .. } | Self::Record { path, .. } => path,
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        match self {
+            Self::Leaf { value, .. } => value.is_null(),
+            Self::Record { fields, .. } => fields.iter().all(|(_, field)| field.is_null()),
+        }
+    }
+
+    /// The same shape with every value null.
+    fn null_like(&self) -> Self {
+        match self {
+            Self::Leaf { path, entries, .. } => Self::Leaf {
+                path,
+                value: AnyValue::Null,
+                entries: *entries,
+            },
+            Self::Record { path, fields } => Self::Record {
+                path,
+                fields: fields
+                    .iter()
+                    .map(|(name, field)| (*name, field.null_like()))
+                    .collect(),
+            },
+        }
+    }
+
+    /// Whether the fields are `field_0`, `field_1`, … in order.
+    fn is_tuple(fields: &[(&str, Self)]) -> bool {
+        !fields.is_empty()
+            && fields
+                .iter()
+                .enumerate()
+                .all(|(index, (name, _))| name.strip_prefix("field_") == Some(&index.to_string()))
+    }
+
+    /// Split a list-valued cell into its elements, or `None` when it is
+    /// null. A record splits into records of its columns' elements.
+    fn elements(self) -> Result<Option<Vec<Self>>, Error> {
+        match self {
+            Self::Leaf {
+                value: AnyValue::Null,
+                ..
+            } => Ok(None),
+            Self::Leaf {
+                path,
+                value: AnyValue::List(series) | AnyValue::Array(series, _),
+                entries,
+            } => (0..series.len())
+                .map(|index| {
+                    let value = series.get(index).map_err(|e| Error::new(e.to_string()))?;
+                    Ok(Self::Leaf {
+                        path,
+                        value: value.into_static(),
+                        entries,
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()
+                .map(Some),
+            Self::Leaf { value, .. } => Err(Error::new(format!(
+                "expected a list, found {}",
+                value.dtype()
+            ))),
+            Self::Record { path, fields } => {
+                let mut len = None;
+                let mut columns = Vec::with_capacity(fields.len());
+                for (name, field) in fields {
+                    let template = field.null_like();
+                    let items = field.elements()?;
+                    if let Some(items) = &items {
+                        match len {
+                            Some(len) if len != items.len() => {
+                                return Err(Error::new(format!(
+                                    "lists nested under `{path}` have different lengths"
+                                )));
+                            }
+                            _ => len = Some(items.len()),
+                        }
+                    }
+                    columns.push((name, items.map(Vec::into_iter), template));
+                }
+                let Some(len) = len else {
+                    return Ok(None);
+                };
+                let elements = (0..len)
+                    .map(|_| Self::Record {
+                        path,
+                        fields: columns
+                            .iter_mut()
+                            .map(|(name, items, template)| {
+                                let item = items
+                                    .as_mut()
+                                    .and_then(Iterator::next)
+                                    .unwrap_or_else(|| template.clone());
+                                (*name, item)
+                            })
+                            .collect(),
+                    })
+                    .collect();
+                Ok(Some(elements))
+            }
+        }
+    }
+
+    /// The `(key, value)` cells of a map column's entries, or `None` when
+    /// the cell is not a list of map entries.
+    fn map_entries(&self) -> Result<Option<Vec<(Self, Self)>>, Error> {
+        let Self::Leaf {
+            value: AnyValue::List(series),
+            entries: Some(entries),
+            ..
+        } = self
+        else {
+            return Ok(None);
+        };
+        if !matches!(series.dtype(), DataType::Struct(_)) {
+            return Ok(None);
+        }
+        (0..series.len())
+            .map(|index| {
+                let entry = series
+                    .get(index)
+                    .map_err(|e| Error::new(e.to_string()))?
+                    .into_static();
+                let (key, value) = match entry {
+                    AnyValue::StructOwned(payload) => {
+                        let mut sides = payload.0.into_iter();
+                        (
+                            sides.next().unwrap_or(AnyValue::Null),
+                            sides.next().unwrap_or(AnyValue::Null),
+                        )
+                    }
+                    _ => (AnyValue::Null, AnyValue::Null),
+                };
+                Ok((entries.key.side_cell(key)?, entries.value.side_cell(value)?))
+            })
+            .collect::<Result<Vec<_>, Error>>()
+            .map(Some)
+    }
+}
+
+struct CellDeserializer<'t, 'df> {
+    cell: Cell<'t, 'df>,
+    naive_utc: bool,
+}
+
+impl<'t, 'df> CellDeserializer<'t, 'df> {
+    const fn new(cell: Cell<'t, 'df>, naive_utc: bool) -> Self {
+        Self { cell, naive_utc }
+    }
+
+    fn visit_seq<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.cell.elements()? {
+            Some(elements) => visitor.visit_seq(CellsAccess {
+                cells: elements.into_iter(),
+                naive_utc: self.naive_utc,
+            }),
+            None => visitor.visit_unit(),
+        }
+    }
+
+    fn visit_fields<V: Visitor<'df>>(
+        fields: Vec<(&'t str, Cell<'t, 'df>)>,
+        naive_utc: bool,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_map(FieldsAccess {
+            fields: fields.into_iter(),
+            value: None,
+            naive_utc,
+        })
+    }
+
+    fn visit_tuple<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.cell {
+            Cell::Record { fields, .. } => visitor.visit_seq(CellsAccess {
+                cells: fields
+                    .into_iter()
+                    .map(|(_, field)| field)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                naive_utc: self.naive_utc,
+            }),
+            leaf @ Cell::Leaf { .. } => Self::new(leaf, self.naive_utc).visit_seq(visitor),
+        }
+    }
+}
+
+/// Run `deserialize` and attach the cell's column to errors that carry
+/// none yet.
+fn in_column<T>(path: &str, deserialize: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    deserialize().map_err(|e| e.at_column(path))
+}
+
+impl<'df> Deserializer<'df> for CellDeserializer<'_, 'df> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        let path = self.cell.path();
+        let naive_utc = self.naive_utc;
+        in_column(path, || {
+            if let Some(entries) = self.cell.map_entries()? {
+                return visitor.visit_map(EntriesAccess {
+                    entries: entries.into_iter(),
+                    value: None,
+                    naive_utc,
+                });
+            }
+            match self.cell {
+                Cell::Leaf { value, .. } => visit_value(value, naive_utc, visitor),
+                Cell::Record { fields, .. } if Cell::is_tuple(&fields) => {
+                    Self::new(Cell::Record { path, fields }, naive_utc).visit_tuple(visitor)
+                }
+                Cell::Record { fields, .. } => Self::visit_fields(fields, naive_utc, visitor),
+            }
+        })
+    }
+
+    fn deserialize_option<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        let path = self.cell.path();
+        in_column(path, || {
+            if self.cell.is_null() {
+                visitor.visit_none()
+            } else {
+                visitor.visit_some(self)
+            }
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        let path = self.cell.path();
+        in_column(path, || self.visit_seq(visitor))
+    }
+
+    fn deserialize_tuple<V: Visitor<'df>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let path = self.cell.path();
+        in_column(path, || self.visit_tuple(visitor))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'df>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'df>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        let path = self.cell.path();
+        let naive_utc = self.naive_utc;
+        in_column(path, || match self.cell {
+            Cell::Record { fields, .. } => Self::visit_fields(fields, naive_utc, visitor),
+            leaf @ Cell::Leaf { .. } => Self::new(leaf, naive_utc).deserialize_any(visitor),
+        })
+    }
+
+    /// Newtype structs are one-field tuple structs (`field_0`), as in the
+    /// derive; a plain column also reads as the wrapped value.
+    fn deserialize_newtype_struct<V: Visitor<'df>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let path = self.cell.path();
+        let naive_utc = self.naive_utc;
+        in_column(path, || match self.cell {
+            Cell::Record { mut fields, .. } if fields.len() == 1 && fields[0].0 == "field_0" => {
+                let (_, field) = fields.remove(0);
+                visitor.visit_newtype_struct(Self::new(field, naive_utc))
+            }
+            cell => visitor.visit_newtype_struct(Self::new(cell, naive_utc)),
+        })
+    }
+
+    fn deserialize_unit<V: Visitor<'df>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.cell {
+            Cell::Record { fields, .. } if fields.is_empty() => visitor.visit_unit(),
+            cell => Self::new(cell, self.naive_utc).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'df>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    /// Enums read unit variants from their name.
+    fn deserialize_enum<V: Visitor<'df>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let path = self.cell.path();
+        in_column(path, || match &self.cell {
+            Cell::Leaf { value, .. } => value.get_str().map_or_else(
+                || {
+                    Err(Error::new(format!(
+                        "expected an enum variant name, found {}",
+                        value.dtype()
+                    )))
+                },
+                |variant| {
+                    visitor.visit_enum::<StrDeserializer<'_, Error>>(variant.into_deserializer())
+                },
+            ),
+            Cell::Record { .. } => Err(Error::new(
+                "expected an enum variant name, found nested columns",
+            )),
+        })
+    }
+
+    forward_to_deserialize_any! {
+        <W: Visitor<'df>>
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf identifier ignored_any
+    }
+}
+
+fn visit_value<'df, V: Visitor<'df>>(
+    value: AnyValue<'df>,
+    naive_utc: bool,
+    visitor: V,
+) -> Result<V::Value, Error> {
+    match value {
+        AnyValue::Null => visitor.visit_unit(),
+        AnyValue::Boolean(v) => visitor.visit_bool(v),
+        AnyValue::Int8(v) => visitor.visit_i8(v),
+        AnyValue::Int16(v) => visitor.visit_i16(v),
+        AnyValue::Int32(v) => visitor.visit_i32(v),
+        AnyValue::Int64(v) | AnyValue::Duration(v, _) => visitor.visit_i64(v),
+        AnyValue::Int128(v) => visitor.visit_i128(v),
+        AnyValue::UInt8(v) => visitor.visit_u8(v),
+        AnyValue::UInt16(v) => visitor.visit_u16(v),
+        AnyValue::UInt32(v) => visitor.visit_u32(v),
+        AnyValue::UInt64(v) => visitor.visit_u64(v),
+        AnyValue::UInt128(v) => visitor.visit_u128(v),
+        AnyValue::Float32(v) => visitor.visit_f32(v),
+        AnyValue::Float64(v) => visitor.visit_f64(v),
+        AnyValue::String(v) => visitor.visit_borrowed_str(v),
+        AnyValue::StringOwned(v) => visitor.visit_str(&v),
+        AnyValue::Binary(v) => visitor.visit_borrowed_bytes(v),
+        AnyValue::BinaryOwned(v) => visitor.visit_byte_buf(v),
+        AnyValue::Date(days) => {
+            // `date32_to_date_opt` adds `days` to the epoch unchecked.
+            let date = date32_to_datetime_opt(days)
+                .ok_or_else(|| Error::new(format!("date {days} days is out of range")))?;
+            visitor.visit_string(date.date().to_string())
+        }
+        AnyValue::Time(nanos) => {
+            let time = time64ns_to_time_opt(nanos)
+                .ok_or_else(|| Error::new(format!("time {nanos}ns is out of range")))?;
+            visitor.visit_string(time.to_string())
+        }
+        AnyValue::Datetime(v, unit, tz) => {
+            visitor.visit_string(datetime_string(v, unit, naive_utc || tz.is_some())?)
+        }
+        AnyValue::DatetimeOwned(v, unit, tz) => {
+            visitor.visit_string(datetime_string(v, unit, naive_utc || tz.is_some())?)
+        }
+        AnyValue::Decimal(mantissa, _, scale) => {
+            visitor.visit_string(decimal_string(mantissa, scale))
+        }
+        list @ (AnyValue::List(_) | AnyValue::Array(..)) => CellDeserializer::new(
+            Cell::Leaf {
+                path: "",
+                value: list,
+                entries: None,
+            },
+            naive_utc,
+        )
+        .visit_seq(visitor),
+        other => other.get_str().map_or_else(
+            || {
+                Err(Error::new(format!(
+                    "cannot deserialize {} values",
+                    other.dtype()
+                )))
+            },
+            |v| visitor.visit_str(v),
+        ),
+    }
+}
+
+/// The form `chrono` serializes: ISO 8601, with a `Z` offset for UTC
+/// instants.
+fn datetime_string(value: i64, unit: TimeUnit, utc: bool) -> Result<String, Error> {
+    let datetime = match unit {
+        TimeUnit::Milliseconds => timestamp_ms_to_datetime_opt(value),
+        TimeUnit::Microseconds => timestamp_us_to_datetime_opt(value),
+        TimeUnit::Nanoseconds => timestamp_ns_to_datetime_opt(value),
+    }
+    .ok_or_else(|| Error::new(format!("datetime {value}{unit} is out of range")))?;
+    Ok(if utc {
+        format!("{datetime:?}Z")
+    } else {
+        format!("{datetime:?}")
+    })
+}
+
+fn decimal_string(mantissa: i128, scale: usize) -> String {
+    let sign = if mantissa < 0 { "-" } else { "" };
+    let digits = mantissa.unsigned_abs().to_string();
+    if scale == 0 {
+        return format!("{sign}{digits}");
+    }
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{sign}{integer}.{fraction}")
+}
+
+struct CellsAccess<'t, 'df> {
+    cells: std::vec::IntoIter<Cell<'t, 'df>>,
+    naive_utc: bool,
+}
+
+impl<'df> de::SeqAccess<'df> for CellsAccess<'_, 'df> {
+    type Error = Error;
+
+    fn next_element_seed<S: DeserializeSeed<'df>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Error> {
+        self.cells
+            .next()
+            .map(|cell| seed.deserialize(CellDeserializer::new(cell, self.naive_utc)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.cells.len())
+    }
+}
+
+struct FieldsAccess<'t, 'df> {
+    fields: std::vec::IntoIter<(&'t str, Cell<'t, 'df>)>,
+    value: Option<Cell<'t, 'df>>,
+    naive_utc: bool,
+}
+
+impl<'df> de::MapAccess<'df> for FieldsAccess<'_, 'df> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'df>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((name, value)) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(StrDeserializer::<Error>::new(name))
+            .map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'df>>(&mut self, seed: S) -> Result<S::Value, Error> {
+        let cell = self
+            .value
+            .take()
+            .ok_or_else(|| Error::new("map value requested before its key"))?;
+        seed.deserialize(CellDeserializer::new(cell, self.naive_utc))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+/// The entries of a map column, each key followed by its value.
+struct EntriesAccess<'t, 'df> {
+    entries: std::vec::IntoIter<(Cell<'t, 'df>, Cell<'t, 'df>)>,
+    value: Option<Cell<'t, 'df>>,
+    naive_utc: bool,
+}
+
+impl<'df> de::MapAccess<'df> for EntriesAccess<'_, 'df> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'df>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(CellDeserializer::new(key, self.naive_utc))
+            .map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'df>>(&mut self, seed: S) -> Result<S::Value, Error> {
+        let cell = self
+            .value
+            .take()
+            .ok_or_else(|| Error::new("map value requested before its key"))?;
+        seed.deserialize(CellDeserializer::new(cell, self.naive_utc))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
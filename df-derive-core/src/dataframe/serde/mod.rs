@@ -0,0 +1,97 @@
+//! Conversions for types that implement `serde` traits but not the derive.
+//!
+//! Enabled by the `serde` feature. [`to_dataframe`] turns any
+//! `T: Serialize` rows into a `DataFrame` laid out the way
+//! `#[derive(ToDataFrame)]` would lay out the same type: nested struct
+//! fields become `parent.child` columns, tuple elements become `field_N`,
+//! `Option`s become nulls, `Vec`s become list columns (a `Vec` of structs
+//! becomes one list column per nested field), and maps become
+//! `List(Struct{key, value})` columns. [`from_dataframe`] and
+//! [`FrameDeserializer`] read such frames back into any `T: Deserialize`.
+
+use std::fmt;
+
+use polars::prelude::PolarsError;
+
+mod de;
+mod ser;
+
+pub use de::{FrameDeserializer, from_dataframe};
+pub use ser::{INFER_SCHEMA_LENGTH, to_dataframe, to_dataframe_with_schema};
+
+/// An error raised while converting rows through `serde`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+    row: Option<usize>,
+    column: Option<String>,
+}
+
+impl Error {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            row: None,
+            column: None,
+        }
+    }
+
+    /// The row being converted when the error occurred.
+    #[must_use]
+    pub const fn row(&self) -> Option<usize> {
+        self.row
+    }
+
+    /// The dotted path of the column being read when the error occurred.
+    #[must_use]
+    pub fn column(&self) -> Option<&str> {
+        self.column.as_deref()
+    }
+
+    const fn at_row(mut self, row: usize) -> Self {
+        if self.row.is_none() {
+            self.row = Some(row);
+        }
+        self
+    }
+
+    /// Record the innermost column; the empty path is the row itself.
+    fn at_column(mut self, column: &str) -> Self {
+        if self.column.is_none() && !column.is_empty() {
+            self.column = Some(column.to_owned());
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.row, &self.column) {
+            (Some(row), Some(column)) => write!(f, "row {row}, column `{column}`: ")?,
+            (Some(row), None) => write!(f, "row {row}: ")?,
+            (None, Some(column)) => write!(f, "column `{column}`: ")?,
+            (None, None) => {}
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ::serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::new(message.to_string())
+    }
+}
+
+impl ::serde::de::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::new(message.to_string())
+    }
+}
+
+impl From<Error> for PolarsError {
+    fn from(error: Error) -> Self {
+        Self::ComputeError(error.to_string().into())
+    }
+}
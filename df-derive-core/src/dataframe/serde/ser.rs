@@ -0,0 +1,799 @@
+//! `Serialize` rows into a `DataFrame`.
+//!
+//! Each row is serialized into a [`Value`] tree and flattened into
+//! `(column path, value)` entries: struct fields join their parent's path
+//! with `.`, tuples and tuple structs name their elements `field_N`, and
+//! sequences of records are transposed into one list column per nested
+//! field. A null or empty list standing in for a record (`None` for an
+//! `Option<Struct>`, an empty `Vec<Struct>`) fills every column nested under
+//! it. Map fields become one `List(Struct{key, value})` column, as in the
+//! derive; a map row and the fields of a `#[serde(flatten)]` struct, which
+//! `serde` writes as a map without a length, are records keyed by the map's
+//! keys instead.
+
+use std::collections::{HashMap, HashSet};
+
+use ::serde::ser::{self, Impossible, Serialize};
+use polars::prelude::{
+    AnyValue, Column, DataFrame, DataType, Field, PlSmallStr, PolarsResult, Schema, Series,
+};
+
+use super::Error;
+use crate::dataframe::columns::nested_column_prefix;
+use crate::dataframe::zero_column_dataframe_with_height;
+
+/// How many leading rows [`to_dataframe`] reads to decide the columns.
+pub const INFER_SCHEMA_LENGTH: usize = 100;
+
+/// Convert `rows` into a `DataFrame`, inferring the schema.
+///
+/// The columns are the paths seen in the first [`INFER_SCHEMA_LENGTH`]
+/// rows; each column's dtype is that of its first non-null value, or `Null`
+/// when every value is null. Types that serialize as strings (`chrono`
+/// timestamps, `rust_decimal::Decimal`, `uuid::Uuid`) become `String`
+/// columns; use [`to_dataframe_with_schema`] to cast them to the dtype the
+/// derive would produce.
+///
+/// # Errors
+/// Returns an error if a row fails to serialize, does not serialize as a
+/// struct, tuple or map, has a column not seen in the leading rows, or if a
+/// column mixes values of different types.
+pub fn to_dataframe<T: Serialize>(rows: &[T]) -> PolarsResult<DataFrame> {
+    let rows = serialize_rows(rows)?;
+    let mut names = Vec::new();
+    for row in rows.iter().take(INFER_SCHEMA_LENGTH) {
+        for (path, _) in row {
+            add_path(&mut names, path);
+        }
+    }
+    build(rows, &names, None)
+}
+
+/// Convert `rows` into a `DataFrame` with exactly the columns of `schema`.
+///
+/// Values are cast to the schema's dtypes with a strict cast, so a derived
+/// `T::schema()` turns string-serialized timestamps and decimals back into
+/// `Datetime` and `Decimal` columns. Columns no row provides are null.
+///
+/// # Errors
+/// Returns an error if a row fails to serialize, has a column that is not
+/// in `schema`, or holds a value that cannot be cast to its column's dtype.
+pub fn to_dataframe_with_schema<T: Serialize>(
+    rows: &[T],
+    schema: &Schema,
+) -> PolarsResult<DataFrame> {
+    let rows = serialize_rows(rows)?;
+    let names: Vec<String> = schema.iter_names().map(ToString::to_string).collect();
+    build(rows, &names, Some(schema))
+}
+
+fn serialize_rows<T: Serialize>(rows: &[T]) -> Result<Vec<Vec<(String, Value)>>, Error> {
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let value = match row.serialize(ValueSerializer) {
+                Ok(Value::Map(entries)) => entries
+                    .into_iter()
+                    .map(|(key, value)| Ok((key_name(key)?, value)))
+                    .collect::<Result<_, Error>>()
+                    .map(Value::Record),
+                value => value,
+            }
+            .map_err(|e| e.at_row(index))?;
+            if !matches!(value, Value::Record(_)) {
+                return Err(Error::new(format!(
+                    "serializes as {}, expected a struct, tuple or map",
+                    value.kind()
+                ))
+                .at_row(index));
+            }
+            let mut entries = Vec::new();
+            flatten("", value, &mut entries).map_err(|e| e.at_row(index))?;
+            Ok(entries)
+        })
+        .collect()
+}
+
+fn build(
+    rows: Vec<Vec<(String, Value)>>,
+    names: &[String],
+    schema: Option<&Schema>,
+) -> PolarsResult<DataFrame> {
+    let height = rows.len();
+    // Records enclosing a column; an entry for one of them is left in place
+    // as a stand-in rather than taken.
+    let enclosing: HashSet<&str> = names
+        .iter()
+        .flat_map(|name| name.match_indices('.').map(|(end, _)| &name[..end]))
+        .collect();
+    let mut values: Vec<Vec<Value>> = names.iter().map(|_| Vec::with_capacity(height)).collect();
+    for (index, entries) in rows.into_iter().enumerate() {
+        let mut entries = RowEntries::new(entries);
+        for (name, column) in names.iter().zip(&mut values) {
+            column.push(entries.take(name).map_err(|e| e.at_row(index))?);
+        }
+        if let Some(path) = entries.remaining().find(|path| !enclosing.contains(path)) {
+            let message = if schema.is_some() {
+                format!("column `{path}` is not in the schema")
+            } else {
+                format!(
+                    "column `{path}` does not appear in the first {INFER_SCHEMA_LENGTH} rows; \
+                     pass an explicit schema"
+                )
+            };
+            return Err(Error::new(message).at_row(index).into());
+        }
+    }
+    if names.is_empty() {
+        return zero_column_dataframe_with_height(height);
+    }
+
+    let columns = names
+        .iter()
+        .zip(values)
+        .map(|(name, values)| {
+            let inferred = infer_dtype(name, &values)?;
+            let name = PlSmallStr::from(name.as_str());
+            let target = schema.and_then(|schema| schema.get(name.as_str()));
+            let series = match target {
+                Some(target) if inferred == DataType::Null => {
+                    Series::full_null(name, height, target)
+                }
+                Some(target) if *target != inferred => {
+                    build_series(name, values, &inferred)?.strict_cast(target)?
+                }
+                _ => build_series(name, values, &inferred)?,
+            };
+            Ok(Column::from(series))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    DataFrame::new_infer_height(columns)
+}
+
+/// One serialized value before it is split into columns.
+#[derive(Clone, Debug)]
+enum Value {
+    Null,
+    Bool(bool),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Int128(i128),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    UInt128(u128),
+    Float32(f32),
+    Float64(f64),
+    String(String),
+    Binary(Vec<u8>),
+    List(Vec<Self>),
+    Record(Vec<(String, Self)>),
+    /// A map's `(key, value)` entries, before they become a list of
+    /// [`Value::Struct`]s.
+    Map(Vec<(Self, Self)>),
+    /// One `Struct` value of a column, such as a map entry.
+    Struct(Vec<(String, Self)>),
+}
+
+impl Value {
+    const fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::List(_) => "a sequence",
+            Self::Record(_) => "a record",
+            Self::Map(_) => "a map",
+            Self::Struct(_) => "a struct",
+            _ => "a scalar",
+        }
+    }
+
+    fn holds_record(&self) -> bool {
+        match self {
+            Self::Record(_) => true,
+            Self::List(items) => items.iter().any(Self::holds_record),
+            _ => false,
+        }
+    }
+
+    /// Null, or lists holding nothing but such values: the shape a missing
+    /// record takes, which can stand in for every column nested under it.
+    fn is_empty_shape(&self) -> bool {
+        match self {
+            Self::Null => true,
+            Self::List(items) => items.iter().all(Self::is_empty_shape),
+            _ => false,
+        }
+    }
+
+    /// The dtype of a non-null scalar.
+    const fn scalar_dtype(&self) -> Option<DataType> {
+        Some(match self {
+            Self::Bool(_) => DataType::Boolean,
+            Self::Int8(_) => DataType::Int8,
+            Self::Int16(_) => DataType::Int16,
+            Self::Int32(_) => DataType::Int32,
+            Self::Int64(_) => DataType::Int64,
+            Self::Int128(_) => DataType::Int128,
+            Self::UInt8(_) => DataType::UInt8,
+            Self::UInt16(_) => DataType::UInt16,
+            Self::UInt32(_) => DataType::UInt32,
+            Self::UInt64(_) => DataType::UInt64,
+            Self::UInt128(_) => DataType::UInt128,
+            Self::Float32(_) => DataType::Float32,
+            Self::Float64(_) => DataType::Float64,
+            Self::String(_) => DataType::String,
+            Self::Binary(_) => DataType::Binary,
+            Self::Null | Self::List(_) | Self::Record(_) | Self::Map(_) | Self::Struct(_) => {
+                return None;
+            }
+        })
+    }
+
+    fn into_any_value(self, dtype: &DataType) -> PolarsResult<AnyValue<'static>> {
+        Ok(match self {
+            Self::Null | Self::Record(_) | Self::Map(_) => AnyValue::Null,
+            Self::Bool(v) => AnyValue::Boolean(v),
+            Self::Int8(v) => AnyValue::Int8(v),
+            Self::Int16(v) => AnyValue::Int16(v),
+            Self::Int32(v) => AnyValue::Int32(v),
+            Self::Int64(v) => AnyValue::Int64(v),
+            Self::Int128(v) => AnyValue::Int128(v),
+            Self::UInt8(v) => AnyValue::UInt8(v),
+            Self::UInt16(v) => AnyValue::UInt16(v),
+            Self::UInt32(v) => AnyValue::UInt32(v),
+            Self::UInt64(v) => AnyValue::UInt64(v),
+            Self::UInt128(v) => AnyValue::UInt128(v),
+            Self::Float32(v) => AnyValue::Float32(v),
+            Self::Float64(v) => AnyValue::Float64(v),
+            Self::String(v) => AnyValue::StringOwned(v.into()),
+            Self::Binary(v) => AnyValue::BinaryOwned(v),
+            Self::List(items) => {
+                let inner = match dtype {
+                    DataType::List(inner) => inner.as_ref(),
+                    _ => &DataType::Null,
+                };
+                AnyValue::List(build_series(PlSmallStr::EMPTY, items, inner)?)
+            }
+            Self::Struct(mut values) => {
+                let DataType::Struct(fields) = dtype else {
+                    return Ok(AnyValue::Null);
+                };
+                let values = fields
+                    .iter()
+                    .map(|field| {
+                        values
+                            .iter()
+                            .position(|(name, _)| *name == field.name)
+                            .map_or(Self::Null, |position| values.swap_remove(position).1)
+                            .into_any_value(&field.dtype)
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                AnyValue::StructOwned(Box::new((values, fields.clone())))
+            }
+        })
+    }
+
+    /// The value as it is stored in a column: maps become lists of
+    /// `Struct{key, value}` entries, with each side laid out the way
+    /// [`flatten`] lays out a row.
+    fn into_column_value(self) -> Result<Self, Error> {
+        Ok(match self {
+            Self::Map(entries) => Self::List(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Ok(Self::Struct(vec![
+                            ("key".to_owned(), key.into_entry_side()?),
+                            ("value".to_owned(), value.into_entry_side()?),
+                        ]))
+                    })
+                    .collect::<Result<_, Error>>()?,
+            ),
+            Self::List(items) => Self::List(
+                items
+                    .into_iter()
+                    .map(Self::into_column_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+            value => value,
+        })
+    }
+
+    /// A map key or value: a single unnamed column keeps its value, anything
+    /// else becomes a `Struct` of its columns.
+    fn into_entry_side(self) -> Result<Self, Error> {
+        let mut entries = Vec::new();
+        flatten("", self, &mut entries)?;
+        Ok(match entries.as_slice() {
+            [(path, _)] if path.is_empty() => entries.remove(0).1,
+            _ => Self::Struct(entries),
+        })
+    }
+}
+
+/// Merge `value`'s dtype into `dtype`, where `Null` means "not yet known".
+fn merge_dtype(dtype: &mut DataType, value: &Value) -> Result<(), DataType> {
+    if let Value::Struct(values) = value {
+        if *dtype == DataType::Null {
+            *dtype = DataType::Struct(Vec::new());
+        }
+        let DataType::Struct(fields) = dtype else {
+            return Err(DataType::Struct(Vec::new()));
+        };
+        for (name, value) in values {
+            let position = fields
+                .iter()
+                .position(|field| field.name == name.as_str())
+                .unwrap_or_else(|| {
+                    fields.push(Field::new(name.as_str().into(), DataType::Null));
+                    fields.len() - 1
+                });
+            merge_dtype(&mut fields[position].dtype, value)?;
+        }
+        return Ok(());
+    }
+    if let Value::List(items) = value {
+        if *dtype == DataType::Null {
+            *dtype = DataType::List(Box::new(DataType::Null));
+        }
+        let DataType::List(inner) = dtype else {
+            return Err(DataType::List(Box::new(DataType::Null)));
+        };
+        return items.iter().try_for_each(|item| merge_dtype(inner, item));
+    }
+    match value.scalar_dtype() {
+        None => Ok(()),
+        Some(found) if *dtype == DataType::Null => {
+            *dtype = found;
+            Ok(())
+        }
+        Some(found) if found == *dtype => Ok(()),
+        Some(found) => Err(found),
+    }
+}
+
+fn infer_dtype(name: &str, values: &[Value]) -> Result<DataType, Error> {
+    let mut dtype = DataType::Null;
+    for value in values {
+        merge_dtype(&mut dtype, value).map_err(|found| {
+            Error::new(format!(
+                "column `{name}` holds both {dtype:?} and {found:?} values"
+            ))
+        })?;
+    }
+    Ok(dtype)
+}
+
+fn build_series(name: PlSmallStr, values: Vec<Value>, dtype: &DataType) -> PolarsResult<Series> {
+    let values = values
+        .into_iter()
+        .map(|value| value.into_any_value(dtype))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Series::from_any_values_and_dtype(name, &values, dtype, true)
+}
+
+/// `prefix` names a record that `path` is nested in. The empty prefix is
+/// the row itself.
+fn is_ancestor(prefix: &str, path: &str) -> bool {
+    prefix.is_empty()
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Add `path` to the ordered column list. A path nested under an existing
+/// placeholder replaces it; other new paths go after the columns they share
+/// the longest parent path with, so late-appearing fields keep their
+/// struct's column order.
+fn add_path(names: &mut Vec<String>, path: &str) {
+    if names
+        .iter()
+        .any(|name| name == path || is_ancestor(path, name))
+    {
+        return;
+    }
+    if let Some(placeholder) = names.iter().position(|name| is_ancestor(name, path)) {
+        path.clone_into(&mut names[placeholder]);
+        return;
+    }
+    let shared = |name: &str| {
+        name.split('.')
+            .zip(path.split('.'))
+            .take_while(|(a, b)| a == b)
+            .count()
+    };
+    let longest = names.iter().map(|name| shared(name)).max().unwrap_or(0);
+    let position = if longest == 0 {
+        names.len()
+    } else {
+        names
+            .iter()
+            .rposition(|name| shared(name) == longest)
+            .map_or(names.len(), |last| last + 1)
+    };
+    names.insert(position, path.to_owned());
+}
+
+/// A row's column entries, indexed by path so each column is found without
+/// scanning the row.
+struct RowEntries {
+    entries: Vec<Option<(String, Value)>>,
+    index: HashMap<String, usize>,
+}
+
+impl RowEntries {
+    fn new(entries: Vec<(String, Value)>) -> Self {
+        let mut index = HashMap::with_capacity(entries.len());
+        for (position, (path, _)) in entries.iter().enumerate() {
+            index.entry(path.clone()).or_insert(position);
+        }
+        Self {
+            entries: entries.into_iter().map(Some).collect(),
+            index,
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<&(String, Value)> {
+        self.entries[*self.index.get(path)?].as_ref()
+    }
+
+    /// Remove the value for column `name`. A null or empty entry for an
+    /// enclosing record stands in for every column under it.
+    fn take(&mut self, name: &str) -> Result<Value, Error> {
+        if let Some(&position) = self.index.get(name)
+            && let Some((_, value)) = self.entries[position].take()
+        {
+            return Ok(value);
+        }
+        let ancestor = name
+            .match_indices('.')
+            .find_map(|(end, _)| self.get(&name[..end]));
+        match ancestor {
+            None => Ok(Value::Null),
+            Some((_, value)) if value.is_empty_shape() => Ok(value.clone()),
+            Some((path, value)) => Err(Error::new(format!(
+                "`{path}` holds {}, but column `{name}` is nested under it",
+                value.kind()
+            ))),
+        }
+    }
+
+    /// Paths of the entries no column has taken.
+    fn remaining(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().flatten().map(|(path, _)| path.as_str())
+    }
+}
+
+/// Flatten `value` into column entries under `prefix`.
+fn flatten(prefix: &str, value: Value, out: &mut Vec<(String, Value)>) -> Result<(), Error> {
+    match value {
+        Value::Record(fields) => {
+            for (name, value) in fields {
+                flatten(&nested_column_prefix(prefix, &name), value, out)?;
+            }
+        }
+        Value::List(items) if items.iter().any(Value::holds_record) => {
+            // One list column per nested field, each holding that field's
+            // value from every element.
+            let mut keys = Vec::new();
+            let mut elements = Vec::with_capacity(items.len());
+            for item in items {
+                let mut entries = Vec::new();
+                flatten("", item, &mut entries)?;
+                for (key, _) in &entries {
+                    add_path(&mut keys, key);
+                }
+                elements.push(RowEntries::new(entries));
+            }
+            for key in keys {
+                let column = elements
+                    .iter_mut()
+                    .map(|entries| entries.take(&key))
+                    .collect::<Result<Vec<_>, _>>()?;
+                out.push((nested_column_prefix(prefix, &key), Value::List(column)));
+            }
+        }
+        value => out.push((prefix.to_owned(), value.into_column_value()?)),
+    }
+    Ok(())
+}
+
+/// Serializes one value into a [`Value`] tree.
+struct ValueSerializer;
+
+macro_rules! serialize_scalars {
+    ($($method:ident($ty:ty) => $variant:ident),* $(,)?) => {$(
+        fn $method(self, v: $ty) -> Result<Value, Error> {
+            Ok(Value::$variant(v))
+        }
+    )*};
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = TupleSerializer;
+    type SerializeTupleStruct = TupleSerializer;
+    type SerializeTupleVariant = Impossible<Value, Error>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = RecordSerializer;
+    type SerializeStructVariant = Impossible<Value, Error>;
+
+    serialize_scalars! {
+        serialize_bool(bool) => Bool,
+        serialize_i8(i8) => Int8,
+        serialize_i16(i16) => Int16,
+        serialize_i32(i32) => Int32,
+        serialize_i64(i64) => Int64,
+        serialize_i128(i128) => Int128,
+        serialize_u8(u8) => UInt8,
+        serialize_u16(u16) => UInt16,
+        serialize_u32(u32) => UInt32,
+        serialize_u64(u64) => UInt64,
+        serialize_u128(u128) => UInt128,
+        serialize_f32(f32) => Float32,
+        serialize_f64(f64) => Float64,
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::Binary(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Record(Vec::new()))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        self.serialize_unit()
+    }
+
+    /// Unit variants become their name, like `as_str` enum fields.
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::String(variant.to_owned()))
+    }
+
+    /// A newtype struct is a one-field tuple struct, as in the derive.
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        Ok(Value::Record(vec![(
+            "field_0".to_owned(),
+            value.serialize(self)?,
+        )]))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _value: &T,
+    ) -> Result<Value, Error> {
+        Err(data_variant(name, variant))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<TupleSerializer, Error> {
+        Ok(TupleSerializer(Vec::with_capacity(len)))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<TupleSerializer, Error> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(data_variant(name, variant))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, Error> {
+        Ok(MapSerializer {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+            record: len.is_none(),
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<RecordSerializer, Error> {
+        Ok(RecordSerializer(Vec::with_capacity(len)))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(data_variant(name, variant))
+    }
+}
+
+fn data_variant(name: &str, variant: &str) -> Error {
+    Error::new(format!(
+        "enum variant `{name}::{variant}` carries data; only unit variants are supported"
+    ))
+}
+
+struct SeqSerializer(Vec<Value>);
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::List(self.0))
+    }
+}
+
+/// Tuples and tuple structs name their elements `field_N`.
+struct TupleSerializer(Vec<(String, Value)>);
+
+impl TupleSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let name = format!("field_{}", self.0.len());
+        self.0.push((name, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for TupleSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Record(self.0))
+    }
+}
+
+impl ser::SerializeTupleStruct for TupleSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Record(self.0))
+    }
+}
+
+struct RecordSerializer(Vec<(String, Value)>);
+
+impl ser::SerializeStruct for RecordSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.0
+            .push((key.to_owned(), value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Record(self.0))
+    }
+}
+
+/// Maps of known length (`HashMap`, `BTreeMap`) keep their entries. A map
+/// without a length is a struct with `#[serde(flatten)]` fields, which is a
+/// record keyed by its string or integer keys.
+struct MapSerializer {
+    entries: Vec<(Value, Value)>,
+    key: Option<Value>,
+    record: bool,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("map value serialized before its key"))?;
+        self.entries.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        if !self.record {
+            return Ok(Value::Map(self.entries));
+        }
+        self.entries
+            .into_iter()
+            .map(|(key, value)| Ok((key_name(key)?, value)))
+            .collect::<Result<_, _>>()
+            .map(Value::Record)
+    }
+}
+
+/// The column name for a map key of a map row or flattened struct.
+fn key_name(key: Value) -> Result<String, Error> {
+    Ok(match key {
+        Value::String(name) => name,
+        Value::Bool(v) => v.to_string(),
+        Value::Int8(v) => v.to_string(),
+        Value::Int16(v) => v.to_string(),
+        Value::Int32(v) => v.to_string(),
+        Value::Int64(v) => v.to_string(),
+        Value::Int128(v) => v.to_string(),
+        Value::UInt8(v) => v.to_string(),
+        Value::UInt16(v) => v.to_string(),
+        Value::UInt32(v) => v.to_string(),
+        Value::UInt64(v) => v.to_string(),
+        Value::UInt128(v) => v.to_string(),
+        Value::Record(mut fields) if fields.len() == 1 && fields[0].0 == "field_0" => {
+            return key_name(fields.remove(0).1);
+        }
+        Value::Float32(_) | Value::Float64(_) => return Err(unsupported_key("a float")),
+        Value::Binary(_) => return Err(unsupported_key("bytes")),
+        Value::Null => return Err(unsupported_key("`None`")),
+        other => return Err(unsupported_key(other.kind())),
+    })
+}
+
+fn unsupported_key(kind: &str) -> Error {
+    Error::new(format!(
+        "map keys become column names and must be strings, integers, or unit variants, not {kind}"
+    ))
+}
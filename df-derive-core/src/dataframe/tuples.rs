@@ -0,0 +1,205 @@
+//! `ToDataFrame` / `Columnar` for tuples of row types, so `Vec<(Trade, Quote)>`
+//! converts without a wrapper struct.
+//!
+//! A tuple's frame is its elements' frames side by side. Each element's
+//! columns are prefixed like a nested field: by default `field_0.`,
+//! `field_1.`, and so on, matching the derive's naming for tuple fields.
+//! [`to_dataframe_with_prefixes`] and [`schema_with_prefixes`] take one
+//! prefix per element instead; an empty prefix keeps that element's column
+//! names as they are.
+//!
+//! ```ignore
+//! let df = to_dataframe_with_prefixes(&pairs, &["trade", "quote"])?;
+//! // trade.symbol, trade.price, quote.bid, quote.ask
+//! ```
+//!
+//! Impls cover tuples of one to eight elements.
+
+use polars::prelude::{
+    ArrowSchema, Column, DataFrame, DataType, PlSmallStr, PolarsResult, polars_bail,
+};
+
+use super::arrow::arrow_schema_from_fields;
+use super::columns::nested_column_prefix;
+use super::{ColumnMetadata, Columnar, ToDataFrame};
+
+/// A tuple whose elements are all row types. Implemented for tuples of one to
+/// eight `ToDataFrame + Columnar` elements.
+pub trait TupleRow: Sized {
+    /// Number of elements, and so of prefixes.
+    const ARITY: usize;
+
+    #[doc(hidden)]
+    fn element_frames(items: &[&Self]) -> PolarsResult<Vec<DataFrame>>;
+    #[doc(hidden)]
+    fn element_schemas() -> PolarsResult<Vec<Vec<(String, DataType)>>>;
+    #[doc(hidden)]
+    fn element_arrow_schemas() -> PolarsResult<Vec<ArrowSchema>>;
+    #[doc(hidden)]
+    fn element_metadata() -> PolarsResult<Vec<Vec<(String, ColumnMetadata)>>>;
+}
+
+/// Convert tuple rows to a `DataFrame`, prefixing element `i`'s columns with
+/// `prefixes[i]`.
+///
+/// # Errors
+/// Returns an error if `prefixes` does not have one entry per element, if two
+/// elements produce the same column name, or if `DataFrame` construction
+/// fails.
+pub fn to_dataframe_with_prefixes<T: TupleRow>(
+    rows: &[T],
+    prefixes: &[&str],
+) -> PolarsResult<DataFrame> {
+    check_arity::<T>(prefixes)?;
+    let refs: Vec<&T> = rows.iter().collect();
+    prefixed_frame(rows.len(), T::element_frames(&refs)?, prefixes)
+}
+
+/// The schema [`to_dataframe_with_prefixes`] produces for `prefixes`.
+///
+/// # Errors
+/// Returns an error if `prefixes` does not have one entry per element, or if
+/// schema generation fails.
+pub fn schema_with_prefixes<T: TupleRow>(
+    prefixes: &[&str],
+) -> PolarsResult<Vec<(String, DataType)>> {
+    check_arity::<T>(prefixes)?;
+    Ok(prefixed(T::element_schemas()?, prefixes))
+}
+
+fn check_arity<T: TupleRow>(prefixes: &[&str]) -> PolarsResult<()> {
+    if prefixes.len() != T::ARITY {
+        polars_bail!(
+            InvalidOperation: "df-derive: expected {} tuple prefixes, got {}",
+            T::ARITY,
+            prefixes.len()
+        );
+    }
+    Ok(())
+}
+
+/// Call `f` with the default `field_0`, `field_1`, … prefixes.
+fn with_default_prefixes<R>(arity: usize, f: impl FnOnce(&[&str]) -> R) -> R {
+    let prefixes: Vec<String> = (0..arity).map(|index| format!("field_{index}")).collect();
+    let prefixes: Vec<&str> = prefixes.iter().map(String::as_str).collect();
+    f(&prefixes)
+}
+
+fn prefixed_frame(
+    height: usize,
+    frames: Vec<DataFrame>,
+    prefixes: &[&str],
+) -> PolarsResult<DataFrame> {
+    let mut columns: Vec<Column> = Vec::new();
+    for (frame, prefix) in frames.into_iter().zip(prefixes) {
+        for mut column in frame.into_columns() {
+            let name = nested_column_prefix(prefix, column.name());
+            column.rename(name.into());
+            columns.push(column);
+        }
+    }
+    DataFrame::new(height, columns)
+}
+
+fn prefixed<V>(elements: Vec<Vec<(String, V)>>, prefixes: &[&str]) -> Vec<(String, V)> {
+    elements
+        .into_iter()
+        .zip(prefixes)
+        .flat_map(|(entries, prefix)| {
+            entries
+                .into_iter()
+                .map(move |(name, value)| (nested_column_prefix(prefix, &name), value))
+        })
+        .collect()
+}
+
+fn prefixed_arrow_schema(elements: Vec<ArrowSchema>, prefixes: &[&str]) -> ArrowSchema {
+    let fields = elements
+        .into_iter()
+        .zip(prefixes)
+        .flat_map(|(schema, prefix)| {
+            schema.into_iter().map(move |(name, mut field)| {
+                field.name = PlSmallStr::from(nested_column_prefix(prefix, &name));
+                field
+            })
+        })
+        .collect();
+    arrow_schema_from_fields(fields)
+}
+
+macro_rules! tuple_row {
+    ($(($($element:ident . $index:tt),+) => $arity:literal;)*) => {$(
+        impl<$($element: ToDataFrame + Columnar),+> TupleRow for ($($element,)+) {
+            const ARITY: usize = $arity;
+
+            fn element_frames(items: &[&Self]) -> PolarsResult<Vec<DataFrame>> {
+                Ok(vec![$({
+                    if items.is_empty() {
+                        $element::empty_dataframe()?
+                    } else {
+                        let refs: Vec<&$element> = items.iter().map(|item| &item.$index).collect();
+                        $element::columnar_from_refs(&refs)?
+                    }
+                }),+])
+            }
+
+            fn element_schemas() -> PolarsResult<Vec<Vec<(String, DataType)>>> {
+                Ok(vec![$($element::schema()?),+])
+            }
+
+            fn element_arrow_schemas() -> PolarsResult<Vec<ArrowSchema>> {
+                Ok(vec![$($element::arrow_schema()?),+])
+            }
+
+            fn element_metadata() -> PolarsResult<Vec<Vec<(String, ColumnMetadata)>>> {
+                Ok(vec![$($element::column_metadata()?),+])
+            }
+        }
+
+        impl<$($element: ToDataFrame + Columnar),+> ToDataFrame for ($($element,)+) {
+            fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+                <Self as Columnar>::columnar_from_refs(&[self])
+            }
+
+            fn empty_dataframe() -> PolarsResult<DataFrame> {
+                <Self as Columnar>::columnar_from_refs(&[])
+            }
+
+            fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+                with_default_prefixes($arity, schema_with_prefixes::<Self>)
+            }
+
+            fn arrow_schema() -> PolarsResult<ArrowSchema> {
+                let elements = Self::element_arrow_schemas()?;
+                Ok(with_default_prefixes($arity, |prefixes| {
+                    prefixed_arrow_schema(elements, prefixes)
+                }))
+            }
+
+            fn column_metadata() -> PolarsResult<Vec<(String, ColumnMetadata)>> {
+                let elements = Self::element_metadata()?;
+                Ok(with_default_prefixes($arity, |prefixes| prefixed(elements, prefixes)))
+            }
+        }
+
+        impl<$($element: ToDataFrame + Columnar),+> Columnar for ($($element,)+) {
+            fn columnar_from_refs(items: &[&Self]) -> PolarsResult<DataFrame> {
+                let frames = Self::element_frames(items)?;
+                with_default_prefixes($arity, |prefixes| {
+                    prefixed_frame(items.len(), frames, prefixes)
+                })
+            }
+        }
+    )*};
+}
+
+tuple_row! {
+    (A.0) => 1;
+    (A.0, B.1) => 2;
+    (A.0, B.1, C.2) => 3;
+    (A.0, B.1, C.2, D.3) => 4;
+    (A.0, B.1, C.2, D.3, E.4) => 5;
+    (A.0, B.1, C.2, D.3, E.4, F.5) => 6;
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6) => 7;
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7) => 8;
+}
@@ -0,0 +1,199 @@
+//! `DataFrame`s that remember the type they were converted from.
+//!
+//! Derives targeting this runtime implement [`TypedFrame`] with a generated
+//! accessor type: one method per primitive column, returning the column's
+//! chunked array (`.price() -> &Float64Chunked`, `.symbol() ->
+//! &StringChunked`, `.fills() -> &ListChunked` for `Vec` fields).
+//! [`TypedDataFrame<T>`] derefs to those accessors, which in turn deref to
+//! the underlying [`DataFrame`], so both typed accessors and every
+//! `DataFrame` method are available on it.
+//!
+//! A `TypedDataFrame<T>` is built either from `T` rows or from a
+//! `DataFrame` whose columns match `T::schema()` exactly, and it offers no
+//! mutable access, so the accessors never see a column with another dtype.
+
+use std::any::type_name;
+use std::fmt;
+use std::fmt::Write as _;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use polars::prelude::{
+    Column, DataFrame, DataType, PlSmallStr, PolarsResult, Schema, TimeChunked, polars_bail,
+};
+
+use super::{Columnar, ToDataFrame, ToDataFrameVec};
+
+/// Types with generated typed column accessors.
+pub trait TypedFrame {
+    /// The generated accessor type wrapping a validated `DataFrame`.
+    type Frame: FrameAccessors;
+}
+
+/// Construction interface of a generated accessor type.
+#[doc(hidden)]
+pub trait FrameAccessors: Deref<Target = DataFrame> {
+    fn from_validated(frame: DataFrame) -> Self;
+
+    fn into_dataframe(self) -> DataFrame;
+}
+
+/// A `DataFrame` whose columns are known to match `T::schema()`.
+pub struct TypedDataFrame<T: TypedFrame> {
+    frame: T::Frame,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: TypedFrame> TypedDataFrame<T> {
+    /// Convert `rows` and keep the result typed.
+    ///
+    /// # Errors
+    /// Returns an error if `DataFrame` construction fails.
+    pub fn from_rows(rows: &[T]) -> PolarsResult<Self>
+    where
+        T: Columnar + ToDataFrame,
+    {
+        Ok(Self::new_unchecked(rows.to_dataframe()?))
+    }
+
+    /// Wrap `frame` after checking it against `T::schema()`.
+    ///
+    /// Every derived column must be present with exactly its derived dtype,
+    /// and no other column may be present. Columns are reordered into
+    /// schema order.
+    ///
+    /// # Errors
+    /// Returns a `SchemaMismatch` error listing every missing, mistyped, or
+    /// unexpected column, or an error if schema generation fails.
+    pub fn try_from_dataframe(frame: DataFrame) -> PolarsResult<Self>
+    where
+        T: ToDataFrame,
+    {
+        let expected = T::schema()?;
+        let actual = frame.schema();
+        let mut problems = column_mismatches(&expected, actual);
+        for name in actual.iter_names() {
+            if !expected
+                .iter()
+                .any(|(expected, _)| expected == name.as_str())
+            {
+                let _ = write!(problems, "\n  unexpected column `{name}`");
+            }
+        }
+        if !problems.is_empty() {
+            polars_bail!(
+                SchemaMismatch: "DataFrame does not match `{}`:{problems}",
+                type_name::<T>()
+            );
+        }
+        let in_order = actual
+            .iter_names()
+            .map(PlSmallStr::as_str)
+            .eq(expected.iter().map(|(name, _)| name.as_str()));
+        let frame = if in_order {
+            frame
+        } else {
+            frame.select(expected.into_iter().map(|(name, _)| name))?
+        };
+        Ok(Self::new_unchecked(frame))
+    }
+
+    fn new_unchecked(frame: DataFrame) -> Self {
+        Self {
+            frame: T::Frame::from_validated(frame),
+            marker: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn as_dataframe(&self) -> &DataFrame {
+        &self.frame
+    }
+
+    /// Drop the type, e.g. to mutate the frame.
+    #[must_use]
+    pub fn into_inner(self) -> DataFrame {
+        self.frame.into_dataframe()
+    }
+}
+
+impl<T: TypedFrame> Deref for TypedDataFrame<T> {
+    type Target = T::Frame;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl<T: TypedFrame> Clone for TypedDataFrame<T> {
+    fn clone(&self) -> Self {
+        Self::new_unchecked(self.as_dataframe().clone())
+    }
+}
+
+impl<T: TypedFrame> fmt::Debug for TypedDataFrame<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_dataframe(), f)
+    }
+}
+
+impl<T: TypedFrame> fmt::Display for TypedDataFrame<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_dataframe(), f)
+    }
+}
+
+impl<T: TypedFrame + ToDataFrame> TryFrom<DataFrame> for TypedDataFrame<T> {
+    type Error = polars::prelude::PolarsError;
+
+    fn try_from(frame: DataFrame) -> PolarsResult<Self> {
+        Self::try_from_dataframe(frame)
+    }
+}
+
+impl<T: TypedFrame> From<TypedDataFrame<T>> for DataFrame {
+    fn from(frame: TypedDataFrame<T>) -> Self {
+        frame.into_inner()
+    }
+}
+
+/// Missing and mistyped columns of `actual`, one indented line each.
+pub(super) fn column_mismatches(expected: &[(String, DataType)], actual: &Schema) -> String {
+    let mut problems = String::new();
+    for (name, dtype) in expected {
+        match actual.get(name.as_str()) {
+            None => {
+                let _ = write!(problems, "\n  missing column `{name}`");
+            }
+            Some(found) if found != dtype => {
+                let _ = write!(
+                    problems,
+                    "\n  column `{name}` is {found:?}, expected {dtype:?}"
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    problems
+}
+
+/// Column lookup behind the generated accessors. The frame was validated on
+/// construction, so a failure here means the accessor type was built
+/// through the hidden [`FrameAccessors`] API with an unchecked frame.
+#[doc(hidden)]
+pub fn typed_column<'a, C>(
+    frame: &'a DataFrame,
+    name: &str,
+    downcast: fn(&'a Column) -> PolarsResult<&'a C>,
+) -> &'a C {
+    match frame.column(name).and_then(downcast) {
+        Ok(column) => column,
+        Err(error) => panic!("typed column `{name}` does not match its derived dtype: {error}"),
+    }
+}
+
+/// `Column` has no `time()` downcast; go through the materialized series.
+#[doc(hidden)]
+pub fn time_column(column: &Column) -> PolarsResult<&TimeChunked> {
+    column.as_materialized_series().time()
+}
@@ -0,0 +1,265 @@
+//! Check a schema against a derived type without touching any data.
+//!
+//! [`validate_schema`] (also reachable as `T::validate_schema`) compares any
+//! Polars [`Schema`] with `T::schema()` and returns every [`Mismatch`] at
+//! once. Dtypes must match exactly; list nesting depth and decimal
+//! precision/scale differences are reported as their own variants, since they
+//! are the ones a producer most often gets subtly wrong.
+//!
+//! `read_parquet_schema` (feature `parquet`) and `read_ipc_schema` (feature
+//! `ipc`) read only a file's footer, so a file can be admitted or rejected
+//! before any row group or record batch is loaded:
+//!
+//! ```ignore
+//! let schema = read_parquet_schema(&mut File::open(path)?)?;
+//! Trade::validate_schema(&schema)?;
+//! ```
+//!
+//! Column order and nullability are not checked: Polars' own writers mark
+//! every column nullable, so a required flag in the file says little.
+
+use std::any::type_name;
+use std::fmt;
+
+use polars::prelude::{DataType, PolarsError, Schema};
+#[cfg(any(feature = "parquet", feature = "ipc"))]
+use polars::prelude::{PolarsResult, SchemaExt as _};
+
+use super::ToDataFrame;
+
+/// One way a schema differs from the derived one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mismatch {
+    /// A derived column is absent.
+    MissingColumn { name: String, expected: DataType },
+    /// A column `T` does not derive.
+    UnexpectedColumn { name: String, found: DataType },
+    /// The column is nested in a different number of lists.
+    ListDepth {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    /// The column's decimals (at the same list depth) differ in precision or
+    /// scale, as `(precision, scale)`.
+    Decimal {
+        name: String,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// Any other dtype difference.
+    Dtype {
+        name: String,
+        expected: DataType,
+        found: DataType,
+    },
+    /// `T::schema()` itself failed, so nothing could be compared.
+    SchemaUnavailable { message: String },
+}
+
+impl Mismatch {
+    /// The column the mismatch is about, if any.
+    #[must_use]
+    pub fn column(&self) -> Option<&str> {
+        match self {
+            Self::MissingColumn { name, .. }
+            | Self::UnexpectedColumn { name, .. }
+            | Self::ListDepth { name, .. }
+            | Self::Decimal { name, .. }
+            | Self::Dtype { name, .. } => Some(name),
+            Self::SchemaUnavailable { .. } => None,
+        }
+    }
+
+    fn between(name: &str, expected: &DataType, found: &DataType) -> Self {
+        let (expected_depth, expected_leaf) = list_depth(expected);
+        let (found_depth, found_leaf) = list_depth(found);
+        let name = name.to_owned();
+        match (expected_leaf, found_leaf) {
+            _ if expected_depth != found_depth => Self::ListDepth {
+                name,
+                expected: expected_depth,
+                found: found_depth,
+            },
+            (
+                DataType::Decimal(expected_precision, expected_scale),
+                DataType::Decimal(found_precision, found_scale),
+            ) => Self::Decimal {
+                name,
+                expected: (*expected_precision, *expected_scale),
+                found: (*found_precision, *found_scale),
+            },
+            _ => Self::Dtype {
+                name,
+                expected: expected.clone(),
+                found: found.clone(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingColumn { name, expected } => {
+                write!(f, "missing column `{name}` ({expected:?})")
+            }
+            Self::UnexpectedColumn { name, found } => {
+                write!(f, "unexpected column `{name}` ({found:?})")
+            }
+            Self::ListDepth {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column `{name}` is nested in {found} list(s), expected {expected}"
+            ),
+            Self::Decimal {
+                name,
+                expected: (expected_precision, expected_scale),
+                found: (found_precision, found_scale),
+            } => write!(
+                f,
+                "column `{name}` is Decimal({found_precision}, {found_scale}), \
+                 expected Decimal({expected_precision}, {expected_scale})"
+            ),
+            Self::Dtype {
+                name,
+                expected,
+                found,
+            } => write!(f, "column `{name}` is {found:?}, expected {expected:?}"),
+            Self::SchemaUnavailable { message } => {
+                write!(f, "derived schema is unavailable: {message}")
+            }
+        }
+    }
+}
+
+/// Every [`Mismatch`] between a schema and the one derived for a type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaMismatch {
+    type_name: &'static str,
+    mismatches: Vec<Mismatch>,
+}
+
+impl SchemaMismatch {
+    /// The type the schema was validated against.
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Mismatches in derived column order, followed by unexpected columns.
+    #[must_use]
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "schema does not match `{}`", self.type_name)?;
+        for mismatch in &self.mismatches {
+            write!(f, "\n  {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaMismatch {}
+
+impl From<SchemaMismatch> for PolarsError {
+    fn from(error: SchemaMismatch) -> Self {
+        Self::SchemaMismatch(error.to_string().into())
+    }
+}
+
+/// Validate `schema` against `T::schema()`.
+///
+/// # Errors
+/// Returns every missing, mistyped, and unexpected column, or
+/// [`Mismatch::SchemaUnavailable`] if `T`'s schema generation fails.
+pub fn validate_schema<T: ToDataFrame>(schema: &Schema) -> Result<(), SchemaMismatch> {
+    let fail = |mismatches| SchemaMismatch {
+        type_name: type_name::<T>(),
+        mismatches,
+    };
+    let expected = T::schema().map_err(|error| {
+        fail(vec![Mismatch::SchemaUnavailable {
+            message: error.to_string(),
+        }])
+    })?;
+
+    let mut mismatches = Vec::new();
+    for (name, dtype) in &expected {
+        match schema.get(name.as_str()) {
+            None => mismatches.push(Mismatch::MissingColumn {
+                name: name.clone(),
+                expected: dtype.clone(),
+            }),
+            Some(found) if found != dtype => {
+                mismatches.push(Mismatch::between(name, dtype, found));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, found) in schema.iter() {
+        if !expected
+            .iter()
+            .any(|(expected, _)| expected == name.as_str())
+        {
+            mismatches.push(Mismatch::UnexpectedColumn {
+                name: name.to_string(),
+                found: found.clone(),
+            });
+        }
+    }
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(fail(mismatches))
+    }
+}
+
+/// Read the schema of a Parquet file from its footer, without reading any
+/// row group.
+///
+/// # Errors
+/// Returns an error if the footer cannot be read or decoded.
+#[cfg(feature = "parquet")]
+pub fn read_parquet_schema<R>(reader: &mut R) -> PolarsResult<Schema>
+where
+    R: std::io::Read + std::io::Seek,
+{
+    use polars_parquet::read::{infer_schema, read_metadata};
+
+    let metadata = read_metadata(reader)?;
+    Ok(Schema::from_arrow_schema(&infer_schema(&metadata)?))
+}
+
+/// Read the schema of an Arrow IPC file from its footer, without reading any
+/// record batch.
+///
+/// # Errors
+/// Returns an error if the footer cannot be read or decoded.
+#[cfg(feature = "ipc")]
+pub fn read_ipc_schema<R>(reader: &mut R) -> PolarsResult<Schema>
+where
+    R: std::io::Read + std::io::Seek,
+{
+    use polars_arrow::io::ipc::read::read_file_metadata;
+
+    let metadata = read_file_metadata(reader)?;
+    Ok(Schema::from_arrow_schema(&metadata.schema))
+}
+
+/// The number of lists wrapping `dtype`, and the dtype inside them.
+fn list_depth(mut dtype: &DataType) -> (usize, &DataType) {
+    let mut depth = 0;
+    while let DataType::List(inner) = dtype {
+        depth += 1;
+        dtype = inner;
+    }
+    (depth, dtype)
+}
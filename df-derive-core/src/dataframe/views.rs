@@ -0,0 +1,308 @@
+//! Borrowed row views over a `DataFrame`.
+//!
+//! `#[df_derive(views)]` generates a `{T}View<'df>` struct with one public
+//! field per column and implements [`RowView`] for `T`. `T::iter_views(&df)`
+//! checks the frame against `T::schema()` once and then yields views whose
+//! `&str` and `&[u8]` fields borrow from the frame's Arrow buffers. Numeric
+//! and `bool` fields are copied out; temporal and decimal fields expose
+//! their physical integer (epoch value in the column's unit, days since the
+//! epoch, nanoseconds since midnight, or the decimal mantissa). `Vec` fields
+//! become [`ListView`] iterators over the list's values and nested structs
+//! become the nested type's view.
+
+use std::fmt;
+use std::fmt::Write as _;
+use std::ops::Range;
+
+use polars::prelude::{ArrowDataType, Column, DataFrame, ListChunked, PolarsResult, polars_bail};
+use polars_arrow::array::{
+    Array, BinaryViewArray, BooleanArray, FixedSizeListArray, ListArray, PrimitiveArray,
+    Utf8ViewArray,
+};
+
+use super::ToDataFrame;
+use super::columns::nested_column_prefix;
+use super::typed::column_mismatches;
+
+/// Types with a generated borrowed row view.
+pub trait RowView {
+    /// The generated `{T}View<'df>` struct.
+    type View<'df>;
+
+    #[doc(hidden)]
+    type Reader<'df>: ViewReader<'df, View = Self::View<'df>>;
+
+    /// Resolve the columns flattened under `prefix`; the empty prefix names
+    /// top-level columns.
+    #[doc(hidden)]
+    fn view_reader<'df>(frame: &'df DataFrame, prefix: &str) -> PolarsResult<Self::Reader<'df>>;
+}
+
+/// Builds one row's view from resolved columns.
+#[doc(hidden)]
+pub trait ViewReader<'df> {
+    type View;
+
+    fn view(&self, row: usize) -> Self::View;
+}
+
+/// Iterator over the rows of a frame as `T::View<'df>`.
+pub struct RowViews<'df, T: RowView> {
+    reader: T::Reader<'df>,
+    rows: Range<usize>,
+}
+
+impl<'df, T: RowView> RowViews<'df, T> {
+    /// The view of `row`, or `None` past the end of the frame.
+    pub fn get(&self, row: usize) -> Option<T::View<'df>> {
+        (row < self.rows.end).then(|| self.reader.view(row))
+    }
+}
+
+impl<'df, T: RowView> Iterator for RowViews<'df, T> {
+    type Item = T::View<'df>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+        Some(self.reader.view(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<T: RowView> DoubleEndedIterator for RowViews<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next_back()?;
+        Some(self.reader.view(row))
+    }
+}
+
+impl<T: RowView> ExactSizeIterator for RowViews<'_, T> {}
+
+/// Views over every row of `frame`.
+///
+/// Every derived column must be present with its derived dtype, and columns
+/// of non-`Option` fields must not contain nulls, nor may the elements of
+/// `Vec`s whose element type is not an `Option`. Other columns are ignored.
+///
+/// # Errors
+/// Returns a `SchemaMismatch` error listing every missing, mistyped, or
+/// unexpectedly null column, or an error if schema generation fails.
+pub fn iter_views<T: RowView + ToDataFrame>(frame: &DataFrame) -> PolarsResult<RowViews<'_, T>> {
+    let mut problems = column_mismatches(&T::schema()?, frame.schema());
+    if problems.is_empty() {
+        for field in T::arrow_schema()?.iter_values() {
+            let column = frame.column(field.name.as_str())?;
+            let nulls = if field.is_nullable {
+                0
+            } else {
+                column.null_count()
+            };
+            if nulls > 0 {
+                let _ = write!(
+                    problems,
+                    "\n  column `{}` of a non-`Option` field has {nulls} null(s)",
+                    field.name
+                );
+            }
+            let element_nulls: usize = column
+                .as_materialized_series()
+                .chunks()
+                .iter()
+                .map(|chunk| element_nulls(chunk.as_ref(), &field.dtype))
+                .sum();
+            if element_nulls > 0 {
+                let _ = write!(
+                    problems,
+                    "\n  column `{}` has {element_nulls} null element(s) in a non-`Option` list",
+                    field.name
+                );
+            }
+        }
+    }
+    if !problems.is_empty() {
+        polars_bail!(
+            SchemaMismatch: "DataFrame cannot be viewed as `{}`:{problems}",
+            std::any::type_name::<T>()
+        );
+    }
+    Ok(RowViews {
+        reader: T::view_reader(frame, "")?,
+        rows: 0..frame.height(),
+    })
+}
+
+/// Nulls among the elements of every list layer of `array` whose item field
+/// in `dtype` is non-nullable; [`ViewElement::read`] assumes those slots
+/// are valid.
+fn element_nulls(array: &dyn Array, dtype: &ArrowDataType) -> usize {
+    let (values, item) = match dtype {
+        ArrowDataType::LargeList(item) => match array.as_any().downcast_ref::<ListArray<i64>>() {
+            Some(list) => (list.values().as_ref(), item),
+            None => return 0,
+        },
+        ArrowDataType::FixedSizeList(item, _) => {
+            match array.as_any().downcast_ref::<FixedSizeListArray>() {
+                Some(list) => (list.values().as_ref(), item),
+                None => return 0,
+            }
+        }
+        _ => return 0,
+    };
+    let own = if item.is_nullable {
+        0
+    } else {
+        values.null_count()
+    };
+    own + element_nulls(values, &item.dtype)
+}
+
+/// A value read from one slot of an Arrow array inside a [`ListView`].
+pub trait ViewElement<'df>: Sized {
+    #[doc(hidden)]
+    type Array: Array + 'static;
+
+    #[doc(hidden)]
+    fn read(array: &'df Self::Array, index: usize) -> Self;
+}
+
+macro_rules! impl_primitive_view_element {
+    ($($native:ty),* $(,)?) => {$(
+        impl ViewElement<'_> for $native {
+            type Array = PrimitiveArray<$native>;
+
+            fn read(array: &Self::Array, index: usize) -> Self {
+                array.value(index)
+            }
+        }
+    )*};
+}
+
+impl_primitive_view_element!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+
+impl ViewElement<'_> for bool {
+    type Array = BooleanArray;
+
+    fn read(array: &Self::Array, index: usize) -> Self {
+        array.value(index)
+    }
+}
+
+impl<'df> ViewElement<'df> for &'df str {
+    type Array = Utf8ViewArray;
+
+    fn read(array: &'df Self::Array, index: usize) -> Self {
+        array.value(index)
+    }
+}
+
+impl<'df> ViewElement<'df> for &'df [u8] {
+    type Array = BinaryViewArray;
+
+    fn read(array: &'df Self::Array, index: usize) -> Self {
+        array.value(index)
+    }
+}
+
+impl<'df, E: ViewElement<'df>> ViewElement<'df> for Option<E> {
+    type Array = E::Array;
+
+    fn read(array: &'df Self::Array, index: usize) -> Self {
+        array.is_valid(index).then(|| E::read(array, index))
+    }
+}
+
+/// Borrowed iterator over the values of one list cell.
+pub struct ListView<'df, E: ViewElement<'df>> {
+    values: Option<&'df E::Array>,
+    range: Range<usize>,
+}
+
+impl<'df, E: ViewElement<'df>> Iterator for ListView<'df, E> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        let index = self.range.next()?;
+        self.values.map(|values| E::read(values, index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'df, E: ViewElement<'df>> DoubleEndedIterator for ListView<'df, E> {
+    fn next_back(&mut self) -> Option<E> {
+        let index = self.range.next_back()?;
+        self.values.map(|values| E::read(values, index))
+    }
+}
+
+impl<'df, E: ViewElement<'df>> ExactSizeIterator for ListView<'df, E> {}
+
+/// An empty list.
+impl<'df, E: ViewElement<'df>> Default for ListView<'df, E> {
+    fn default() -> Self {
+        Self {
+            values: None,
+            range: 0..0,
+        }
+    }
+}
+
+impl<'df, E: ViewElement<'df>> Clone for ListView<'df, E> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values,
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<'df, E: ViewElement<'df> + fmt::Debug> fmt::Debug for ListView<'df, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[doc(hidden)]
+pub fn view_column<'df, C>(
+    frame: &'df DataFrame,
+    prefix: &str,
+    name: &str,
+    downcast: fn(&'df Column) -> PolarsResult<&'df C>,
+) -> PolarsResult<&'df C> {
+    downcast(frame.column(&nested_column_prefix(prefix, name))?)
+}
+
+/// The list cell at `row`, or `None` when the cell is null.
+#[doc(hidden)]
+pub fn list_view<'df, E: ViewElement<'df>>(
+    column: &'df ListChunked,
+    mut row: usize,
+) -> Option<ListView<'df, E>> {
+    for chunk in column.downcast_iter() {
+        if row >= chunk.len() {
+            row -= chunk.len();
+            continue;
+        }
+        if !chunk.is_valid(row) {
+            return None;
+        }
+        let (start, end) = chunk.offsets().start_end(row);
+        let values = chunk.values().as_any().downcast_ref::<E::Array>();
+        assert!(
+            values.is_some() || start == end,
+            "list column `{}` does not hold {} values",
+            column.name(),
+            std::any::type_name::<E>()
+        );
+        return Some(ListView {
+            values,
+            range: start..end,
+        });
+    }
+    None
+}
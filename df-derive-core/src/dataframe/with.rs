@@ -0,0 +1,234 @@
+//! Fields converted through `#[df_derive(with = "path")]`.
+//!
+//! A `with` field is written as the columns of whatever its function
+//! returns. The function's result type is never named in generated code:
+//! the derive emits one marker type per field whose [`WithConversion`] impl
+//! hands the function to a [`WithVisitor`], and each visitor receives the
+//! result type as a generic parameter. [`WithColumns`] describes and builds
+//! the result's columns through those visitors. A field that also has
+//! `dtype = "..."` must return a single numeric, temporal, or decimal
+//! column, which is cast to the target dtype.
+
+use std::marker::PhantomData;
+
+use polars::prelude::{
+    ArrowSchema, Column, CompatLevel, DataFrame, DataType, PolarsResult, polars_bail,
+};
+
+use super::columns::{ColumnHandles, ColumnRef, column_ref};
+use super::metadata::ColumnMetadata;
+use super::{Columnar, ToDataFrame};
+
+/// Operation run with a `with` field's function and its result type.
+pub trait WithVisitor<X: ?Sized> {
+    type Output;
+
+    fn visit<U, F>(self, f: F) -> Self::Output
+    where
+        F: Fn(&X) -> U,
+        U: ToDataFrame + Columnar;
+}
+
+/// Implemented by the derive's per-field marker types.
+pub trait WithConversion {
+    /// Value the function reads, after the field's `Option` and list
+    /// wrappers are peeled.
+    type Source: ?Sized;
+
+    fn convert<V: WithVisitor<Self::Source>>(visitor: V) -> V::Output;
+
+    /// Target dtype of the field's `dtype = "..."` attribute, and whether
+    /// values it cannot hold become nulls.
+    #[must_use]
+    fn leaf_cast() -> Option<(DataType, bool)> {
+        None
+    }
+
+    /// Cast the result's column to [`leaf_cast`](Self::leaf_cast)'s dtype.
+    ///
+    /// # Errors
+    /// Returns an error if a value does not fit the target dtype.
+    fn cast_columns(columns: &mut [Column]) -> PolarsResult<()> {
+        let _ = columns;
+        Ok(())
+    }
+}
+
+/// Type-level description of a `with` field's columns. Never constructed;
+/// generated code only calls its associated functions.
+pub struct WithColumns<M>(PhantomData<fn() -> M>);
+
+/// `Columnar` for [`WithColumns`]: converts the field values rather than
+/// values of the adapter type.
+pub trait WithColumnar {
+    type Source: ?Sized;
+
+    /// # Errors
+    /// Returns an error if `DataFrame` construction fails.
+    fn columnar_from_refs(items: &[&Self::Source]) -> PolarsResult<DataFrame>;
+}
+
+struct Schema;
+
+impl<X: ?Sized> WithVisitor<X> for Schema {
+    type Output = PolarsResult<Vec<(String, DataType)>>;
+
+    fn visit<U, F>(self, _f: F) -> Self::Output
+    where
+        F: Fn(&X) -> U,
+        U: ToDataFrame + Columnar,
+    {
+        U::schema()
+    }
+}
+
+struct ArrowSchemaOf;
+
+impl<X: ?Sized> WithVisitor<X> for ArrowSchemaOf {
+    type Output = PolarsResult<ArrowSchema>;
+
+    fn visit<U, F>(self, _f: F) -> Self::Output
+    where
+        F: Fn(&X) -> U,
+        U: ToDataFrame + Columnar,
+    {
+        U::arrow_schema()
+    }
+}
+
+struct Metadata;
+
+impl<X: ?Sized> WithVisitor<X> for Metadata {
+    type Output = PolarsResult<Vec<(String, ColumnMetadata)>>;
+
+    fn visit<U, F>(self, _f: F) -> Self::Output
+    where
+        F: Fn(&X) -> U,
+        U: ToDataFrame + Columnar,
+    {
+        U::column_metadata()
+    }
+}
+
+struct Empty;
+
+impl<X: ?Sized> WithVisitor<X> for Empty {
+    type Output = PolarsResult<DataFrame>;
+
+    fn visit<U, F>(self, _f: F) -> Self::Output
+    where
+        F: Fn(&X) -> U,
+        U: ToDataFrame + Columnar,
+    {
+        U::empty_dataframe()
+    }
+}
+
+struct Convert<'a, 'b, X: ?Sized> {
+    items: &'a [&'b X],
+}
+
+impl<X: ?Sized> WithVisitor<X> for Convert<'_, '_, X> {
+    type Output = PolarsResult<DataFrame>;
+
+    fn visit<U, F>(self, f: F) -> Self::Output
+    where
+        F: Fn(&X) -> U,
+        U: ToDataFrame + Columnar,
+    {
+        let values: Vec<U> = self.items.iter().map(|&item| f(item)).collect();
+        let refs: Vec<&U> = values.iter().collect();
+        U::columnar_from_refs(&refs)
+    }
+}
+
+/// Check that a `dtype` field's function returned one column the cast
+/// applies to.
+fn check_castable(schema: &[(String, DataType)]) -> PolarsResult<()> {
+    match schema {
+        [(_, dtype)]
+            if dtype.is_primitive_numeric() || dtype.is_temporal() || dtype.is_decimal() =>
+        {
+            Ok(())
+        }
+        [(_, dtype)] => polars_bail!(
+            ComputeError:
+            "df-derive: a `with` field with `dtype` must return a numeric, temporal, or decimal value, got {}",
+            dtype,
+        ),
+        columns => polars_bail!(
+            ComputeError:
+            "df-derive: a `with` field with `dtype` must return one column, got {}",
+            columns.len(),
+        ),
+    }
+}
+
+fn cast_frame<M: WithConversion>(df: DataFrame) -> PolarsResult<DataFrame> {
+    if M::leaf_cast().is_none() {
+        return Ok(df);
+    }
+    let schema = df
+        .columns()
+        .iter()
+        .map(|column| (column.name().to_string(), column.dtype().clone()))
+        .collect::<Vec<_>>();
+    check_castable(&schema)?;
+    let height = df.height();
+    let mut columns = df.into_columns();
+    M::cast_columns(&mut columns)?;
+    DataFrame::new(height, columns)
+}
+
+impl<M: WithConversion> ToDataFrame for WithColumns<M> {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        unreachable!("`WithColumns` is a type-level adapter and is never constructed")
+    }
+
+    fn empty_dataframe() -> PolarsResult<DataFrame> {
+        cast_frame::<M>(M::convert(Empty)?)
+    }
+
+    fn schema() -> PolarsResult<Vec<(String, DataType)>> {
+        let mut schema = M::convert(Schema)?;
+        if let Some((leaf, _)) = M::leaf_cast() {
+            check_castable(&schema)?;
+            schema[0].1 = leaf;
+        }
+        Ok(schema)
+    }
+
+    fn arrow_schema() -> PolarsResult<ArrowSchema> {
+        let mut schema = M::convert(ArrowSchemaOf)?;
+        if let Some((leaf, overflow_to_null)) = M::leaf_cast() {
+            check_castable(&M::convert(Schema)?)?;
+            for (_, field) in schema.iter_mut() {
+                field.dtype = leaf.to_arrow(CompatLevel::newest());
+                field.is_nullable |= overflow_to_null;
+            }
+        }
+        Ok(schema)
+    }
+
+    fn column_metadata() -> PolarsResult<Vec<(String, ColumnMetadata)>> {
+        M::convert(Metadata)
+    }
+}
+
+impl<M: WithConversion> WithColumnar for WithColumns<M> {
+    type Source = M::Source;
+
+    fn columnar_from_refs(items: &[&M::Source]) -> PolarsResult<DataFrame> {
+        cast_frame::<M>(M::convert(Convert { items })?)
+    }
+}
+
+/// The function's result type is not known at the type level, so a `with`
+/// field's handle names the field itself, whatever columns it produces.
+impl<M> ColumnHandles for WithColumns<M> {
+    type Columns = ColumnRef;
+
+    fn columns_at(prefix: String) -> Self::Columns {
+        column_ref(&prefix, "")
+    }
+}
@@ -17,8 +17,52 @@
 //!   value as an `i128` mantissa rescaled to a target scale. The reference
 //!   `rust_decimal::Decimal` impl is gated behind the `rust_decimal`
 //!   feature (enabled by default).
+//! - [`dataframe::DfLeaf`] — the contract for a user-defined scalar type
+//!   written as one column by `#[df_derive(leaf)]` fields: its dtype and
+//!   how each value is encoded.
+//! - [`dataframe::arrow`] — nullability-aware Arrow schema helpers and, behind
+//!   the `parquet` / `ipc` features, writers that keep non-`Option` columns
+//!   `REQUIRED` in the written file.
+//! - [`dataframe::coerce`] — normalizes an arbitrary `DataFrame` into a
+//!   derived schema, reporting every reorder, cast, null fill, and dropped
+//!   column.
+//! - [`dataframe::columns`] — the [`dataframe::columns::ColumnHandles`] trait
+//!   behind the generated `T::cols()` column handles.
+//! - [`dataframe::metadata`] — the [`dataframe::ColumnMetadata`] map that
+//!   derived impls fill from field doc comments and metadata attributes.
+//! - [`dataframe::schema_export`] — renders a derived schema as SQL
+//!   `CREATE TABLE` DDL (Postgres, `DuckDB`, `ClickHouse`), JSON Schema, or
+//!   Avro.
+//! - [`dataframe::schema_diff`] — classifies the differences between a stored
+//!   schema and a derived one as compatible or breaking.
+//! - `dataframe::serde` — behind the `serde` feature, converts any
+//!   `T: Serialize` rows into a `DataFrame` with the derive's column layout,
+//!   and deserializes such frames back into any `T: Deserialize`.
+//! - [`dataframe::typed`] — [`dataframe::TypedDataFrame`], a `DataFrame`
+//!   that keeps its row type and exposes generated typed column accessors.
+//! - [`dataframe::validate`] — checks a frame's or file footer's schema
+//!   against a derived one and lists every mismatch, including list depth and
+//!   decimal precision/scale differences.
+//! - [`dataframe::views`] — the [`dataframe::views::RowView`] trait behind
+//!   `#[df_derive(views)]` borrowed row views.
 //! - `impl ToDataFrame for ()` and `impl Columnar for ()` — the zero-column
 //!   payload behavior used by generic `Wrapper<()>` shapes.
+//! - `ToDataFrame` / `Columnar` impls for the primitive leaf types (`f64`,
+//!   `String`, chrono types behind the `chrono` feature, `Decimal` behind
+//!   `rust_decimal`, …), each a single unnamed column, so `Wrapper<f64>`
+//!   shapes produce one column named after the generic field.
+//! - `ToDataFrame` / `Columnar` impls for `Box<T>`, `Arc<T>`, `Rc<T>`,
+//!   `Cow<'_, T>`, `&T`, and `Option<T>` (all columns nullable, `None` as a
+//!   row of nulls), which also make `[Arc<T>]`, `[&T]`, and the like
+//!   `ToDataFrameVec`.
+//! - `ToDataFrame` / `Columnar` impls for `HashMap<K, V>` and
+//!   `BTreeMap<K, V>`, each a single unnamed `List(Struct{key, value})`
+//!   column, used for map payloads and by derived map fields.
+//! - The adapter behind `#[df_derive(with = "path")]` fields, which writes
+//!   the columns of whatever the field's function returns.
+//! - [`dataframe::tuples`] — impls for tuples of row types, which place the
+//!   elements' columns side by side under `field_N.` or caller-chosen
+//!   prefixes.
 //!
 //! # When to use this crate
 //!
@@ -62,7 +106,33 @@
 #![allow(clippy::multiple_crate_versions)]
 
 pub mod dataframe {
-    use polars::prelude::{AnyValue, DataFrame, DataType, PolarsResult, Series};
+    use polars::prelude::{
+        AnyValue, ArrowSchema, DataFrame, DataType, PolarsResult, Schema, Series,
+    };
+
+    pub mod arrow;
+    pub mod coerce;
+    pub mod columns;
+    mod containers;
+    pub mod leaf;
+    mod leaf_payloads;
+    mod maps;
+    pub mod metadata;
+    pub mod schema_diff;
+    pub mod schema_export;
+    #[cfg(feature = "serde")]
+    pub mod serde;
+    pub mod tuples;
+    pub mod typed;
+    pub mod validate;
+    pub mod views;
+    mod with;
+
+    pub use coerce::CoercePolicy;
+    pub use leaf::DfLeaf;
+    pub use metadata::ColumnMetadata;
+    pub use typed::TypedDataFrame;
+    pub use validate::SchemaMismatch;
 
     #[cfg(feature = "rust_decimal")]
     const DECIMAL128_MAX_SCALE: u32 = 38;
@@ -73,6 +143,19 @@ pub mod dataframe {
     pub mod __private {
         pub use polars;
         pub use polars_arrow;
+
+        pub use super::arrow::{
+            array_arrow_field, arrow_schema_from_fields, list_arrow_field, required_arrow_field,
+        };
+        pub use super::columns::{NestedColumnHandles, column_ref, nested_column_prefix};
+        pub use super::leaf::{LeafColumnar, LeafColumns};
+        pub use super::maps::{
+            IterationOrder, KeyOrder, MapColumnar, MapColumns, SplitEntries, StructEntries,
+        };
+        pub use super::metadata::{column_metadata_entry, nested_column_metadata};
+        pub use super::typed::{FrameAccessors, time_column, typed_column};
+        pub use super::views::{ViewReader, list_view, view_column};
+        pub use super::with::{WithColumnar, WithColumns, WithConversion, WithVisitor};
     }
 
     pub trait ToDataFrame {
@@ -85,6 +168,63 @@ pub mod dataframe {
         /// # Errors
         /// Returns an error if schema generation fails.
         fn schema() -> PolarsResult<Vec<(String, DataType)>>;
+
+        /// Arrow schema for the generated columns, including which columns
+        /// and list elements may hold nulls.
+        ///
+        /// The default marks every field and list element nullable, since
+        /// `schema()` alone cannot tell. Derived impls override it with the
+        /// nullability implied by each field's `Option` wrappers.
+        ///
+        /// # Errors
+        /// Returns an error if schema generation fails.
+        fn arrow_schema() -> PolarsResult<ArrowSchema> {
+            Ok(arrow::nullable_arrow_schema(Self::schema()?))
+        }
+
+        /// Metadata for the generated columns, keyed by column name in
+        /// `schema()` order. Columns without metadata are omitted.
+        ///
+        /// The default reports none. Derived impls collect each field's doc
+        /// comment (as `description`) and its `description`, `unit`, and
+        /// `metadata(...)` attributes; nested columns inherit the outer
+        /// field's entries under the inner type's own.
+        ///
+        /// # Errors
+        /// Returns an error if schema generation fails.
+        fn column_metadata() -> PolarsResult<Vec<(String, ColumnMetadata)>> {
+            Ok(Vec::new())
+        }
+
+        /// Normalize `frame` into this type's schema: reorder columns, cast
+        /// them safely to the derived dtypes, fill missing nullable columns
+        /// with nulls, and drop or reject extra columns per `policy`. See
+        /// [`coerce`] for which casts count as safe.
+        ///
+        /// # Errors
+        /// Returns a `SchemaMismatch` error listing every column that cannot
+        /// be coerced, or an error if schema generation or a cast fails.
+        fn coerce_dataframe(
+            frame: &DataFrame,
+            policy: CoercePolicy,
+        ) -> PolarsResult<coerce::Coerced>
+        where
+            Self: Sized,
+        {
+            coerce::coerce_dataframe::<Self>(frame, policy)
+        }
+
+        /// Check `schema` (of a frame, or read from a file footer with the
+        /// helpers in [`validate`]) against `schema()` without reading data.
+        ///
+        /// # Errors
+        /// Returns every missing, mistyped, and unexpected column.
+        fn validate_schema(schema: &Schema) -> Result<(), SchemaMismatch>
+        where
+            Self: Sized,
+        {
+            validate::validate_schema::<Self>(schema)
+        }
     }
 
     /// Columnar batch trait implemented by the derive macro.
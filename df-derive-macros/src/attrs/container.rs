@@ -9,6 +9,13 @@ pub struct ContainerAttrs {
     pub to_dataframe: Option<RuntimeOverridePath>,
     pub columnar: Option<RuntimeOverridePath>,
     pub decimal128_encode: Option<RuntimeOverridePath>,
+    /// `schema_lock = "path"`, relative to the deriving crate's manifest.
+    pub schema_lock: Option<Spanned<String>>,
+    /// Span of a `views` flag requesting borrowed row views.
+    pub views: Option<Span>,
+    /// Span of a `transparent` flag: the struct's single field converts as
+    /// if it appeared in the struct's place.
+    pub transparent: Option<Span>,
 }
 
 pub type RuntimeOverridePath = Spanned<syn::Path>;
@@ -68,6 +75,52 @@ fn set_runtime_override(
     Ok(())
 }
 
+fn set_schema_lock(
+    slot: &mut Option<Spanned<String>>,
+    meta: &syn::meta::ParseNestedMeta<'_>,
+) -> syn::Result<()> {
+    let span = meta.path.span();
+    let lit: syn::LitStr = meta.value()?.parse()?;
+    if let Some(existing) = slot {
+        let mut error = syn::Error::new(
+            span,
+            "container attribute declares duplicate `schema_lock`; remove one",
+        );
+        error.combine(syn::Error::new(
+            existing.span,
+            "first `schema_lock` declared here",
+        ));
+        return Err(error);
+    }
+    if lit.value().is_empty() {
+        return Err(syn::Error::new(
+            lit.span(),
+            "`schema_lock` path cannot be empty",
+        ));
+    }
+    *slot = Some(Spanned {
+        value: lit.value(),
+        span: lit.span(),
+    });
+    Ok(())
+}
+
+fn set_flag(slot: &mut Option<Span>, key: &'static str, span: Span) -> syn::Result<()> {
+    if let Some(existing) = slot {
+        let mut error = syn::Error::new(
+            span,
+            format!("container attribute declares duplicate `{key}`; remove one"),
+        );
+        error.combine(syn::Error::new(
+            *existing,
+            format!("first `{key}` declared here"),
+        ));
+        return Err(error);
+    }
+    *slot = Some(span);
+    Ok(())
+}
+
 fn reject_columnar_without_trait(columnar_span: Span) -> syn::Error {
     syn::Error::new(
         columnar_span,
@@ -241,6 +294,9 @@ pub fn parse_container_attrs(input: &DeriveInput) -> syn::Result<ContainerAttrs>
     let mut to_dataframe: Option<RuntimeOverridePath> = None;
     let mut columnar: Option<RuntimeOverridePath> = None;
     let mut decimal128_encode: Option<RuntimeOverridePath> = None;
+    let mut schema_lock: Option<Spanned<String>> = None;
+    let mut views: Option<Span> = None;
+    let mut transparent: Option<Span> = None;
 
     for attr in &input.attrs {
         if attr.path().is_ident("df_derive") {
@@ -260,6 +316,12 @@ pub fn parse_container_attrs(input: &DeriveInput) -> syn::Result<ContainerAttrs>
                         path,
                         key_span,
                     )
+                } else if meta.path.is_ident("schema_lock") {
+                    set_schema_lock(&mut schema_lock, &meta)
+                } else if meta.path.is_ident("views") {
+                    set_flag(&mut views, "views", key_span)
+                } else if meta.path.is_ident("transparent") {
+                    set_flag(&mut transparent, "transparent", key_span)
                 } else {
                     Err(meta.error("unsupported key in #[df_derive(...)] attribute"))
                 }
@@ -278,6 +340,9 @@ pub fn parse_container_attrs(input: &DeriveInput) -> syn::Result<ContainerAttrs>
         to_dataframe,
         columnar,
         decimal128_encode,
+        schema_lock,
+        views,
+        transparent,
     })
 }
 
@@ -1,6 +1,8 @@
 use proc_macro2::Span;
 use syn::spanned::Spanned as SynSpanned;
 
+use super::field::LeafOverride;
+
 fn duplicate_decimal_key_error(
     key: &'static str,
     existing: (u8, Span),
@@ -27,9 +29,10 @@ fn duplicate_decimal_key_error(
 
 pub(super) fn parse_decimal_attr(
     meta: &syn::meta::ParseNestedMeta<'_>,
-) -> Result<(u8, u8), syn::Error> {
+) -> Result<LeafOverride, syn::Error> {
     let mut precision: Option<(u8, Span)> = None;
     let mut scale: Option<(u8, Span)> = None;
+    let mut mantissa = false;
     meta.parse_nested_meta(|sub| {
         if sub.path.is_ident("precision") {
             let key_span = sub.path.span();
@@ -68,9 +71,18 @@ pub(super) fn parse_decimal_attr(
             }
             scale = Some((value, key_span));
             Ok(())
+        } else if sub.path.is_ident("mantissa") {
+            if mantissa {
+                return Err(
+                    sub.error("`decimal(...)` declares duplicate `mantissa` key; remove one")
+                );
+            }
+            mantissa = true;
+            Ok(())
         } else {
             Err(sub.error(
-                "unknown key inside `decimal(...)`; expected `precision = N` or `scale = N`",
+                "unknown key inside `decimal(...)`; expected `precision = N`, `scale = N`, \
+                 or `mantissa`",
             ))
         }
     })?;
@@ -83,5 +95,9 @@ pub(super) fn parse_decimal_attr(
     if s > p {
         return Err(meta.error(format!("decimal scale ({s}) cannot exceed precision ({p})")));
     }
-    Ok((p, s))
+    Ok(LeafOverride::Decimal {
+        precision: p,
+        scale: s,
+        mantissa,
+    })
 }
@@ -0,0 +1,175 @@
+use syn::spanned::Spanned as SynSpanned;
+
+use crate::ir::{CastDtype, DateTimeUnit, LeafCast, NumericKind};
+
+use super::Spanned;
+
+/// `dtype = "..."` and `on_overflow = "..."` as parsed, before they are
+/// checked against each other.
+#[derive(Default)]
+pub(super) struct CastKeys {
+    dtype: Option<Spanned<CastDtype>>,
+    on_overflow: Option<Spanned<bool>>,
+}
+
+impl CastKeys {
+    pub(super) fn parse_dtype(
+        &mut self,
+        meta: &syn::meta::ParseNestedMeta<'_>,
+        field_display_name: &str,
+    ) -> Result<(), syn::Error> {
+        let span = meta.path.span();
+        reject_duplicate(self.dtype.is_some(), meta, "dtype", field_display_name)?;
+        let lit: syn::LitStr = meta.value()?.parse()?;
+        let text: String = lit.value().chars().filter(|c| !c.is_whitespace()).collect();
+        let value = parse_cast_dtype(&text).ok_or_else(|| {
+            syn::Error::new_spanned(
+                &lit,
+                format!(
+                    "unsupported `dtype` value `{}`; expected an integer or float dtype such as \
+                     \"Int64\" or \"Float32\", \"Date\", \"Time\", \"Datetime(ms|us|ns)\", \
+                     \"Duration(ms|us|ns)\", or \"Decimal(precision, scale)\"",
+                    lit.value()
+                ),
+            )
+        })?;
+        self.dtype = Some(Spanned { value, span });
+        Ok(())
+    }
+
+    pub(super) fn parse_on_overflow(
+        &mut self,
+        meta: &syn::meta::ParseNestedMeta<'_>,
+        field_display_name: &str,
+    ) -> Result<(), syn::Error> {
+        let span = meta.path.span();
+        reject_duplicate(
+            self.on_overflow.is_some(),
+            meta,
+            "on_overflow",
+            field_display_name,
+        )?;
+        let lit: syn::LitStr = meta.value()?.parse()?;
+        let value = match lit.value().as_str() {
+            "error" => false,
+            "null" => true,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    &lit,
+                    format!(
+                        "invalid `on_overflow` value `{other}`; expected one of \"error\", \"null\""
+                    ),
+                ));
+            }
+        };
+        self.on_overflow = Some(Spanned { value, span });
+        Ok(())
+    }
+
+    pub(super) fn into_cast(self) -> Result<Option<Spanned<LeafCast>>, syn::Error> {
+        match (self.dtype, self.on_overflow) {
+            (None, None) => Ok(None),
+            (None, Some(on_overflow)) => Err(syn::Error::new(
+                on_overflow.span,
+                "`on_overflow` requires `dtype = \"...\"`; it picks what a cast does with \
+                 values the target dtype cannot hold",
+            )),
+            (Some(dtype), on_overflow) => Ok(Some(Spanned {
+                value: LeafCast {
+                    dtype: dtype.value,
+                    overflow_to_null: on_overflow.is_some_and(|on_overflow| on_overflow.value),
+                },
+                span: dtype.span,
+            })),
+        }
+    }
+}
+
+fn reject_duplicate(
+    seen: bool,
+    meta: &syn::meta::ParseNestedMeta<'_>,
+    key: &str,
+    field_display_name: &str,
+) -> Result<(), syn::Error> {
+    if seen {
+        return Err(meta.error(format!(
+            "field `{field_display_name}` has duplicate `{key}` attributes"
+        )));
+    }
+    Ok(())
+}
+
+/// Parse a dtype written the way Polars prints it, whitespace removed.
+fn parse_cast_dtype(text: &str) -> Option<CastDtype> {
+    let (name, args) = match text.split_once('(') {
+        Some((name, rest)) => (name, Some(rest.strip_suffix(')')?)),
+        None => (text, None),
+    };
+    let numeric = |kind| Some(CastDtype::Numeric(kind));
+    match (name, args) {
+        ("Int8", None) => numeric(NumericKind::I8),
+        ("Int16", None) => numeric(NumericKind::I16),
+        ("Int32", None) => numeric(NumericKind::I32),
+        ("Int64", None) => numeric(NumericKind::I64),
+        ("Int128", None) => numeric(NumericKind::I128),
+        ("UInt8", None) => numeric(NumericKind::U8),
+        ("UInt16", None) => numeric(NumericKind::U16),
+        ("UInt32", None) => numeric(NumericKind::U32),
+        ("UInt64", None) => numeric(NumericKind::U64),
+        ("UInt128", None) => numeric(NumericKind::U128),
+        ("Float32", None) => numeric(NumericKind::F32),
+        ("Float64", None) => numeric(NumericKind::F64),
+        ("Date", None) => Some(CastDtype::Date),
+        ("Time", None) => Some(CastDtype::Time),
+        ("Datetime", Some(unit)) => time_unit(unit).map(CastDtype::Datetime),
+        ("Duration", Some(unit)) => time_unit(unit).map(CastDtype::Duration),
+        ("Decimal", Some(args)) => {
+            let (precision, scale) = args.split_once(',')?;
+            let precision: u8 = precision.parse().ok()?;
+            let scale: u8 = scale.parse().ok()?;
+            ((1..=38).contains(&precision) && scale <= precision)
+                .then_some(CastDtype::Decimal { precision, scale })
+        }
+        _ => None,
+    }
+}
+
+fn time_unit(unit: &str) -> Option<DateTimeUnit> {
+    match unit {
+        "ms" => Some(DateTimeUnit::Milliseconds),
+        "us" => Some(DateTimeUnit::Microseconds),
+        "ns" => Some(DateTimeUnit::Nanoseconds),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_polars_dtype_names() {
+        assert_eq!(
+            parse_cast_dtype("Int64"),
+            Some(CastDtype::Numeric(NumericKind::I64))
+        );
+        assert_eq!(
+            parse_cast_dtype("Float32"),
+            Some(CastDtype::Numeric(NumericKind::F32))
+        );
+        assert_eq!(
+            parse_cast_dtype("Datetime(us)"),
+            Some(CastDtype::Datetime(DateTimeUnit::Microseconds))
+        );
+        assert_eq!(
+            parse_cast_dtype("Decimal(18,4)"),
+            Some(CastDtype::Decimal {
+                precision: 18,
+                scale: 4,
+            })
+        );
+        assert_eq!(parse_cast_dtype("Decimal(4,18)"), None);
+        assert_eq!(parse_cast_dtype("Datetime"), None);
+        assert_eq!(parse_cast_dtype("String"), None);
+    }
+}
@@ -1,16 +1,26 @@
-use crate::ir::DateTimeUnit;
+use crate::ir::{DateTimeUnit, LeafCast};
 use proc_macro2::Span;
 use syn::spanned::Spanned as SynSpanned;
 
 use super::Spanned;
 use super::decimal::parse_decimal_attr;
+use super::dtype::CastKeys;
 use super::field_conflicts::{FieldAttr, set_override};
+use super::metadata::{
+    FieldMetadata, collect_doc_comment, parse_metadata_key_attr, parse_metadata_list_attr,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LeafOverride {
     AsStr,
     AsString,
-    Decimal { precision: u8, scale: u8 },
+    /// `mantissa`: the integer value is the mantissa at `scale` rather
+    /// than a whole number.
+    Decimal {
+        precision: u8,
+        scale: u8,
+        mantissa: bool,
+    },
     TimeUnit(DateTimeUnit),
 }
 
@@ -20,11 +30,38 @@ pub enum FieldDisposition {
     Include(FieldConversion),
 }
 
+#[derive(Clone, Debug)]
+pub struct FieldAttrs {
+    pub disposition: FieldDisposition,
+    pub metadata: FieldMetadata,
+    pub map: MapOptions,
+    /// Span of `as_list`, which reads the collection the field type stops at
+    /// as one more list layer.
+    pub as_list: Option<Span>,
+    /// `dtype = "..."` and `on_overflow`, spanned at the `dtype` key.
+    pub cast: Option<Spanned<LeafCast>>,
+}
+
+/// Spans of the collection layout flags `sort_keys` and `split_map`. Lowering
+/// checks that the field is a map, or for `sort_keys` a `HashSet` list.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MapOptions {
+    pub sort_keys: Option<Span>,
+    pub split: Option<Span>,
+}
+
 #[derive(Clone, Debug)]
 pub enum FieldConversion {
     Default,
     LeafOverride(Spanned<LeafOverride>),
-    Binary { span: Span },
+    Binary {
+        span: Span,
+    },
+    /// `with = "path"`: convert each leaf value through the function at
+    /// `path` and write the result's columns.
+    With(Spanned<syn::ExprPath>),
+    /// `leaf`: write the field type as one column through its `DfLeaf` impl.
+    DfLeaf,
 }
 
 fn parse_time_unit_attr(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<DateTimeUnit, syn::Error> {
@@ -40,49 +77,57 @@ fn parse_time_unit_attr(meta: &syn::meta::ParseNestedMeta<'_>) -> Result<DateTim
     }
 }
 
-pub fn parse_field_disposition(
+fn set_flag(
+    meta: &syn::meta::ParseNestedMeta<'_>,
+    slot: &mut Option<Span>,
+    key: &str,
+    field_display_name: &str,
+) -> Result<(), syn::Error> {
+    if slot.is_some() {
+        return Err(meta.error(format!(
+            "field `{field_display_name}` has duplicate `{key}` attributes"
+        )));
+    }
+    *slot = Some(meta.path.span());
+    Ok(())
+}
+
+/// Override selected by a bare key such as `as_str` or `leaf`.
+fn flag_override(path: &syn::Path) -> Option<FieldAttr> {
+    let ident = path.get_ident()?;
+    Some(match ident.to_string().as_str() {
+        "skip" => FieldAttr::Skip,
+        "as_string" => FieldAttr::Leaf(LeafOverride::AsString),
+        "as_str" => FieldAttr::Leaf(LeafOverride::AsStr),
+        "as_binary" => FieldAttr::Binary,
+        "leaf" => FieldAttr::DfLeaf,
+        _ => return None,
+    })
+}
+
+pub fn parse_field_attrs(
     field: &syn::Field,
     field_display_name: &str,
-) -> Result<FieldDisposition, syn::Error> {
+) -> Result<FieldAttrs, syn::Error> {
     let mut override_: Option<(FieldAttr, Span)> = None;
+    let mut metadata = FieldMetadata::default();
+    let mut map = MapOptions::default();
+    let mut as_list: Option<Span> = None;
+    let mut with: Option<syn::ExprPath> = None;
+    let mut cast = CastKeys::default();
+    collect_doc_comment(field, &mut metadata);
     for attr in &field.attrs {
         if attr.path().is_ident("df_derive") {
             attr.parse_nested_meta(|meta| {
                 let incoming_span = meta.path.span();
-                if meta.path.is_ident("skip") {
-                    set_override(
-                        field_display_name,
-                        &mut override_,
-                        FieldAttr::Skip,
-                        incoming_span,
-                    )
-                } else if meta.path.is_ident("as_string") {
-                    set_override(
-                        field_display_name,
-                        &mut override_,
-                        FieldAttr::Leaf(LeafOverride::AsString),
-                        incoming_span,
-                    )
-                } else if meta.path.is_ident("as_str") {
-                    set_override(
-                        field_display_name,
-                        &mut override_,
-                        FieldAttr::Leaf(LeafOverride::AsStr),
-                        incoming_span,
-                    )
-                } else if meta.path.is_ident("as_binary") {
-                    set_override(
-                        field_display_name,
-                        &mut override_,
-                        FieldAttr::Binary,
-                        incoming_span,
-                    )
+                if let Some(flag) = flag_override(&meta.path) {
+                    set_override(field_display_name, &mut override_, flag, incoming_span)
                 } else if meta.path.is_ident("decimal") {
-                    let (precision, scale) = parse_decimal_attr(&meta)?;
+                    let decimal = parse_decimal_attr(&meta)?;
                     set_override(
                         field_display_name,
                         &mut override_,
-                        FieldAttr::Leaf(LeafOverride::Decimal { precision, scale }),
+                        FieldAttr::Leaf(decimal),
                         incoming_span,
                     )
                 } else if meta.path.is_ident("time_unit") {
@@ -93,18 +138,51 @@ pub fn parse_field_disposition(
                         FieldAttr::Leaf(LeafOverride::TimeUnit(unit)),
                         incoming_span,
                     )
+                } else if meta.path.is_ident("with") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    set_override(
+                        field_display_name,
+                        &mut override_,
+                        FieldAttr::With,
+                        incoming_span,
+                    )?;
+                    with = Some(lit.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("dtype") {
+                    cast.parse_dtype(&meta, field_display_name)
+                } else if meta.path.is_ident("on_overflow") {
+                    cast.parse_on_overflow(&meta, field_display_name)
+                } else if meta.path.is_ident("sort_keys") {
+                    set_flag(&meta, &mut map.sort_keys, "sort_keys", field_display_name)
+                } else if meta.path.is_ident("split_map") {
+                    set_flag(&meta, &mut map.split, "split_map", field_display_name)
+                } else if meta.path.is_ident("as_list") {
+                    set_flag(&meta, &mut as_list, "as_list", field_display_name)
+                } else if meta.path.is_ident("description") {
+                    parse_metadata_key_attr(&meta, "description", field_display_name, &mut metadata)
+                } else if meta.path.is_ident("unit") {
+                    parse_metadata_key_attr(&meta, "unit", field_display_name, &mut metadata)
+                } else if meta.path.is_ident("metadata") {
+                    parse_metadata_list_attr(&meta, field_display_name, &mut metadata)
                 } else {
                     Err(meta.error(
-                        "unknown key in #[df_derive(...)] field attribute; expected `skip`, `as_str`, `as_string`, `as_binary`, `decimal(precision = N, scale = N)`, or `time_unit = \"ms\"|\"us\"|\"ns\"`",
+                        "unknown key in #[df_derive(...)] field attribute; expected `skip`, `as_str`, `as_string`, `as_binary`, `decimal(precision = N, scale = N)`, `time_unit = \"ms\"|\"us\"|\"ns\"`, `with = \"path\"`, `leaf`, `dtype = \"...\"`, `on_overflow = \"error\"|\"null\"`, `sort_keys`, `split_map`, `as_list`, `description = \"...\"`, `unit = \"...\"`, or `metadata(key = \"value\", ...)`",
                     ))
                 }
             })?;
         }
     }
-    Ok(override_.map_or(
+    let disposition = override_.map_or(
         FieldDisposition::Include(FieldConversion::Default),
-        |(value, span)| value.into_disposition(span),
-    ))
+        |(value, span)| value.into_disposition(span, with),
+    );
+    Ok(FieldAttrs {
+        disposition,
+        metadata,
+        map,
+        as_list,
+        cast: cast.into_cast()?,
+    })
 }
 
 #[cfg(test)]
@@ -112,7 +190,14 @@ mod tests {
     use super::*;
 
     fn parse_disposition(field: &syn::Field) -> syn::Result<FieldDisposition> {
-        parse_field_disposition(field, "value")
+        parse_field_attrs(field, "value").map(|attrs| attrs.disposition)
+    }
+
+    fn metadata_entries(field: &syn::Field) -> Vec<(String, String)> {
+        parse_field_attrs(field, "value")
+            .expect("field attributes should parse")
+            .metadata
+            .into_entries()
     }
 
     fn leaf_override_value(field: &syn::Field) -> LeafOverride {
@@ -147,6 +232,20 @@ mod tests {
             LeafOverride::Decimal {
                 precision: 10,
                 scale: 2,
+                mantissa: false,
+            }
+        ));
+
+        let mantissa = leaf_override_value(&syn::parse_quote! {
+            #[df_derive(decimal(precision = 18, scale = 4, mantissa))]
+            value: i64
+        });
+        assert!(matches!(
+            mantissa,
+            LeafOverride::Decimal {
+                precision: 18,
+                scale: 4,
+                mantissa: true,
             }
         ));
     }
@@ -187,4 +286,150 @@ mod tests {
         assert!(rendered.contains("has both `as_str` and `as_string`"));
         assert!(rendered.contains("first `as_str` override declared here"));
     }
+
+    #[test]
+    fn collects_doc_comments_and_metadata_attributes() {
+        let entries = metadata_entries(&syn::parse_quote! {
+            /// Last traded price.
+            ///
+            /// Quoted in the instrument currency.
+            #[df_derive(unit = "USD", metadata(source = "feed"))]
+            value: f64
+        });
+        assert_eq!(
+            entries,
+            [
+                (
+                    "description".to_owned(),
+                    "Last traded price.\n\nQuoted in the instrument currency.".to_owned()
+                ),
+                ("unit".to_owned(), "USD".to_owned()),
+                ("source".to_owned(), "feed".to_owned()),
+            ]
+        );
+
+        let explicit = metadata_entries(&syn::parse_quote! {
+            /// Rust-facing docs.
+            #[df_derive(description = "Catalog description")]
+            value: f64
+        });
+        assert_eq!(
+            explicit,
+            [("description".to_owned(), "Catalog description".to_owned())]
+        );
+    }
+
+    #[test]
+    fn parses_as_list_alongside_leaf_overrides() {
+        let attrs = parse_field_attrs(
+            &syn::parse_quote! {
+                #[df_derive(as_list, as_string)]
+                value: SmallVec<[Side; 4]>
+            },
+            "value",
+        )
+        .expect("field attributes should parse");
+        assert!(attrs.as_list.is_some());
+        assert!(matches!(
+            attrs.disposition,
+            FieldDisposition::Include(FieldConversion::LeafOverride(_))
+        ));
+
+        let duplicate = parse_disposition(&syn::parse_quote! {
+            #[df_derive(as_list, as_list)]
+            value: SmallVec<[i32; 4]>
+        });
+        assert!(duplicate.is_err());
+    }
+
+    #[test]
+    fn parses_with_paths_and_rejects_other_overrides() {
+        let disposition = parse_disposition(&syn::parse_quote! {
+            #[df_derive(with = "Money::cents")]
+            value: Money
+        })
+        .expect("field disposition should parse");
+        let FieldDisposition::Include(FieldConversion::With(with)) = disposition else {
+            panic!("field `with` conversion should be present");
+        };
+        assert_eq!(with.value, syn::parse_quote!(Money::cents));
+
+        let error = parse_disposition(&syn::parse_quote! {
+            #[df_derive(as_str, with = "to_str")]
+            value: Code
+        })
+        .expect_err("`with` should conflict with `as_str`");
+        assert!(
+            error
+                .to_string()
+                .contains("combines `with = \"...\"` with another override")
+        );
+
+        let not_a_path = parse_disposition(&syn::parse_quote! {
+            #[df_derive(with = "|value| value.0")]
+            value: Code
+        });
+        assert!(not_a_path.is_err());
+    }
+
+    #[test]
+    fn parses_leaf_flag_and_rejects_other_overrides() {
+        let disposition = parse_disposition(&syn::parse_quote! {
+            #[df_derive(leaf)]
+            value: Ticks
+        })
+        .expect("field disposition should parse");
+        assert!(matches!(
+            disposition,
+            FieldDisposition::Include(FieldConversion::DfLeaf)
+        ));
+
+        let error = parse_disposition(&syn::parse_quote! {
+            #[df_derive(leaf, decimal(precision = 10, scale = 2))]
+            value: Ticks
+        })
+        .expect_err("`leaf` should conflict with `decimal`");
+        assert!(
+            error
+                .to_string()
+                .contains("combines `leaf` with another override")
+        );
+    }
+
+    #[test]
+    fn parses_dtype_casts_alongside_other_keys() {
+        let attrs = parse_field_attrs(
+            &syn::parse_quote! {
+                #[df_derive(dtype = "Datetime(us)", on_overflow = "null", time_unit = "ns")]
+                value: chrono::NaiveDateTime
+            },
+            "value",
+        )
+        .expect("field attributes should parse");
+        let cast = attrs.cast.expect("field cast should be present").value;
+        assert_eq!(
+            cast.dtype,
+            crate::ir::CastDtype::Datetime(DateTimeUnit::Microseconds)
+        );
+        assert!(cast.overflow_to_null);
+        assert!(matches!(
+            attrs.disposition,
+            FieldDisposition::Include(FieldConversion::LeafOverride(_))
+        ));
+
+        let without_dtype = parse_disposition(&syn::parse_quote! {
+            #[df_derive(on_overflow = "null")]
+            value: u64
+        });
+        assert!(without_dtype.is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_metadata_keys() {
+        let result = parse_disposition(&syn::parse_quote! {
+            #[df_derive(unit = "USD", metadata(unit = "EUR"))]
+            value: f64
+        });
+        assert!(result.is_err());
+    }
 }
@@ -8,6 +8,8 @@ use super::field::{FieldConversion, FieldDisposition, LeafOverride};
 pub(super) enum FieldAttr {
     Skip,
     Binary,
+    With,
+    DfLeaf,
     Leaf(LeafOverride),
 }
 
@@ -15,6 +17,8 @@ pub(super) enum FieldAttr {
 enum FieldOverrideKey {
     Skip,
     AsBinary,
+    With,
+    DfLeaf,
     AsStr,
     AsString,
     Decimal,
@@ -26,6 +30,8 @@ impl FieldAttr {
         match self {
             Self::Skip => FieldOverrideKey::Skip,
             Self::Binary => FieldOverrideKey::AsBinary,
+            Self::With => FieldOverrideKey::With,
+            Self::DfLeaf => FieldOverrideKey::DfLeaf,
             Self::Leaf(LeafOverride::AsStr) => FieldOverrideKey::AsStr,
             Self::Leaf(LeafOverride::AsString) => FieldOverrideKey::AsString,
             Self::Leaf(LeafOverride::Decimal { .. }) => FieldOverrideKey::Decimal,
@@ -37,6 +43,8 @@ impl FieldAttr {
         match self.key() {
             FieldOverrideKey::Skip => "skip",
             FieldOverrideKey::AsBinary => "as_binary",
+            FieldOverrideKey::With => "with",
+            FieldOverrideKey::DfLeaf => "leaf",
             FieldOverrideKey::AsStr => "as_str",
             FieldOverrideKey::AsString => "as_string",
             FieldOverrideKey::Decimal => "decimal(...)",
@@ -44,10 +52,20 @@ impl FieldAttr {
         }
     }
 
-    pub(super) const fn into_disposition(self, span: Span) -> FieldDisposition {
+    /// `with` is the function path parsed alongside a `With` override.
+    pub(super) fn into_disposition(
+        self,
+        span: Span,
+        with: Option<syn::ExprPath>,
+    ) -> FieldDisposition {
         match self {
             Self::Skip => FieldDisposition::Skip,
             Self::Binary => FieldDisposition::Include(FieldConversion::Binary { span }),
+            Self::With => FieldDisposition::Include(FieldConversion::With(Spanned {
+                value: with.expect("`with` override carries its function path"),
+                span,
+            })),
+            Self::DfLeaf => FieldDisposition::Include(FieldConversion::DfLeaf),
             Self::Leaf(leaf_override) => {
                 FieldDisposition::Include(FieldConversion::LeafOverride(Spanned {
                     value: leaf_override,
@@ -79,10 +97,12 @@ fn duplicate_override_conflict(
             FieldAttr::Leaf(LeafOverride::Decimal {
                 precision: existing_precision,
                 scale: existing_scale,
+                ..
             }),
             FieldAttr::Leaf(LeafOverride::Decimal {
                 precision: incoming_precision,
                 scale: incoming_scale,
+                ..
             }),
         ) if existing_precision != incoming_precision || existing_scale != incoming_scale => {
             format!(
@@ -116,7 +136,7 @@ fn duplicate_override_conflict(
 }
 
 fn conflict_message(field_display_name: &str, existing: FieldAttr, incoming: FieldAttr) -> String {
-    use FieldOverrideKey::{AsBinary, AsStr, AsString, Decimal, Skip, TimeUnit};
+    use FieldOverrideKey::{AsBinary, AsStr, AsString, Decimal, DfLeaf, Skip, TimeUnit, With};
 
     match (existing.key(), incoming.key()) {
         (AsStr, AsString) | (AsString, AsStr) => {
@@ -151,6 +171,17 @@ fn conflict_message(field_display_name: &str, existing: FieldAttr, incoming: Fie
             "field `{field_display_name}` combines `skip` with another field attribute; \
              `skip` omits the field entirely, so conversion attributes have no effect; drop one"
         ),
+        (With, _) | (_, With) => format!(
+            "field `{field_display_name}` combines `with = \"...\"` with another override; \
+             the function's return type decides the column, so `as_str`, `as_string`, \
+             `as_binary`, `leaf`, `decimal(...)`, and `time_unit = \"...\"` have no \
+             effect — convert inside the function instead"
+        ),
+        (DfLeaf, _) | (_, DfLeaf) => format!(
+            "field `{field_display_name}` combines `leaf` with another override; \
+             `leaf` writes the type through its `DfLeaf` impl, which picks the dtype \
+             and encoding itself — drop one"
+        ),
         (AsBinary, _) | (_, AsBinary) => format!(
             "field `{field_display_name}` combines `as_binary` with another override; \
              `as_binary` produces a Binary column over a `Vec<u8>` shape and is \
@@ -0,0 +1,113 @@
+use proc_macro2::Span;
+use syn::spanned::Spanned as SynSpanned;
+
+/// Metadata keys attached to a field, in declaration order. `description`
+/// falls back to the field's `///` doc comment when not given explicitly.
+#[derive(Clone, Debug, Default)]
+pub struct FieldMetadata {
+    entries: Vec<(String, String, Span)>,
+    doc: Option<String>,
+}
+
+impl FieldMetadata {
+    pub(super) fn set(
+        &mut self,
+        field_display_name: &str,
+        key: String,
+        value: String,
+        span: Span,
+    ) -> syn::Result<()> {
+        if let Some((_, _, existing_span)) = self.entries.iter().find(|(k, _, _)| *k == key) {
+            let mut error = syn::Error::new(
+                span,
+                format!(
+                    "field `{field_display_name}` declares duplicate `{key}` metadata; remove one"
+                ),
+            );
+            error.combine(syn::Error::new(
+                *existing_span,
+                format!("first `{key}` metadata declared here"),
+            ));
+            return Err(error);
+        }
+        self.entries.push((key, value, span));
+        Ok(())
+    }
+
+    /// Resolved `(key, value)` pairs, with the doc comment standing in for a
+    /// missing `description`.
+    pub fn into_entries(self) -> Vec<(String, String)> {
+        let has_description = self.entries.iter().any(|(key, _, _)| key == "description");
+        let doc = self
+            .doc
+            .filter(|_| !has_description)
+            .map(|doc| ("description".to_owned(), doc));
+        doc.into_iter()
+            .chain(self.entries.into_iter().map(|(key, value, _)| (key, value)))
+            .collect()
+    }
+}
+
+fn parse_metadata_value(meta: &syn::meta::ParseNestedMeta<'_>) -> syn::Result<String> {
+    let lit: syn::LitStr = meta.value()?.parse()?;
+    Ok(lit.value())
+}
+
+/// Parse `description = "..."` or `unit = "..."` into `metadata`.
+pub(super) fn parse_metadata_key_attr(
+    meta: &syn::meta::ParseNestedMeta<'_>,
+    key: &str,
+    field_display_name: &str,
+    metadata: &mut FieldMetadata,
+) -> syn::Result<()> {
+    let span = meta.path.span();
+    let value = parse_metadata_value(meta)?;
+    metadata.set(field_display_name, key.to_owned(), value, span)
+}
+
+/// Parse `metadata(key = "value", ...)` into `metadata`.
+pub(super) fn parse_metadata_list_attr(
+    meta: &syn::meta::ParseNestedMeta<'_>,
+    field_display_name: &str,
+    metadata: &mut FieldMetadata,
+) -> syn::Result<()> {
+    meta.parse_nested_meta(|entry| {
+        let Some(key) = entry.path.get_ident() else {
+            return Err(entry.error("`metadata(...)` keys must be plain identifiers"));
+        };
+        let span = entry.path.span();
+        let value = parse_metadata_value(&entry)?;
+        metadata.set(field_display_name, key.to_string(), value, span)
+    })
+}
+
+/// Join the field's `///` lines into one description, dropping the single
+/// leading space rustdoc inserts and any blank edges.
+pub(super) fn collect_doc_comment(field: &syn::Field, metadata: &mut FieldMetadata) {
+    let lines: Vec<String> = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_owned()
+        })
+        .collect();
+    let doc = lines.join("\n").trim().to_owned();
+    if !doc.is_empty() {
+        metadata.doc = Some(doc);
+    }
+}
@@ -1,12 +1,16 @@
 mod container;
 mod decimal;
+mod dtype;
 mod field;
 mod field_conflicts;
+mod metadata;
 mod spanned;
 
 pub use container::{
     explicit_builtin_default_dataframe_mod, parse_container_attrs, rebase_last_segment,
     runtime_trait_path,
 };
-pub use field::{FieldConversion, FieldDisposition, LeafOverride, parse_field_disposition};
+pub use field::{
+    FieldAttrs, FieldConversion, FieldDisposition, LeafOverride, MapOptions, parse_field_attrs,
+};
 pub use spanned::Spanned;
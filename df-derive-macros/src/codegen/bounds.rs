@@ -1,5 +1,8 @@
-use super::{MacroConfig, type_deps};
-use crate::ir::{DecimalBackend, DisplayBase, LeafSpec, StringyBase, StructIR};
+use super::encoder::idents;
+use super::{MacroConfig, nested_target, type_deps};
+use crate::ir::{
+    AsListTypes, DecimalBackend, DisplayBase, LeafSpec, MapLeaf, StringyBase, StructIR,
+};
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -7,12 +10,16 @@ use quote::quote;
 struct GenericRequirements {
     nested_params: Vec<syn::Ident>,
     nested_types: Vec<syn::Type>,
+    maps: Vec<MapLeaf>,
+    df_leaves: Vec<syn::Type>,
     decimal_params: Vec<syn::Ident>,
     decimal_types: Vec<syn::Type>,
     as_ref_str: Vec<syn::Ident>,
     as_ref_str_types: Vec<syn::Type>,
     display_params: Vec<syn::Ident>,
     display_types: Vec<syn::Type>,
+    ord_params: Vec<syn::Ident>,
+    as_lists: Vec<AsListTypes>,
 }
 
 fn push_unique(out: &mut Vec<syn::Ident>, ident: &syn::Ident) {
@@ -31,6 +38,12 @@ fn collect_leaf_requirements(leaf: &LeafSpec, reqs: &mut GenericRequirements) {
             push_unique(&mut reqs.nested_params, ident);
         } else if let LeafSpec::Struct(ty) = leaf {
             type_deps::push_unique_type(&mut reqs.nested_types, ty);
+        } else if let LeafSpec::Map(map) = leaf {
+            if !reqs.maps.contains(map) {
+                reqs.maps.push(map.clone());
+            }
+        } else if let LeafSpec::DfLeaf(ty) = leaf {
+            type_deps::push_unique_type(&mut reqs.df_leaves, ty);
         } else if let LeafSpec::AsStr(StringyBase::Generic(ident)) = leaf {
             push_unique(&mut reqs.as_ref_str, ident);
         } else if let LeafSpec::AsStr(StringyBase::Struct(ty)) = leaf {
@@ -41,7 +54,9 @@ fn collect_leaf_requirements(leaf: &LeafSpec, reqs: &mut GenericRequirements) {
             type_deps::push_unique_type(&mut reqs.display_types, ty);
         } else if let LeafSpec::Decimal { backend, .. } = leaf {
             match backend {
-                DecimalBackend::RuntimeKnown => {}
+                DecimalBackend::RuntimeKnown
+                | DecimalBackend::Mantissa(_)
+                | DecimalBackend::Float(_) => {}
                 DecimalBackend::Generic(ident) => push_unique(&mut reqs.decimal_params, ident),
                 DecimalBackend::Struct(ty) => {
                     type_deps::push_unique_type(&mut reqs.decimal_types, ty);
@@ -56,6 +71,21 @@ fn collect_generic_requirements(ir: &StructIR) -> GenericRequirements {
 
     for column in &ir.columns {
         collect_leaf_requirements(column.leaf_spec().as_leaf_spec(), &mut reqs);
+        for types in column.as_list_types() {
+            if !reqs.as_lists.contains(types) {
+                reqs.as_lists.push(types.clone());
+            }
+        }
+        if column.has_sorted_set() {
+            column
+                .leaf_spec()
+                .as_leaf_spec()
+                .walk_terminal_leaves(&mut |leaf| {
+                    if let LeafSpec::Generic(ident) = leaf {
+                        push_unique(&mut reqs.ord_params, ident);
+                    }
+                });
+        }
     }
 
     reqs
@@ -66,7 +96,49 @@ fn collect_generic_requirements(ir: &StructIR) -> GenericRequirements {
 /// each parameter's role: direct generic dataframe payloads need
 /// `ToDataFrame` + `Columnar`, decimal backends need `Decimal128Encode`, generic `as_str`
 /// leaves need `AsRef<str>`, generic `as_string` leaves need `Display`, and
-/// concrete conversion/nested types receive exact `where` predicates.
+/// concrete conversion/nested types receive exact `where` predicates. Map
+/// fields bound their `MapColumns` adapter, which in turn requires the key
+/// and value types to be dataframe payloads, and `leaf` fields bound their
+/// `LeafColumns` adapter, which requires a `DfLeaf` impl. Generic elements of a
+/// `sort_keys` `HashSet` also need `Ord`, and `as_list` collections must be
+/// iterable by reference into their element type.
+/// Bounds on the runtime adapter types behind map and `leaf` fields:
+/// `ToDataFrame` plus the adapter's own columnar trait.
+fn push_adapter_predicates(
+    where_clause: &mut syn::WhereClause,
+    reqs: &GenericRequirements,
+    config: &MacroConfig,
+) {
+    let to_df_trait = &config.traits.to_dataframe;
+    let adapters = reqs
+        .maps
+        .iter()
+        .map(|map| {
+            let runtime = nested_target::builtin_runtime(config);
+            (
+                nested_target::map_columns_type(map, runtime),
+                quote! { #runtime::__private::MapColumnar },
+            )
+        })
+        .chain(reqs.df_leaves.iter().map(|ty| {
+            let runtime = nested_target::builtin_runtime(config);
+            (
+                nested_target::leaf_columns_type(ty, runtime),
+                quote! { #runtime::__private::LeafColumnar<Leaf = #ty> },
+            )
+        }));
+    for (adapter, columnar_trait) in adapters {
+        where_clause.predicates.push(
+            syn::parse2(quote! { #adapter: #to_df_trait })
+                .expect("adapter ToDataFrame where predicate should parse"),
+        );
+        where_clause.predicates.push(
+            syn::parse2(quote! { #adapter: #columnar_trait })
+                .expect("adapter columnar where predicate should parse"),
+        );
+    }
+}
+
 pub(in crate::codegen) fn impl_parts_with_bounds(
     ir: &StructIR,
     config: &MacroConfig,
@@ -110,12 +182,19 @@ pub(in crate::codegen) fn impl_parts_with_bounds(
         if contains_ident(&reqs.display_params, &tp.ident) {
             tp.bounds.push(display_bound.clone());
         }
+
+        if contains_ident(&reqs.ord_params, &tp.ident) {
+            tp.bounds.push(syn::parse_quote!(::core::cmp::Ord));
+        }
     }
 
     if !reqs.nested_types.is_empty()
+        || !reqs.maps.is_empty()
+        || !reqs.df_leaves.is_empty()
         || !reqs.as_ref_str_types.is_empty()
         || !reqs.display_types.is_empty()
         || !reqs.decimal_types.is_empty()
+        || !reqs.as_lists.is_empty()
     {
         let where_clause_mut = generics.make_where_clause();
 
@@ -131,6 +210,7 @@ pub(in crate::codegen) fn impl_parts_with_bounds(
                     .expect("nested Columnar where predicate should parse"),
             );
         }
+        push_adapter_predicates(where_clause_mut, &reqs, config);
         for ty in &reqs.as_ref_str_types {
             let as_str_ty = quote! { #ty };
 
@@ -155,6 +235,9 @@ pub(in crate::codegen) fn impl_parts_with_bounds(
                     .expect("decimal backend where predicate should parse"),
             );
         }
+        where_clause_mut
+            .predicates
+            .extend(reqs.as_lists.iter().map(as_list_predicate));
     }
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
@@ -164,3 +247,15 @@ pub(in crate::codegen) fn impl_parts_with_bounds(
         quote! { #where_clause },
     )
 }
+
+/// `&C: IntoIterator<Item = &E>` for every borrow of an `as_list` collection.
+/// The `Item` binding matters: without it a generic element's projection is
+/// not normalized back to `&E` inside the impl.
+fn as_list_predicate(types: &AsListTypes) -> syn::WherePredicate {
+    let AsListTypes { collection, elem } = types;
+    let lt = idents::as_list_lifetime();
+    syn::parse2(quote! {
+        for<#lt> &#lt #collection: ::core::iter::IntoIterator<Item = &#lt #elem>
+    })
+    .expect("as_list collection where predicate should parse")
+}
@@ -1,14 +1,15 @@
 //! Per-column encoder dispatch.
 
 use crate::ir::{
-    ColumnIR, FieldColumn, NestedLeaf, PrimitiveLeaf, TerminalLeafRoute, TerminalLeafSpec,
+    ColumnIR, FieldColumn, PrimitiveLeaf, TerminalLeafRoute, TerminalLeafSpec,
     TupleParentOptionColumn, TupleParentVecColumn, TupleStaticColumn, WrapperShape,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Ident;
 
-use super::encoder::{self, BaseCtx, Encoder, LeafCtx, NestedLeafCtx, idents, struct_type_tokens};
+use super::encoder::{self, BaseCtx, Encoder, LeafCtx, NestedLeafCtx, idents};
+use super::nested_target::{NestedTarget, nested_target};
 
 /// Per-column emission mode.
 ///
@@ -26,21 +27,78 @@ pub(in crate::codegen) enum ColumnEmit {
     },
 }
 
-fn nested_type_path(nested: NestedLeaf<'_>) -> TokenStream {
-    match nested {
-        NestedLeaf::Struct(ty) => struct_type_tokens(ty),
-        NestedLeaf::Generic(id) => quote! { #id },
-    }
-}
-
 /// Build the columnar emit pieces for one column. Routes every primitive
 /// shape through the encoder IR, and every nested-struct/generic column
 /// through the encoder's nested path (which covers every wrapper stack).
+/// A `dtype` cast and array layers are applied to the finished columns: the
+/// builders are followed by a cast of the leaf values to the target dtype,
+/// then by a rebuild of the list layers that are fixed-width arrays.
 pub fn build_column_emit(
     column: &ColumnIR,
     config: &super::MacroConfig,
     idx: usize,
     it_ident: &Ident,
+) -> ColumnEmit {
+    let mut emit = build_list_column_emit(column, config, idx, it_ident);
+    if let (Some(cast), TerminalLeafRoute::Primitive(leaf)) =
+        (column.leaf_cast(), column.leaf_spec().route())
+    {
+        let cast_leaf_columns = idents::cast_leaf_columns();
+        let dtype = column.leaf_logical(leaf).dtype(&config.external_paths);
+        let overflow_to_null = cast.overflow_to_null;
+        emit = emit.then_for_pushed_columns(|columns| {
+            quote! { #cast_leaf_columns(#columns, &#dtype, #overflow_to_null)?; }
+        });
+    }
+    if let Some(fixed_lens) = super::schema::fixed_lens_arg(column) {
+        let to_array_columns = idents::to_array_columns();
+        emit = emit.then_for_pushed_columns(|columns| {
+            quote! { #to_array_columns(#columns, #fixed_lens)?; }
+        });
+    }
+    emit
+}
+
+impl ColumnEmit {
+    /// Follow each builder with `post`, which receives the slice of the
+    /// columns that builder pushed.
+    fn then_for_pushed_columns(self, post: impl Fn(&TokenStream) -> TokenStream) -> Self {
+        let columns = idents::columns();
+        let wrap = |builders: Vec<TokenStream>| {
+            builders
+                .into_iter()
+                .map(|builder| {
+                    let post = post(&quote! { &mut #columns[pushed_start..] });
+                    quote! {{
+                        let pushed_start = #columns.len();
+                        #builder
+                        #post
+                    }}
+                })
+                .collect()
+        };
+        match self {
+            Self::RowWise {
+                decls,
+                push,
+                builders,
+            } => Self::RowWise {
+                decls,
+                push,
+                builders: wrap(builders),
+            },
+            Self::WholeColumn { builders } => Self::WholeColumn {
+                builders: wrap(builders),
+            },
+        }
+    }
+}
+
+fn build_list_column_emit(
+    column: &ColumnIR,
+    config: &super::MacroConfig,
+    idx: usize,
+    it_ident: &Ident,
 ) -> ColumnEmit {
     match column {
         ColumnIR::Field(column) => build_field_column_emit(column, config, idx, it_ident),
@@ -60,8 +118,7 @@ fn build_field_column_emit(
 ) -> ColumnEmit {
     match column.leaf_spec().route() {
         TerminalLeafRoute::Nested(nested) => {
-            let type_path = nested_type_path(nested);
-            build_nested_emit(column, config, idx, &type_path)
+            build_nested_emit(column, config, idx, &nested_target(nested, config))
         }
         TerminalLeafRoute::Primitive(leaf) => {
             build_primitive_emit(column, config, idx, it_ident, leaf)
@@ -73,7 +130,7 @@ fn build_nested_emit(
     column: &FieldColumn,
     config: &super::MacroConfig,
     idx: usize,
-    type_path: &TokenStream,
+    target: &NestedTarget,
 ) -> ColumnEmit {
     // The nested encoder paths run their own `for __df_derive_it in items`
     // loops to build their flat ref vec, so the access expression is
@@ -88,8 +145,9 @@ fn build_nested_emit(
             idx,
             name,
         },
-        ty: type_path,
-        columnar_trait: &config.traits.columnar,
+        ty: &target.ty,
+        item_ty: &target.item_ty,
+        columnar_trait: &target.columnar_trait,
         to_df_trait: &config.traits.to_dataframe,
         paths: &config.external_paths,
     };
@@ -153,8 +211,7 @@ fn build_parent_vec_projection_emit(
 ) -> ColumnEmit {
     let builder = match column.leaf_spec().route() {
         TerminalLeafRoute::Nested(nested) => {
-            let type_path = nested_type_path(nested);
-            encoder::build_projected_vec_nested(column, &type_path, idx, config)
+            encoder::build_projected_vec_nested(column, &nested_target(nested, config), idx, config)
         }
         TerminalLeafRoute::Primitive(leaf) => {
             encoder::build_projected_vec_primitive(column, leaf, idx, config)
@@ -214,8 +271,8 @@ fn build_projected_standard_emit(
     let pp = config.external_paths.prelude();
 
     if let TerminalLeafRoute::Nested(nested) = leaf_spec.route() {
-        let type_path = nested_type_path(nested);
-        return build_nested_emit_with_access(name, wrapper_shape, config, idx, &type_path, access);
+        let target = nested_target(nested, config);
+        return build_nested_emit_with_access(name, wrapper_shape, config, idx, &target, access);
     }
 
     let TerminalLeafRoute::Primitive(leaf) = leaf_spec.route() else {
@@ -264,13 +321,14 @@ fn build_nested_emit_with_access(
     wrapper_shape: &WrapperShape,
     config: &super::MacroConfig,
     idx: usize,
-    type_path: &TokenStream,
+    target: &NestedTarget,
     access: &TokenStream,
 ) -> ColumnEmit {
     let ctx = NestedLeafCtx {
         base: BaseCtx { access, idx, name },
-        ty: type_path,
-        columnar_trait: &config.traits.columnar,
+        ty: &target.ty,
+        item_ty: &target.item_ty,
+        columnar_trait: &target.columnar_trait,
         to_df_trait: &config.traits.to_dataframe,
         paths: &config.external_paths,
     };
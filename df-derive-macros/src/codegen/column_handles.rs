@@ -0,0 +1,274 @@
+use crate::codegen::encoder::idents;
+use crate::codegen::nested_target::{leaf_columns_type, map_columns_type, with_columns_type};
+use crate::ir::{ColumnIR, NestedLeaf, StructIR, TerminalLeafRoute};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{GenericParam, Ident};
+
+/// `column_names()` for every type, read from its schema, plus a
+/// `COLUMN_NAMES` constant when every column name is known at expansion
+/// time. Nested and generic fields get their column names from the inner
+/// type's derive, which a `const` cannot concatenate here.
+pub fn generate_column_names(ir: &StructIR, config: &super::MacroConfig) -> TokenStream {
+    let struct_name = &ir.name;
+    let (impl_generics, ty_generics, where_clause) = ir.generics.split_for_impl();
+    let pp = config.external_paths.prelude();
+    let to_df_trait = &config.traits.to_dataframe;
+    let lifetime = idents::deferred_bound_lifetime();
+    let all_primitive = ir
+        .columns
+        .iter()
+        .all(|column| matches!(column.leaf_spec().route(), TerminalLeafRoute::Primitive(_)));
+    let column_names_const = all_primitive.then(|| {
+        let names = ir.columns.iter().map(crate::ir::ColumnIR::name);
+        quote! {
+            /// Names of the columns this type converts into, in schema order.
+            pub const COLUMN_NAMES: &'static [&'static str] = &[#(#names),*];
+        }
+    });
+    quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #column_names_const
+
+            /// Names of the columns this type converts into, in schema
+            /// order, including the flattened columns of nested fields.
+            ///
+            /// # Errors
+            /// Returns an error if schema generation fails.
+            pub fn column_names() -> #pp::PolarsResult<::std::vec::Vec<::std::string::String>>
+            where
+                for<#lifetime> Self: #to_df_trait,
+            {
+                ::std::result::Result::Ok(
+                    <Self as #to_df_trait>::schema()?
+                        .into_iter()
+                        .map(|(name, _)| name)
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Accessor name for a flattened column: `spread.field_0` becomes
+/// `spread_field_0`, and keywords such as `type` become raw identifiers.
+pub(super) fn accessor_ident(column_name: &str) -> Ident {
+    let name = column_name.replace('.', "_");
+    syn::parse_str::<Ident>(&name).unwrap_or_else(|_| Ident::new_raw(&name, Span::call_site()))
+}
+
+/// Two named columns whose accessors, in `T::cols()` and on the typed frame,
+/// would share a name, such as `pair.field_0` next to a `pair_field_0`
+/// field.
+pub(super) fn accessor_collision(ir: &StructIR) -> Option<syn::Error> {
+    let mut seen: Vec<(Ident, &str)> = Vec::new();
+    for column in &ir.columns {
+        let name = column.name();
+        if name.is_empty() {
+            continue;
+        }
+        let ident = accessor_ident(name);
+        if let Some((_, earlier)) = seen.iter().find(|(existing, _)| *existing == ident) {
+            return Some(syn::Error::new(
+                ir.name.span(),
+                format!(
+                    "columns `{earlier}` and `{name}` both map to the accessor `{ident}()`; \
+                     rename one of the fields"
+                ),
+            ));
+        }
+        seen.push((ident, name));
+    }
+    None
+}
+
+/// `fn(..)` arguments that let a generated helper type carry the deriving
+/// struct's lifetime and type parameters without owning them. Const
+/// parameters may go unused.
+pub(super) fn phantom_args(generics: &syn::Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(lifetime) => {
+                let lifetime = &lifetime.lifetime;
+                Some(quote! { &#lifetime () })
+            }
+            GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                Some(quote! { &#ident })
+            }
+            GenericParam::Const(_) => None,
+        })
+        .collect()
+}
+
+/// The generated handle namespace, its `ColumnHandles` impl, and the
+/// inherent `T::cols()` entry point. Only emitted for df-derive's own
+/// runtime, which defines `ColumnHandles`.
+pub fn generate_column_handles(ir: &StructIR, config: &super::MacroConfig) -> TokenStream {
+    let Some(runtime) = &config.builtin_runtime else {
+        return TokenStream::new();
+    };
+    let struct_name = &ir.name;
+    let (impl_generics, ty_generics, where_clause) = ir.generics.split_for_impl();
+    let handles_trait = quote! { #runtime::columns::ColumnHandles };
+
+    if ir.columns.is_empty() {
+        return quote! {
+            #[automatically_derived]
+            impl #impl_generics #handles_trait for #struct_name #ty_generics #where_clause {
+                type Columns = ();
+
+                fn columns_at(_prefix: ::std::string::String) -> Self::Columns {}
+            }
+
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                /// Typed handles for this type's columns (none).
+                pub fn cols() {}
+            }
+        };
+    }
+
+    if let [column] = ir.columns.as_slice()
+        && column.name().is_empty()
+    {
+        return transparent_column_handles(ir, column, runtime);
+    }
+
+    let handles = idents::column_handles();
+    let marker = idents::column_handles_marker();
+
+    // The handle namespace carries the deriving struct's generics, for
+    // generic payload accessors, plus a marker parameter that keeps nested
+    // accessor bounds non-trivial.
+    let mut decl_generics = ir.generics.clone();
+    decl_generics.params.push(syn::parse_quote!(#marker = ()));
+    let decl_where = &decl_generics.where_clause;
+    let mut accessor_generics = ir.generics.clone();
+    accessor_generics.params.push(syn::parse_quote!(#marker));
+    let (accessor_impl_generics, accessor_ty_generics, _) = accessor_generics.split_for_impl();
+    let phantom_args = phantom_args(&ir.generics);
+
+    let accessors = ir.columns.iter().map(|column| {
+        let name = column.name();
+        let ident = accessor_ident(name);
+        match column.leaf_spec().route() {
+            TerminalLeafRoute::Primitive(_) => quote! {
+                pub fn #ident(&self) -> #runtime::columns::ColumnRef {
+                    #runtime::__private::column_ref(&self.prefix, #name)
+                }
+            },
+            TerminalLeafRoute::Nested(nested) => {
+                let ty = match nested {
+                    NestedLeaf::Struct(ty) => quote! { #ty },
+                    NestedLeaf::Generic(ident) => quote! { #ident },
+                    NestedLeaf::Map(map) => map_columns_type(map, runtime),
+                    NestedLeaf::With(with) => with_columns_type(with, runtime),
+                    NestedLeaf::DfLeaf(ty) => leaf_columns_type(ty, runtime),
+                };
+                let nested_trait = quote! { #runtime::__private::NestedColumnHandles<#marker> };
+                quote! {
+                    pub fn #ident(&self) -> <#ty as #nested_trait>::Columns
+                    where
+                        #ty: #nested_trait,
+                    {
+                        <#ty as #nested_trait>::nested_columns(
+                            #runtime::__private::nested_column_prefix(&self.prefix, #name),
+                        )
+                    }
+                }
+            }
+        }
+    });
+
+    quote! {
+        pub struct #handles #decl_generics #decl_where {
+            prefix: ::std::string::String,
+            marker: ::std::marker::PhantomData<fn(#(#phantom_args),*) -> #marker>,
+        }
+
+        impl #accessor_impl_generics #handles #accessor_ty_generics #where_clause {
+            #(#accessors)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #handles_trait for #struct_name #ty_generics #where_clause {
+            type Columns = #handles #ty_generics;
+
+            fn columns_at(prefix: ::std::string::String) -> Self::Columns {
+                #handles {
+                    prefix,
+                    marker: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Typed handles for this type's columns.
+            pub fn cols() -> <Self as #handles_trait>::Columns {
+                <Self as #handles_trait>::columns_at(::std::string::String::new())
+            }
+        }
+    }
+}
+
+/// Handles for a `transparent` struct, whose single unnamed column takes
+/// the field's name in an outer struct: a primitive column is a
+/// `ColumnRef`, and a nested one reuses the inner type's handles. The
+/// forwarding bound is higher-ranked so it is checked where the handles are
+/// used rather than failing the derive when the inner type has none.
+fn transparent_column_handles(
+    ir: &StructIR,
+    column: &ColumnIR,
+    runtime: &TokenStream,
+) -> TokenStream {
+    let struct_name = &ir.name;
+    let handles_trait = quote! { #runtime::columns::ColumnHandles };
+    let (columns_ty, columns_at, generics) = match column.leaf_spec().route() {
+        TerminalLeafRoute::Primitive(_) => (
+            quote! { #runtime::columns::ColumnRef },
+            quote! { #runtime::__private::column_ref(&prefix, "") },
+            ir.generics.clone(),
+        ),
+        TerminalLeafRoute::Nested(nested) => {
+            let ty = match nested {
+                NestedLeaf::Struct(ty) => quote! { #ty },
+                NestedLeaf::Generic(ident) => quote! { #ident },
+                NestedLeaf::Map(map) => map_columns_type(map, runtime),
+                NestedLeaf::With(with) => with_columns_type(with, runtime),
+                NestedLeaf::DfLeaf(ty) => leaf_columns_type(ty, runtime),
+            };
+            let lifetime = idents::deferred_bound_lifetime();
+            let mut generics = ir.generics.clone();
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(for<#lifetime> #ty: #handles_trait));
+            (
+                quote! { <#ty as #handles_trait>::Columns },
+                quote! { <#ty as #handles_trait>::columns_at(prefix) },
+                generics,
+            )
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #handles_trait for #struct_name #ty_generics #where_clause {
+            type Columns = #columns_ty;
+
+            fn columns_at(prefix: ::std::string::String) -> Self::Columns {
+                #columns_at
+            }
+        }
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Typed handles for the column of this type's field.
+            pub fn cols() -> <Self as #handles_trait>::Columns {
+                <Self as #handles_trait>::columns_at(::std::string::String::new())
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use proc_macro_crate::{FoundCrate, crate_name};
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::DeriveInput;
 
@@ -26,6 +26,19 @@ pub struct MacroConfig {
     /// External runtime dependency roots (`polars::prelude`,
     /// `polars_arrow`) used by generated code.
     pub external_paths: external_paths::ExternalPaths,
+    /// `dataframe` module of df-derive's own runtime when the derive targets
+    /// it. Only that runtime provides the extended `ToDataFrame` surface
+    /// (`arrow_schema`, ...); custom runtimes keep the minimum three-method
+    /// impl.
+    pub builtin_runtime: Option<TokenStream>,
+    /// Lock file the derived column list is checked against, from
+    /// `#[df_derive(schema_lock = "...")]`.
+    pub schema_lock: Option<attrs::Spanned<String>>,
+    /// Span of `#[df_derive(views)]`, which requests borrowed row views.
+    pub views: Option<Span>,
+    /// Span of `#[df_derive(transparent)]`, which converts the struct as its
+    /// single field.
+    pub transparent: Option<Span>,
 }
 
 fn resolve_dataframe_mod_for_crate(name: &str, lib_crate_name: &str) -> Option<TokenStream> {
@@ -52,6 +65,13 @@ fn is_expanding_lib_target(lib_crate_name: &str) -> bool {
     std::env::var("CARGO_CRATE_NAME").as_deref() == Ok(lib_crate_name)
 }
 
+/// The `dataframe` module of df-derive's own runtime, if the deriving crate
+/// depends on the facade or on `df-derive-core`.
+fn resolve_builtin_dataframe_mod() -> Option<TokenStream> {
+    resolve_dataframe_mod_for_crate("df-derive", "df_derive")
+        .or_else(|| resolve_dataframe_mod_for_crate("df-derive-core", "df_derive_core"))
+}
+
 pub fn resolve_default_dataframe_mod() -> TokenStream {
     // Default discovery order:
     // - `df-derive` facade (`df_derive::dataframe`, or `crate::dataframe` inside the facade)
@@ -59,8 +79,7 @@ pub fn resolve_default_dataframe_mod() -> TokenStream {
     // - `paft-utils` direct runtime (`paft_utils::dataframe`)
     // - `paft` facade (`paft::dataframe`)
     // - local fallback (`crate::core::dataframe`)
-    resolve_dataframe_mod_for_crate("df-derive", "df_derive")
-        .or_else(|| resolve_dataframe_mod_for_crate("df-derive-core", "df_derive_core"))
+    resolve_builtin_dataframe_mod()
         .or_else(|| resolve_dataframe_mod_for_crate("paft-utils", "paft_utils"))
         .or_else(|| resolve_dataframe_mod_for_crate("paft", "paft"))
         .unwrap_or_else(|| quote! { crate::core::dataframe })
@@ -89,6 +108,11 @@ pub fn build_macro_config(ast: &DeriveInput) -> syn::Result<MacroConfig> {
         (None, Some(override_)) => attrs::rebase_last_segment(&override_.value, "Decimal128Encode"),
         (None, None) => attrs::runtime_trait_path(&default_df_mod, "Decimal128Encode"),
     };
+    let builtin_runtime = match &explicit_default_dataframe_mod {
+        Some(dataframe_mod) => Some(quote! { #dataframe_mod }),
+        None if uses_default_dataframe_runtime => resolve_builtin_dataframe_mod(),
+        None => None,
+    };
     let external_paths = explicit_default_dataframe_mod.as_ref().map_or_else(
         || {
             if uses_default_dataframe_runtime {
@@ -110,5 +134,9 @@ pub fn build_macro_config(ast: &DeriveInput) -> syn::Result<MacroConfig> {
             decimal128_encode,
         },
         external_paths,
+        builtin_runtime,
+        schema_lock: attrs.schema_lock,
+        views: attrs.views,
+        transparent: attrs.transparent,
     })
 }
@@ -191,6 +191,7 @@ fn ctb_materialize(
 ) -> TokenStream {
     let CollectThenBulk {
         ty,
+        item_ty: _,
         columnar_trait,
         to_df_trait,
         name,
@@ -333,7 +334,7 @@ fn ctb_emit(
 ) -> TokenStream {
     let flat = idents::nested_flat(ctb.idx);
     let positions = idents::nested_positions(ctb.idx);
-    let ty = ctb.ty;
+    let item_ty = ctb.item_ty;
 
     let (precount, scan, offsets_decls, validity_decls, flat_capacity) = match wrapper {
         WrapperShape::Leaf(LeafShape::Bare) => {
@@ -417,7 +418,7 @@ fn ctb_emit(
 
     quote! {{
         #precount
-        let mut #flat: ::std::vec::Vec<&#ty> = ::std::vec::Vec::with_capacity(#flat_capacity);
+        let mut #flat: ::std::vec::Vec<&#item_ty> = ::std::vec::Vec::with_capacity(#flat_capacity);
         #positions_decl
         #offsets_decls
         #validity_decls
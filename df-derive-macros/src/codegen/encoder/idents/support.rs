@@ -1,3 +1,4 @@
+use proc_macro2::Span;
 use quote::format_ident;
 use syn::Ident;
 
@@ -25,6 +26,46 @@ pub(in crate::codegen) fn list_assembly() -> Ident {
     format_ident!("__DfDeriveListAssembly")
 }
 
+pub(in crate::codegen) fn sorted_set() -> Ident {
+    format_ident!("__DfDeriveSortedSet")
+}
+
+pub(in crate::codegen) fn as_list() -> Ident {
+    format_ident!("__DfDeriveAsList")
+}
+
+pub(in crate::codegen) fn as_list_lifetime() -> syn::Lifetime {
+    syn::Lifetime::new("'__df_derive_list", Span::call_site())
+}
+
+pub(in crate::codegen) fn array_dtype() -> Ident {
+    format_ident!("__df_derive_array_dtype")
+}
+
+pub(in crate::codegen) fn fixed_size_arrays() -> Ident {
+    format_ident!("__df_derive_fixed_size_arrays")
+}
+
+pub(in crate::codegen) fn to_array_columns() -> Ident {
+    format_ident!("__df_derive_to_array_columns")
+}
+
+pub(in crate::codegen) fn cast_leaf_dtype() -> Ident {
+    format_ident!("__df_derive_cast_leaf_dtype")
+}
+
+pub(in crate::codegen) fn leaf_values() -> Ident {
+    format_ident!("__df_derive_leaf_values")
+}
+
+pub(in crate::codegen) fn null_f32_overflow() -> Ident {
+    format_ident!("__df_derive_null_f32_overflow")
+}
+
+pub(in crate::codegen) fn cast_leaf_columns() -> Ident {
+    format_ident!("__df_derive_cast_leaf_columns")
+}
+
 pub(in crate::codegen) fn validate_nested_frame() -> Ident {
     format_ident!("__df_derive_validate_nested_frame")
 }
@@ -52,3 +93,33 @@ pub(in crate::codegen) fn decimal_backend_assert_helper() -> Ident {
 pub(in crate::codegen) fn collapse_option_param() -> Ident {
     format_ident!("__df_derive_o")
 }
+
+pub(in crate::codegen) fn column_handles() -> Ident {
+    format_ident!("__DfDeriveColumns")
+}
+
+pub(in crate::codegen) fn column_handles_marker() -> Ident {
+    format_ident!("__DfDeriveColumnsMarker")
+}
+
+pub(in crate::codegen) fn deferred_bound_lifetime() -> syn::Lifetime {
+    syn::Lifetime::new("'__df_derive_deferred", Span::call_site())
+}
+
+pub(in crate::codegen) fn typed_frame() -> Ident {
+    format_ident!("__DfDeriveFrame")
+}
+
+pub(in crate::codegen) fn view_reader() -> Ident {
+    format_ident!("__DfDeriveViewReader")
+}
+
+pub(in crate::codegen) fn view_lifetime_marker() -> Ident {
+    format_ident!("__df_derive_lifetime")
+}
+
+/// Marker type of a `with` field, whose `WithConversion` impl calls the
+/// field's function.
+pub(in crate::codegen) fn with_marker(field: &Ident) -> Ident {
+    format_ident!("__DfDeriveWith_{}", syn::ext::IdentExt::unraw(field))
+}
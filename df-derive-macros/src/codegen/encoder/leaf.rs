@@ -4,7 +4,7 @@
 //! reach these builders through a collapsed single-Option access.
 
 use crate::codegen::type_registry::{PrimitiveExprReceiver, ScalarTransform};
-use crate::ir::{DateTimeUnit, DurationSource, NumericKind, StringyBase};
+use crate::ir::{DateTimeUnit, DecimalSource, DurationSource, NumericKind, StringyBase};
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -385,6 +385,7 @@ pub(super) fn decimal_leaf(
     ctx: &LeafCtx<'_>,
     precision: u8,
     scale: u8,
+    source: DecimalSource,
     arm: LeafArmKind,
 ) -> LeafArm {
     let buf = idents::primitive_buf(ctx.base.idx);
@@ -393,7 +394,11 @@ pub(super) fn decimal_leaf(
     let int128 = ctx.paths.int128_chunked();
     let p = precision as usize;
     let s = scale as usize;
-    let leaf = ScalarTransform::Decimal { precision, scale };
+    let leaf = ScalarTransform::Decimal {
+        precision,
+        scale,
+        source,
+    };
     let push = mapped_push(ctx, leaf, arm);
     match arm {
         LeafArmKind::Bare => {
@@ -18,6 +18,7 @@ pub(super) struct PerElementPush {
 #[derive(Clone, Copy)]
 pub(super) struct CollectThenBulk<'a> {
     pub ty: &'a TokenStream,
+    pub item_ty: &'a TokenStream,
     pub columnar_trait: &'a syn::Path,
     pub to_df_trait: &'a syn::Path,
     pub name: &'a str,
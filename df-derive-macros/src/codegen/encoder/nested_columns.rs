@@ -370,6 +370,8 @@ pub(super) fn consume_nested_columns(
     let prefixed = idents::nested_prefixed_name();
     let inner = idents::nested_inner_series();
     let named = idents::field_named_series();
+    let prefixed_name =
+        crate::codegen::schema_nested::prefixed_column_name(parent_name, &quote! { #col_name });
     quote! {
         for (#col_name, #dtype) in
             <#ty as #to_df_trait>::schema()?
@@ -377,9 +379,7 @@ pub(super) fn consume_nested_columns(
             let #col_name: &str = #col_name.as_str();
             let #dtype: &#pp::DataType = &#dtype;
             {
-                let #prefixed = ::std::format!(
-                    "{}.{}", #parent_name, #col_name,
-                );
+                let #prefixed = #prefixed_name;
                 let #inner: #pp::Series = #series_expr;
                 let #named = #inner
                     .with_name(#prefixed.as_str().into());
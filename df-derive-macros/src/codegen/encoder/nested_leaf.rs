@@ -32,10 +32,12 @@ use crate::codegen::external_paths::ExternalPaths;
 /// Per-call-site context for nested-struct/generic encoders. Carries the
 /// type-as-path expression and the fully-qualified trait paths used in UFCS
 /// calls (`<#ty as #columnar_trait>::columnar_from_refs`,
-/// `<#ty as #to_df_trait>::schema`).
+/// `<#ty as #to_df_trait>::schema`), plus the field leaf type the collected
+/// references point to (the same type except for map adapters).
 pub struct NestedLeafCtx<'a> {
     pub base: BaseCtx<'a>,
     pub ty: &'a TokenStream,
+    pub item_ty: &'a TokenStream,
     pub columnar_trait: &'a syn::Path,
     pub to_df_trait: &'a syn::Path,
     pub paths: &'a ExternalPaths,
@@ -45,6 +47,7 @@ impl<'a> From<&NestedLeafCtx<'a>> for CollectThenBulk<'a> {
     fn from(ctx: &NestedLeafCtx<'a>) -> Self {
         Self {
             ty: ctx.ty,
+            item_ty: ctx.item_ty,
             columnar_trait: ctx.columnar_trait,
             to_df_trait: ctx.to_df_trait,
             name: ctx.base.name,
@@ -1,4 +1,5 @@
 use crate::codegen::MacroConfig;
+use crate::codegen::nested_target::NestedTarget;
 use crate::ir::{AccessChain, TupleParentVecColumn, VecLayers};
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -39,7 +40,7 @@ pub(in crate::codegen) fn build_projected_vec_primitive(
 
 pub(in crate::codegen) fn build_projected_vec_nested(
     column: &TupleParentVecColumn,
-    type_path: &TokenStream,
+    target: &NestedTarget,
     idx: usize,
     config: &MacroConfig,
 ) -> TokenStream {
@@ -51,7 +52,7 @@ pub(in crate::codegen) fn build_projected_vec_nested(
         &parent_access,
         shape,
         projection,
-        type_path,
+        target,
         idx,
         column.name(),
         config,
@@ -306,11 +307,12 @@ fn emit_projected_vec_nested(
     parent_access: &TokenStream,
     shape: &VecLayers,
     projection: ParentVecProjection<'_>,
-    type_path: &TokenStream,
+    target: &NestedTarget,
     idx: usize,
     column_name: &str,
     config: &MacroConfig,
 ) -> TokenStream {
+    let item_ty = &target.item_ty;
     let pp = config.external_paths.prelude();
     let pa_root = config.external_paths.polars_arrow_root();
     let total_leaves = idents::nested_total(idx);
@@ -383,7 +385,7 @@ fn emit_projected_vec_nested(
 
     let dispatch = materialize_nested_columns(&NestedMaterializeCtx {
         field_idx: idx,
-        ty: type_path,
+        ty: &target.ty,
         column_prefix: column_name,
         flat: &flat,
         positions: has_inner_option.then_some(&positions),
@@ -393,7 +395,7 @@ fn emit_projected_vec_nested(
             layers: &layers,
             arr_id_for_layer: idents::tuple_layer_list_arr,
         },
-        columnar_trait: &config.traits.columnar,
+        columnar_trait: &target.columnar_trait,
         to_df_trait: &config.traits.to_dataframe,
         paths: &config.external_paths,
     });
@@ -401,7 +403,7 @@ fn emit_projected_vec_nested(
     quote! {
         {
             #precount
-            let mut #flat: ::std::vec::Vec<&#type_path> =
+            let mut #flat: ::std::vec::Vec<&#item_ty> =
                 ::std::vec::Vec::with_capacity(#total_leaves);
             #positions_decl
             #offsets_decls
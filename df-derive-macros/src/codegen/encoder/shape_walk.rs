@@ -238,7 +238,8 @@ impl ShapePrecount<'_> {
     }
 
     fn build_layer(&self, cur: usize, bind: &TokenStream) -> TokenStream {
-        let layer_access = access_chain_to_ref(bind, &self.shape.layers[cur].access);
+        // Counting does not depend on order, so sorted sets are not sorted here.
+        let layer_access = access_chain_to_ref(bind, &self.shape.layers[cur].access.unsorted());
         if layer_access.has_option {
             let inner_vec_bind = format_ident!("{}{}", self.outer_some_prefix, cur);
             let inner = self.build_iter(cur, &quote! { #inner_vec_bind });
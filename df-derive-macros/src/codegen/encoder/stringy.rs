@@ -26,6 +26,7 @@ pub(in crate::codegen) fn stringy_base_ty_path(base: &StringyBase) -> TokenStrea
         StringyBase::String => quote! { ::std::string::String },
         StringyBase::BorrowedStr => quote! { &'_ str },
         StringyBase::CowStr => quote! { ::std::borrow::Cow<'_, str> },
+        StringyBase::Str => quote! { str },
         StringyBase::Struct(ty) => struct_type_tokens(ty),
         StringyBase::Generic(ident) => quote! { #ident },
     }
@@ -45,7 +46,10 @@ pub(in crate::codegen) fn stringy_value_expr(
     binding: &TokenStream,
     kind: StringyExprKind,
 ) -> TokenStream {
-    if matches!(base, StringyBase::BorrowedStr | StringyBase::CowStr) {
+    if matches!(
+        base,
+        StringyBase::BorrowedStr | StringyBase::CowStr | StringyBase::Str
+    ) {
         let v = idents::leaf_value();
         return match kind {
             StringyExprKind::Bare => {
@@ -413,8 +413,13 @@ fn vec_encoder_bool_bare(ctx: &LeafCtx<'_>, shape: &VecLayers) -> Encoder {
     // The depth-1 fast path uses `(&access).iter().copied()`, which
     // requires a plain `&bool`-yielding iterator. Any inner access chain
     // (Option or smart-pointer boundary) routes through the generalized
-    // scanner so that boundary is resolved before the leaf push.
-    if shape.depth() == 1 && !shape.any_outer_validity() && shape.inner_access.is_empty() {
+    // scanner so that boundary is resolved before the leaf push, as do
+    // layers iterated through a generated adapter.
+    if shape.depth() == 1
+        && !shape.any_outer_validity()
+        && !shape.has_adapter()
+        && shape.inner_access.is_empty()
+    {
         let pa_root = ctx.paths.polars_arrow_root();
         let pp = ctx.paths.prelude();
         let series_local = vec_encoder_series_local(ctx.base.idx);
@@ -509,6 +514,7 @@ fn mapped_numeric_plan(
     }
 }
 
+#[allow(clippy::too_many_lines)]
 fn vec_leaf_plan(leaf: PrimitiveLeaf<'_>, ctx: &LeafCtx<'_>) -> VecLeafPlan {
     let v = idents::leaf_value();
     match leaf {
@@ -561,9 +567,17 @@ fn vec_leaf_plan(leaf: PrimitiveLeaf<'_>, ctx: &LeafCtx<'_>) -> VecLeafPlan {
         PrimitiveLeaf::NaiveDate => {
             mapped_numeric_plan(ctx, ScalarTransform::NaiveDate, quote! { i32 })
         }
-        PrimitiveLeaf::Decimal { precision, scale } => mapped_numeric_plan(
+        PrimitiveLeaf::Decimal {
+            precision,
+            scale,
+            source,
+        } => mapped_numeric_plan(
             ctx,
-            ScalarTransform::Decimal { precision, scale },
+            ScalarTransform::Decimal {
+                precision,
+                scale,
+                source,
+            },
             quote! { i128 },
         ),
         PrimitiveLeaf::AsString => {
@@ -651,9 +665,11 @@ pub(super) fn build_leaf(leaf: PrimitiveLeaf<'_>, ctx: &LeafCtx<'_>, kind: LeafA
         PrimitiveLeaf::NaiveDate => leaf::naive_date_leaf(ctx, kind),
         PrimitiveLeaf::NaiveTime => leaf::naive_time_leaf(ctx, kind),
         PrimitiveLeaf::Duration { unit, source } => leaf::duration_leaf(ctx, unit, source, kind),
-        PrimitiveLeaf::Decimal { precision, scale } => {
-            leaf::decimal_leaf(ctx, precision, scale, kind)
-        }
+        PrimitiveLeaf::Decimal {
+            precision,
+            scale,
+            source,
+        } => leaf::decimal_leaf(ctx, precision, scale, source, kind),
         PrimitiveLeaf::AsString => leaf::as_string_leaf(ctx, kind),
         PrimitiveLeaf::AsStr(stringy) => leaf::as_str_leaf(ctx, stringy, kind),
     }
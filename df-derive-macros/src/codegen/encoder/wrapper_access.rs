@@ -109,6 +109,20 @@ pub(in crate::codegen) fn access_chain_to_ref(base: &TokenStream, chain: &Access
                     quote! { (#expr).as_ref() }
                 };
             }
+            AccessStep::SortedSet | AccessStep::AsList => {
+                expr = apply_pending_smart_ptrs(expr, has_option, pending_smart_ptrs);
+                pending_smart_ptrs = 0;
+                let adapter = if *step == AccessStep::SortedSet {
+                    idents::sorted_set()
+                } else {
+                    idents::as_list()
+                };
+                expr = if has_option {
+                    quote! { (#expr).map(#adapter::new) }
+                } else {
+                    quote! { (&#adapter::new(#expr)) }
+                };
+            }
         }
     }
 
@@ -1,34 +1,61 @@
 mod asserts;
 mod bounds;
 mod column_emit;
+mod column_handles;
 mod columnar_impl;
 mod config;
 mod encoder;
 pub mod external_paths;
+mod nested_target;
+mod row_views;
 mod schema;
+mod schema_lock;
 mod schema_nested;
 mod source_access;
 mod support;
 mod trait_impl;
 mod type_deps;
 mod type_registry;
+mod typed_frame;
 
-use crate::ir::StructIR;
+use crate::ir::{LeafSpec, StructIR};
 use proc_macro2::TokenStream;
 use quote::quote;
 
 pub use config::{MacroConfig, build_macro_config};
 
 pub fn generate_code(ir: &StructIR, config: &MacroConfig) -> TokenStream {
+    if config.builtin_runtime.is_none()
+        && let Some(error) = first_builtin_runtime_leaf(ir)
+    {
+        return error.to_compile_error();
+    }
+    if config.builtin_runtime.is_some()
+        && let Some(error) = column_handles::accessor_collision(ir)
+    {
+        return error.to_compile_error();
+    }
+    let with_markers = nested_target::generate_with_markers(ir, config);
     let support = support::generate_support(ir, config);
     let trait_impl = trait_impl::generate_trait_impl(ir, config);
     let columnar_impl = columnar_impl::generate_columnar_impl(ir, config);
+    let column_names = column_handles::generate_column_names(ir, config);
+    let column_handles = column_handles::generate_column_handles(ir, config);
+    let typed_frame = typed_frame::generate_typed_frame(ir, config);
+    let row_views::RowViews {
+        view_struct,
+        impls: row_view_impls,
+    } = row_views::generate_row_views(ir, config);
     let eager_asserts = asserts::generate_eager_asserts(
         ir,
         &config.traits.to_dataframe,
         &config.traits.columnar,
         &config.traits.decimal128_encode,
     );
+    let schema_lock = config
+        .schema_lock
+        .as_ref()
+        .map(|lock| schema_lock::check_schema_lock(ir, lock));
 
     // Keep helper names private while still emitting inherent impls for the
     // target type. The list assembly wrapper is emitted only for derives that
@@ -38,13 +65,54 @@ pub fn generate_code(ir: &StructIR, config: &MacroConfig) -> TokenStream {
     quote! {
         const _: () = {
             #eager_asserts
+            #schema_lock
 
+            #with_markers
             #support
 
             #trait_impl
             #columnar_impl
+            #column_names
+            #column_handles
+            #typed_frame
+            #row_view_impls
         };
+
+        #view_struct
+    }
+}
+
+/// The first map, `with`, or `leaf` leaf, whose adapters only the built-in
+/// runtime provides.
+fn first_builtin_runtime_leaf(ir: &StructIR) -> Option<syn::Error> {
+    let mut found = None;
+    for column in &ir.columns {
+        column
+            .leaf_spec()
+            .as_leaf_spec()
+            .walk_terminal_leaves(&mut |leaf| match leaf {
+                LeafSpec::Map(map) if found.is_none() => {
+                    found = Some(syn::Error::new_spanned(
+                        &map.ty,
+                        "`HashMap` / `BTreeMap` fields require the built-in df-derive runtime",
+                    ));
+                }
+                LeafSpec::DfLeaf(ty) if found.is_none() => {
+                    found = Some(syn::Error::new_spanned(
+                        ty,
+                        "`leaf` fields require the built-in df-derive runtime",
+                    ));
+                }
+                LeafSpec::With(with) if found.is_none() => {
+                    found = Some(syn::Error::new_spanned(
+                        &with.func,
+                        "`with = \"...\"` fields require the built-in df-derive runtime",
+                    ));
+                }
+                _ => {}
+            });
     }
+    found
 }
 
 #[cfg(test)]
@@ -65,6 +133,10 @@ mod tests {
                 decimal128_encode: syn::parse_quote!(crate::dataframe::Decimal128Encode),
             },
             external_paths: external_paths::default_runtime_paths(&dataframe_mod),
+            builtin_runtime: Some(dataframe_mod),
+            schema_lock: None,
+            views: None,
+            transparent: None,
         }
     }
 
@@ -118,6 +190,8 @@ mod tests {
                 VecLayerSpec {
                     option_layers_above: 0,
                     access: AccessChain::empty(),
+                    fixed_len: None,
+                    as_list: None,
                 },
                 Vec::new(),
             ),
@@ -129,6 +203,7 @@ mod tests {
     #[test]
     fn generated_trait_impls_are_automatically_derived() {
         let empty_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("EmptyRow"),
             generics: syn::Generics::default(),
             columns: Vec::new(),
@@ -136,6 +211,7 @@ mod tests {
         assert_generated_impls_are_automatically_derived(&empty_ir);
 
         let non_empty_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("Row"),
             generics: syn::Generics::default(),
             columns: vec![numeric_column("id", WrapperShape::Leaf(LeafShape::Bare))],
@@ -146,6 +222,7 @@ mod tests {
     #[test]
     fn list_assembly_helper_is_emitted_only_for_vec_shapes() {
         let scalar_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("ScalarRow"),
             generics: syn::Generics::default(),
             columns: vec![numeric_column("id", WrapperShape::Leaf(LeafShape::Bare))],
@@ -159,6 +236,7 @@ mod tests {
         assert!(!scalar.contains("unsafe"), "{scalar}");
 
         let vec_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("VecRow"),
             generics: syn::Generics::default(),
             columns: vec![numeric_column("ids", depth_one_vec_shape())],
@@ -179,6 +257,7 @@ mod tests {
             encoder::idents::validate_nested_column_dtype().to_string();
 
         let scalar_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("ScalarRow"),
             generics: syn::Generics::default(),
             columns: vec![numeric_column("id", WrapperShape::Leaf(LeafShape::Bare))],
@@ -188,6 +267,7 @@ mod tests {
         assert!(!scalar.contains(&validate_nested_column_dtype), "{scalar}");
 
         let primitive_vec_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("PrimitiveVecRow"),
             generics: syn::Generics::default(),
             columns: vec![numeric_column("ids", depth_one_vec_shape())],
@@ -203,6 +283,7 @@ mod tests {
         );
 
         let nested_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("NestedRow"),
             generics: syn::Generics::default(),
             columns: vec![nested_column("inner", WrapperShape::Leaf(LeafShape::Bare))],
@@ -212,6 +293,7 @@ mod tests {
         assert!(nested.contains(&validate_nested_column_dtype), "{nested}");
 
         let tuple_nested_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("TupleNestedRow"),
             generics: syn::Generics::default(),
             columns: vec![ColumnIR::field(
@@ -235,6 +317,7 @@ mod tests {
     #[test]
     fn builder_only_columnar_impl_omits_empty_row_loop() {
         let vec_ir = StructIR {
+            vis: syn::Visibility::Inherited,
             name: format_ident!("VecOnlyRow"),
             generics: syn::Generics::default(),
             columns: vec![numeric_column("ids", depth_one_vec_shape())],
@@ -244,4 +327,137 @@ mod tests {
 
         assert!(!generated.contains(&empty_loop), "{generated}");
     }
+
+    #[test]
+    fn arrow_schema_override_is_emitted_only_for_builtin_runtime() {
+        let ir = StructIR {
+            vis: syn::Visibility::Inherited,
+            name: format_ident!("Row"),
+            generics: syn::Generics::default(),
+            columns: vec![numeric_column("id", depth_one_vec_shape())],
+        };
+        let builtin = generate_code(&ir, &test_config()).to_string();
+        assert!(builtin.contains("fn arrow_schema"), "{builtin}");
+        assert!(builtin.contains("& [false , false]"), "{builtin}");
+
+        let custom_config = MacroConfig {
+            builtin_runtime: None,
+            ..test_config()
+        };
+        let custom = generate_code(&ir, &custom_config).to_string();
+        assert!(!custom.contains("fn arrow_schema"), "{custom}");
+    }
+
+    #[test]
+    fn schema_lock_renders_one_column_per_line() {
+        let ir = StructIR {
+            vis: syn::Visibility::Inherited,
+            name: format_ident!("Row"),
+            generics: syn::Generics::default(),
+            columns: vec![
+                numeric_column("id", WrapperShape::Leaf(LeafShape::Bare)),
+                numeric_column("ids", depth_one_vec_shape()),
+                nested_column("inner", WrapperShape::Leaf(LeafShape::Bare)),
+            ],
+        };
+        assert_eq!(
+            schema_lock::render(&ir),
+            "{\n  \"type\": \"Row\",\n  \"columns\": [\n    \
+             {\"name\": \"id\", \"dtype\": \"UInt32\", \"nullable\": [false]},\n    \
+             {\"name\": \"ids\", \"dtype\": \"List(UInt32)\", \"nullable\": [false, false]},\n    \
+             {\"name\": \"inner\", \"nested\": \"Inner\", \"list_depth\": 0, \"nullable\": [false]}\n  \
+             ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn column_handles_are_emitted_only_for_builtin_runtime() {
+        let flat_ir = StructIR {
+            vis: syn::Visibility::Inherited,
+            name: format_ident!("Row"),
+            generics: syn::Generics::default(),
+            columns: vec![numeric_column("id", WrapperShape::Leaf(LeafShape::Bare))],
+        };
+        let builtin = generate_code(&flat_ir, &test_config()).to_string();
+        assert!(builtin.contains("COLUMN_NAMES"), "{builtin}");
+        assert!(builtin.contains("fn cols"), "{builtin}");
+
+        let custom_config = MacroConfig {
+            builtin_runtime: None,
+            ..test_config()
+        };
+        let custom = generate_code(&flat_ir, &custom_config).to_string();
+        assert!(custom.contains("COLUMN_NAMES"), "{custom}");
+        assert!(!custom.contains("fn cols"), "{custom}");
+
+        let nested_ir = StructIR {
+            vis: syn::Visibility::Inherited,
+            name: format_ident!("Outer"),
+            generics: syn::Generics::default(),
+            columns: vec![nested_column("inner", WrapperShape::Leaf(LeafShape::Bare))],
+        };
+        let nested = generate_code(&nested_ir, &test_config()).to_string();
+        assert!(!nested.contains("COLUMN_NAMES"), "{nested}");
+        assert!(nested.contains("NestedColumnHandles"), "{nested}");
+    }
+
+    #[test]
+    fn typed_frame_accessors_cover_primitive_columns_on_builtin_runtime() {
+        let ir = StructIR {
+            vis: syn::Visibility::Inherited,
+            name: format_ident!("Row"),
+            generics: syn::Generics::default(),
+            columns: vec![
+                numeric_column("id", WrapperShape::Leaf(LeafShape::Bare)),
+                nested_column("inner", WrapperShape::Leaf(LeafShape::Bare)),
+            ],
+        };
+        let builtin = generate_code(&ir, &test_config()).to_string();
+        assert!(builtin.contains("TypedFrame for Row"), "{builtin}");
+        assert!(
+            builtin.contains("typed_column (& self . frame , \"id\""),
+            "{builtin}"
+        );
+        assert!(
+            !builtin.contains("typed_column (& self . frame , \"inner\""),
+            "{builtin}"
+        );
+
+        let custom_config = MacroConfig {
+            builtin_runtime: None,
+            ..test_config()
+        };
+        let custom = generate_code(&ir, &custom_config).to_string();
+        assert!(!custom.contains("TypedFrame"), "{custom}");
+    }
+
+    #[test]
+    fn row_views_are_emitted_only_on_request() {
+        let ir = StructIR {
+            vis: syn::parse_quote!(pub),
+            name: format_ident!("Quote"),
+            generics: syn::Generics::default(),
+            columns: vec![numeric_column("id", WrapperShape::Leaf(LeafShape::Bare))],
+        };
+        let plain = generate_code(&ir, &test_config()).to_string();
+        assert!(!plain.contains("RowView"), "{plain}");
+
+        let views_config = MacroConfig {
+            views: Some(proc_macro2::Span::call_site()),
+            ..test_config()
+        };
+        let views = generate_code(&ir, &views_config).to_string();
+        assert!(views.contains("pub struct QuoteView < 'df >"), "{views}");
+        assert!(views.contains("fn iter_views"), "{views}");
+
+        let custom_config = MacroConfig {
+            builtin_runtime: None,
+            ..views_config
+        };
+        let custom = generate_code(&ir, &custom_config).to_string();
+        assert!(
+            custom.contains("requires the built-in df-derive runtime"),
+            "{custom}"
+        );
+    }
 }
@@ -0,0 +1,209 @@
+//! Dispatch target of a nested column.
+//!
+//! Struct and generic leaves dispatch through their own type and the
+//! runtime's `Columnar`. Map leaves dispatch through the runtime's
+//! `MapColumns<Map, Order, Layout>` adapter, whose `ToDataFrame` impl
+//! describes the map's columns and whose `MapColumnar` impl converts the maps
+//! themselves. `with` leaves dispatch the same way through
+//! `WithColumns<Marker>`, where the marker is a per-field type emitted here
+//! whose `WithConversion` impl calls the field's function, and `leaf`
+//! fields through `LeafColumns<Type>` and the type's `DfLeaf` impl.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use crate::ir::{LeafCast, LeafSpec, MapLeaf, NestedLeaf, StructIR, WithLeaf};
+
+use super::MacroConfig;
+use super::column_handles::phantom_args;
+use super::encoder::{idents, struct_type_tokens};
+
+pub(in crate::codegen) struct NestedTarget {
+    /// Type whose `ToDataFrame` impl describes the nested columns.
+    pub ty: TokenStream,
+    /// Field leaf type the encoder collects references to.
+    pub item_ty: TokenStream,
+    /// Trait whose `columnar_from_refs` converts `&[&FieldType]`.
+    pub columnar_trait: syn::Path,
+}
+
+pub(in crate::codegen) fn nested_target(
+    nested: NestedLeaf<'_>,
+    config: &MacroConfig,
+) -> NestedTarget {
+    match nested {
+        NestedLeaf::Struct(ty) => NestedTarget {
+            ty: struct_type_tokens(ty),
+            item_ty: struct_type_tokens(ty),
+            columnar_trait: config.traits.columnar.clone(),
+        },
+        NestedLeaf::Generic(ident) => NestedTarget {
+            ty: quote! { #ident },
+            item_ty: quote! { #ident },
+            columnar_trait: config.traits.columnar.clone(),
+        },
+        NestedLeaf::Map(map) => {
+            let runtime = builtin_runtime(config);
+            let map_ty = &map.ty;
+            NestedTarget {
+                ty: map_columns_type(map, runtime),
+                item_ty: quote! { #map_ty },
+                columnar_trait: syn::parse_quote!(#runtime::__private::MapColumnar),
+            }
+        }
+        NestedLeaf::DfLeaf(ty) => {
+            let runtime = builtin_runtime(config);
+            NestedTarget {
+                ty: leaf_columns_type(ty, runtime),
+                item_ty: quote! { #ty },
+                columnar_trait: syn::parse_quote!(#runtime::__private::LeafColumnar),
+            }
+        }
+        NestedLeaf::With(with) => {
+            let runtime = builtin_runtime(config);
+            let source = &with.source;
+            NestedTarget {
+                ty: with_columns_type(with, runtime),
+                item_ty: quote! { #source },
+                columnar_trait: syn::parse_quote!(#runtime::__private::WithColumnar),
+            }
+        }
+    }
+}
+
+/// `MapColumns<Map, Order, Layout>` for a map leaf.
+pub(in crate::codegen) fn map_columns_type(map: &MapLeaf, runtime: &TokenStream) -> TokenStream {
+    let ty = &map.ty;
+    let order = if map.sort_keys {
+        quote! { KeyOrder }
+    } else {
+        quote! { IterationOrder }
+    };
+    let layout = if map.split {
+        quote! { SplitEntries }
+    } else {
+        quote! { StructEntries }
+    };
+    quote! {
+        #runtime::__private::MapColumns<
+            #ty,
+            #runtime::__private::#order,
+            #runtime::__private::#layout,
+        >
+    }
+}
+
+/// `LeafColumns<Type>` for a `leaf` field.
+pub(in crate::codegen) fn leaf_columns_type(ty: &syn::Type, runtime: &TokenStream) -> TokenStream {
+    quote! { #runtime::__private::LeafColumns<#ty> }
+}
+
+/// `WithColumns<Marker<..>>` for a `with` leaf.
+pub(in crate::codegen) fn with_columns_type(with: &WithLeaf, runtime: &TokenStream) -> TokenStream {
+    let marker = idents::with_marker(&with.field);
+    let (_, ty_generics, _) = with.generics.split_for_impl();
+    quote! { #runtime::__private::WithColumns<#marker #ty_generics> }
+}
+
+/// One marker type per `with` field. The marker carries the struct's
+/// generics, which the source type may use, and its `WithConversion` impl
+/// passes the function to the runtime's visitor, so the function's return
+/// type is inferred rather than named.
+pub(in crate::codegen) fn generate_with_markers(
+    ir: &StructIR,
+    config: &MacroConfig,
+) -> TokenStream {
+    let mut withs: Vec<(&WithLeaf, Option<LeafCast>)> = Vec::new();
+    for column in &ir.columns {
+        column
+            .leaf_spec()
+            .as_leaf_spec()
+            .walk_terminal_leaves(&mut |leaf| {
+                if let LeafSpec::With(with) = leaf
+                    && !withs.iter().any(|(seen, _)| seen.field == with.field)
+                {
+                    withs.push((with, column.leaf_cast().copied()));
+                }
+            });
+    }
+    if withs.is_empty() {
+        return TokenStream::new();
+    }
+    let runtime = builtin_runtime(config);
+    let (impl_generics, ty_generics, where_clause) = ir.generics.split_for_impl();
+    let decl_where = &ir.generics.where_clause;
+    let decl_generics = &ir.generics;
+    let phantom_args = phantom_args(&ir.generics);
+
+    let markers = withs.into_iter().map(|(with, cast)| {
+        let marker = idents::with_marker(&with.field);
+        let source = &with.source;
+        let func = &with.func;
+        let visit = quote_spanned! {func.span()=> visitor.visit(#func) };
+        let cast = cast.map(|cast| with_cast(with, cast, config));
+        quote! {
+            #[allow(non_camel_case_types, dead_code)]
+            struct #marker #decl_generics #decl_where (
+                ::core::marker::PhantomData<fn(#(#phantom_args),*)>,
+            );
+
+            #[automatically_derived]
+            impl #impl_generics #runtime::__private::WithConversion
+                for #marker #ty_generics #where_clause
+            {
+                type Source = #source;
+
+                fn convert<V: #runtime::__private::WithVisitor<Self::Source>>(
+                    visitor: V,
+                ) -> V::Output {
+                    #visit
+                }
+
+                #cast
+            }
+        }
+    });
+    quote! { #(#markers)* }
+}
+
+/// `WithConversion` items that cast a `dtype` field's result through the
+/// derive's leaf-cast helper. The result's column is unnamed until it is
+/// placed under the field, so it carries the field's name while the cast
+/// runs and an overflow error names the field.
+fn with_cast(with: &WithLeaf, cast: LeafCast, config: &MacroConfig) -> TokenStream {
+    let pp = config.external_paths.prelude();
+    let cast_leaf_columns = idents::cast_leaf_columns();
+    let dtype = cast.dtype.logical().dtype(&config.external_paths);
+    let overflow_to_null = cast.overflow_to_null;
+    let field_name = with.field.to_string();
+    quote! {
+        fn leaf_cast() -> ::core::option::Option<(#pp::DataType, bool)> {
+            ::core::option::Option::Some((#dtype, #overflow_to_null))
+        }
+
+        fn cast_columns(columns: &mut [#pp::Column]) -> #pp::PolarsResult<()> {
+            for column in columns {
+                let name = column.name().clone();
+                column.rename(#pp::PlSmallStr::from_static(#field_name));
+                let cast = #cast_leaf_columns(
+                    ::core::slice::from_mut(column),
+                    &#dtype,
+                    #overflow_to_null,
+                );
+                column.rename(name);
+                cast?;
+            }
+            ::std::result::Result::Ok(())
+        }
+    }
+}
+
+/// The built-in runtime, which `generate_code` requires before emitting any
+/// map, `with`, or `leaf` column.
+pub(in crate::codegen) const fn builtin_runtime(config: &MacroConfig) -> &TokenStream {
+    config
+        .builtin_runtime
+        .as_ref()
+        .expect("map, `with`, and `leaf` fields are rejected on custom runtimes before codegen")
+}
@@ -0,0 +1,303 @@
+// Borrowed row views requested with `#[df_derive(views)]`.
+//
+// The view struct is emitted next to the deriving type so it can be named
+// (`TradeView<'df>`); its reader and the `RowView` impl stay inside the
+// derive's `const _` block. Each column becomes one view field, named like
+// its `cols()` accessor: primitive columns read from the column's chunked
+// array, single-level `Vec`s become `ListView` iterators, and non-optional
+// nested structs delegate to the nested type's own view.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::codegen::encoder::idents;
+use crate::ir::{ColumnIR, NestedLeaf, StructIR, TerminalLeafRoute};
+
+use super::column_handles::accessor_ident;
+
+pub struct RowViews {
+    /// The public view struct, emitted outside the `const _` block.
+    pub view_struct: TokenStream,
+    /// Reader, `RowView` impl and `T::iter_views`.
+    pub impls: TokenStream,
+}
+
+/// One view field: its type, the reader member resolving its column, and
+/// the expression reading one row from that member.
+struct ViewField {
+    ident: Ident,
+    doc: String,
+    ty: TokenStream,
+    borrows: bool,
+    reader_ty: TokenStream,
+    reader_init: TokenStream,
+    read: TokenStream,
+}
+
+fn unsupported(column: &ColumnIR, span: Span) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "`views` cannot view column `{}`; views support primitive fields, \
+             `Option`s of them, single-level `Vec`s of them, and non-optional \
+             nested structs that also derive `views`",
+            column.name()
+        ),
+    )
+}
+
+fn view_field(
+    column: &ColumnIR,
+    member: &syn::Index,
+    config: &super::MacroConfig,
+    runtime: &TokenStream,
+    span: Span,
+) -> syn::Result<ViewField> {
+    let paths = &config.external_paths;
+    let pp = paths.prelude();
+    let name = column.name();
+    let doc = format!("Column `{name}`.");
+    let ident = accessor_ident(name);
+    let nullability = column.nullability();
+    let nullable = nullability.first().copied().unwrap_or(false);
+    let lifetime = quote! { 'df };
+
+    match column.leaf_spec().route() {
+        TerminalLeafRoute::Primitive(leaf) => {
+            let logical = column.leaf_logical(leaf);
+            let (value, physical) = logical.view_value(paths, &lifetime);
+            let borrows = logical.view_borrows();
+            match column.vec_depth() {
+                0 => {
+                    let (chunked, downcast) = logical.chunked_downcast(paths, runtime);
+                    let resolve = quote! {
+                        #runtime::__private::view_column(frame, prefix, #name, #downcast)?
+                    };
+                    let (reader_ty, reader_init) = physical.map_or_else(
+                        || (chunked, resolve.clone()),
+                        |physical| (physical, quote! { #resolve.physical() }),
+                    );
+                    let (ty, read) = if nullable {
+                        (
+                            quote! { ::core::option::Option<#value> },
+                            quote! { self.#member.get(row) },
+                        )
+                    } else {
+                        (value, quote! { self.#member.get(row).unwrap_or_default() })
+                    };
+                    Ok(ViewField {
+                        ident,
+                        doc,
+                        ty,
+                        borrows,
+                        reader_ty: quote! { &'df #reader_ty },
+                        reader_init,
+                        read,
+                    })
+                }
+                1 if column.fixed_lens().is_empty() => {
+                    let element_nullable = nullability.get(1).copied().unwrap_or(false);
+                    let element = if element_nullable {
+                        quote! { ::core::option::Option<#value> }
+                    } else {
+                        value
+                    };
+                    let list = quote! { #runtime::views::ListView<'df, #element> };
+                    let read = quote! { #runtime::__private::list_view(self.#member, row) };
+                    let (ty, read) = if nullable {
+                        (quote! { ::core::option::Option<#list> }, read)
+                    } else {
+                        (list, quote! { #read.unwrap_or_default() })
+                    };
+                    Ok(ViewField {
+                        ident,
+                        doc,
+                        ty,
+                        borrows: true,
+                        reader_ty: quote! { &'df #pp::ListChunked },
+                        reader_init: quote! {
+                            #runtime::__private::view_column(frame, prefix, #name, #pp::Column::list)?
+                        },
+                        read,
+                    })
+                }
+                _ => Err(unsupported(column, span)),
+            }
+        }
+        TerminalLeafRoute::Nested(NestedLeaf::Struct(ty))
+            if column.vec_depth() == 0 && !nullable =>
+        {
+            let row_view = quote! { #runtime::views::RowView };
+            Ok(ViewField {
+                ident,
+                doc: format!("Columns under `{name}`."),
+                ty: quote! { <#ty as #row_view>::View<'df> },
+                borrows: true,
+                reader_ty: quote! { <#ty as #row_view>::Reader<'df> },
+                reader_init: quote! {
+                    <#ty as #row_view>::view_reader(
+                        frame,
+                        &#runtime::__private::nested_column_prefix(prefix, #name),
+                    )?
+                },
+                read: quote! { #runtime::__private::ViewReader::view(&self.#member, row) },
+            })
+        }
+        TerminalLeafRoute::Nested(_) => Err(unsupported(column, span)),
+    }
+}
+
+fn view_fields(
+    ir: &StructIR,
+    config: &super::MacroConfig,
+    runtime: &TokenStream,
+    span: Span,
+) -> syn::Result<Vec<ViewField>> {
+    if !ir.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "`views` is not supported on generic structs",
+        ));
+    }
+    if config.transparent.is_some() {
+        return Err(syn::Error::new(
+            span,
+            "`views` is not supported on `transparent` structs",
+        ));
+    }
+    if ir.columns.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "`views` requires at least one column",
+        ));
+    }
+    let mut fields: Vec<ViewField> = Vec::with_capacity(ir.columns.len());
+    for (index, column) in ir.columns.iter().enumerate() {
+        let field = view_field(column, &syn::Index::from(index), config, runtime, span)?;
+        if fields.iter().any(|existing| existing.ident == field.ident) {
+            return Err(syn::Error::new(
+                span,
+                format!(
+                    "`views` needs one field per column, but column `{}` maps to \
+                     the same view field as an earlier column",
+                    column.name()
+                ),
+            ));
+        }
+        fields.push(field);
+    }
+    Ok(fields)
+}
+
+pub fn generate_row_views(ir: &StructIR, config: &super::MacroConfig) -> RowViews {
+    let empty = || RowViews {
+        view_struct: TokenStream::new(),
+        impls: TokenStream::new(),
+    };
+    let Some(span) = config.views else {
+        return empty();
+    };
+    let Some(runtime) = &config.builtin_runtime else {
+        return RowViews {
+            view_struct: syn::Error::new(span, "`views` requires the built-in df-derive runtime")
+                .to_compile_error(),
+            impls: TokenStream::new(),
+        };
+    };
+    let fields = match view_fields(ir, config, runtime, span) {
+        Ok(fields) => fields,
+        Err(error) => {
+            return RowViews {
+                view_struct: error.to_compile_error(),
+                ..empty()
+            };
+        }
+    };
+
+    let pp = config.external_paths.prelude();
+    let vis = &ir.vis;
+    let struct_name = &ir.name;
+    let view = format_ident!("{}View", struct_name, span = struct_name.span());
+    let reader = idents::view_reader();
+    let doc =
+        format!("Borrowed row view of [`{struct_name}`], generated by `#[df_derive(views)]`.");
+
+    let idents: Vec<&Ident> = fields.iter().map(|field| &field.ident).collect();
+    let docs = fields.iter().map(|field| &field.doc);
+    let tys = fields.iter().map(|field| &field.ty);
+    let reader_tys = fields.iter().map(|field| &field.reader_ty);
+    let reader_inits = fields.iter().map(|field| &field.reader_init);
+    let reads = fields.iter().map(|field| &field.read);
+
+    // A view with no borrowed field still carries `'df`.
+    let (marker_decl, marker_init) = if fields.iter().any(|field| field.borrows) {
+        (TokenStream::new(), TokenStream::new())
+    } else {
+        let marker = idents::view_lifetime_marker();
+        (
+            quote! { #marker: ::core::marker::PhantomData<&'df ()>, },
+            quote! { #marker: ::core::marker::PhantomData, },
+        )
+    };
+
+    let view_struct = quote! {
+        #[doc = #doc]
+        #[derive(::core::clone::Clone, ::core::fmt::Debug)]
+        #vis struct #view<'df> {
+            #(
+                #[doc = #docs]
+                pub #idents: #tys,
+            )*
+            #marker_decl
+        }
+    };
+
+    // The reader shares the view's visibility so neither leaks through the
+    // other's trait impl.
+    let impls = quote! {
+        #vis struct #reader<'df>(
+            #(#reader_tys,)*
+            ::core::marker::PhantomData<&'df ()>,
+        );
+
+        impl<'df> #runtime::__private::ViewReader<'df> for #reader<'df> {
+            type View = #view<'df>;
+
+            fn view(&self, row: usize) -> Self::View {
+                #view {
+                    #(#idents: #reads,)*
+                    #marker_init
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #runtime::views::RowView for #struct_name {
+            type View<'df> = #view<'df>;
+            type Reader<'df> = #reader<'df>;
+
+            fn view_reader<'df>(
+                frame: &'df #pp::DataFrame,
+                prefix: &str,
+            ) -> #pp::PolarsResult<Self::Reader<'df>> {
+                ::core::result::Result::Ok(#reader(
+                    #(#reader_inits,)*
+                    ::core::marker::PhantomData,
+                ))
+            }
+        }
+
+        impl #struct_name {
+            /// Borrowed views of `frame`'s rows, after checking it against
+            /// `Self::schema()` once.
+            pub fn iter_views(
+                frame: &#pp::DataFrame,
+            ) -> #pp::PolarsResult<#runtime::views::RowViews<'_, Self>> {
+                #runtime::views::iter_views::<Self>(frame)
+            }
+        }
+    };
+
+    RowViews { view_struct, impls }
+}
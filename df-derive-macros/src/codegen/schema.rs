@@ -1,41 +1,64 @@
-use crate::ir::{ColumnIR, NestedLeaf, PrimitiveLeaf, TerminalLeafRoute};
+use crate::ir::{ColumnIR, PrimitiveLeaf, TerminalLeafRoute};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use super::encoder::struct_type_tokens;
-
-fn nested_type_path(nested: NestedLeaf<'_>) -> TokenStream {
-    match nested {
-        NestedLeaf::Struct(ty) => struct_type_tokens(ty),
-        NestedLeaf::Generic(id) => quote! { #id },
-    }
-}
+use super::nested_target::nested_target;
 
 fn column_full_dtype(
     leaf: PrimitiveLeaf<'_>,
-    vec_depth: usize,
+    column: &ColumnIR,
     config: &super::MacroConfig,
 ) -> TokenStream {
     let pp = config.external_paths.prelude();
-    let elem_dtype = leaf.dtype(&config.external_paths);
-    super::external_paths::wrap_list_layers_compile_time(pp, elem_dtype, vec_depth)
+    let elem_dtype = column.leaf_logical(leaf).dtype(&config.external_paths);
+    let fixed_lens = column.fixed_lens();
+    if fixed_lens.is_empty() {
+        return super::external_paths::wrap_list_layers_compile_time(
+            pp,
+            elem_dtype,
+            column.vec_depth(),
+        );
+    }
+    fixed_lens.iter().rev().fold(elem_dtype, |dt, len| {
+        len.map_or_else(
+            || quote! { #pp::DataType::List(::std::boxed::Box::new(#dt)) },
+            |len| quote! { #pp::DataType::Array(::std::boxed::Box::new(#dt), #len) },
+        )
+    })
+}
+
+/// `&[Option<usize>]` literal holding the length of each fixed-width list
+/// layer of `column`, outermost first, or `None` when it has none.
+pub(in crate::codegen) fn fixed_lens_arg(column: &ColumnIR) -> Option<TokenStream> {
+    let fixed_lens = column.fixed_lens();
+    if fixed_lens.is_empty() {
+        return None;
+    }
+    let lens = fixed_lens.iter().map(|len| {
+        len.map_or_else(
+            || quote! { ::core::option::Option::None },
+            |len| quote! { ::core::option::Option::Some(#len) },
+        )
+    });
+    Some(quote! { &[#(#lens),*] })
 }
 
 pub fn build_schema_entries(column: &ColumnIR, config: &super::MacroConfig) -> TokenStream {
     let name = column.name();
     match column.leaf_spec().route() {
         TerminalLeafRoute::Nested(nested) => {
-            let type_path = nested_type_path(nested);
+            let type_path = nested_target(nested, config).ty;
             super::schema_nested::generate_schema_entries_for_struct(
                 &type_path,
                 &config.traits.to_dataframe,
                 name,
                 column.vec_depth(),
+                fixed_lens_arg(column).as_ref(),
                 &config.external_paths,
             )
         }
         TerminalLeafRoute::Primitive(leaf) => {
-            let dtype = column_full_dtype(leaf, column.vec_depth(), config);
+            let dtype = column_full_dtype(leaf, column, config);
             quote! { ::std::vec![(::std::string::String::from(#name), #dtype)] }
         }
     }
@@ -45,19 +68,89 @@ pub fn build_empty_series(column: &ColumnIR, config: &super::MacroConfig) -> Tok
     let name = column.name();
     match column.leaf_spec().route() {
         TerminalLeafRoute::Nested(nested) => {
-            let type_path = nested_type_path(nested);
+            let type_path = nested_target(nested, config).ty;
             super::schema_nested::nested_empty_series_row(
                 &type_path,
                 &config.traits.to_dataframe,
                 name,
                 column.vec_depth(),
+                fixed_lens_arg(column).as_ref(),
                 &config.external_paths,
             )
         }
         TerminalLeafRoute::Primitive(leaf) => {
-            let dtype = column_full_dtype(leaf, column.vec_depth(), config);
+            let dtype = column_full_dtype(leaf, column, config);
             let pp = config.external_paths.prelude();
             quote! { ::std::vec![#pp::Series::new_empty(#name.into(), &#dtype).into()] }
         }
     }
 }
+
+/// Arrow field entries for `arrow_schema()`, carrying the column's
+/// nullability. `runtime` is the built-in `dataframe` module whose hidden
+/// helpers assemble the fields.
+pub fn build_arrow_field_entries(
+    column: &ColumnIR,
+    config: &super::MacroConfig,
+    runtime: &TokenStream,
+) -> TokenStream {
+    let name = column.name();
+    let nullability = column.nullability();
+    match column.leaf_spec().route() {
+        TerminalLeafRoute::Nested(nested) => {
+            let type_path = nested_target(nested, config).ty;
+            super::schema_nested::generate_arrow_fields_for_struct(
+                &type_path,
+                &config.traits.to_dataframe,
+                name,
+                &nullability,
+                fixed_lens_arg(column).as_ref(),
+                runtime,
+                &config.external_paths,
+            )
+        }
+        TerminalLeafRoute::Primitive(leaf) => {
+            let leaf_dtype = column.leaf_logical(leaf).dtype(&config.external_paths);
+            let field = super::schema_nested::layered_arrow_field(
+                &quote! { #name.into() },
+                &quote! { #runtime::__private::required_arrow_field(&#leaf_dtype) },
+                &nullability,
+                fixed_lens_arg(column).as_ref(),
+                runtime,
+            );
+            quote! { ::std::vec![#field] }
+        }
+    }
+}
+
+/// Column metadata entries for `column_metadata()`. Primitive columns
+/// without metadata contribute nothing; nested columns always defer to the
+/// inner type, which may carry metadata of its own.
+pub fn build_column_metadata_entries(
+    column: &ColumnIR,
+    config: &super::MacroConfig,
+    runtime: &TokenStream,
+) -> Option<TokenStream> {
+    let name = column.name();
+    let keys = column.metadata().iter().map(|(key, _)| key);
+    let values = column.metadata().iter().map(|(_, value)| value);
+    let entries = quote! { &[#((#keys, #values)),*] };
+    match column.leaf_spec().route() {
+        TerminalLeafRoute::Nested(nested) => {
+            let type_path = nested_target(nested, config).ty;
+            let to_df_trait = &config.traits.to_dataframe;
+            Some(quote! {
+                #runtime::__private::nested_column_metadata(
+                    #name,
+                    #entries,
+                    <#type_path as #to_df_trait>::schema()?,
+                    <#type_path as #to_df_trait>::column_metadata()?,
+                )
+            })
+        }
+        TerminalLeafRoute::Primitive(_) if column.metadata().is_empty() => None,
+        TerminalLeafRoute::Primitive(_) => Some(quote! {
+            ::std::vec![#runtime::__private::column_metadata_entry(#name, #entries)]
+        }),
+    }
+}
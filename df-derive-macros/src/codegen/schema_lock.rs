@@ -0,0 +1,266 @@
+use crate::attrs::Spanned;
+use crate::ir::{ColumnIR, NestedLeaf, StructIR, TerminalLeafRoute};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+// Nested and generic columns are locked by type name and list layers only.
+// Their own columns come from the nested type's derive, which this expansion
+// cannot see; lock those through the nested type's own `schema_lock`.
+
+/// Environment variable that makes the derive rewrite lock files instead of
+/// comparing against them.
+const UPDATE_ENV: &str = "DF_DERIVE_UPDATE_SCHEMAS";
+
+pub fn check_schema_lock(ir: &StructIR, lock: &Spanned<String>) -> TokenStream {
+    match check(ir, lock) {
+        Ok(path) => {
+            // Track the lock file so editing it re-runs the check.
+            let path = path.to_string_lossy().into_owned();
+            quote! { const _: &[u8] = ::core::include_bytes!(#path); }
+        }
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+fn check(ir: &StructIR, lock: &Spanned<String>) -> syn::Result<PathBuf> {
+    let path = resolve_path(&lock.value);
+    let rendered = render(ir);
+    let display = path.display();
+
+    if std::env::var_os(UPDATE_ENV).is_some_and(|value| value != "0") {
+        write_lock(&path, &rendered).map_err(|error| {
+            syn::Error::new(
+                lock.span,
+                format!("failed to write schema lock `{display}`: {error}"),
+            )
+        })?;
+        return Ok(path);
+    }
+
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(existing) => existing,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Err(syn::Error::new(
+                lock.span,
+                format!(
+                    "schema lock `{display}` does not exist; rebuild with \
+                     `{UPDATE_ENV}=1` to create it"
+                ),
+            ));
+        }
+        Err(error) => {
+            return Err(syn::Error::new(
+                lock.span,
+                format!("failed to read schema lock `{display}`: {error}"),
+            ));
+        }
+    };
+
+    if normalize(&existing) == normalize(&rendered) {
+        return Ok(path);
+    }
+    Err(syn::Error::new(
+        lock.span,
+        format!(
+            "derived columns of `{}` do not match schema lock `{display}`:\n{}\n\
+             if the change is intended, rebuild with `{UPDATE_ENV}=1` to update the lock",
+            ir.name,
+            line_diff(&existing, &rendered),
+        ),
+    ))
+}
+
+/// Lock paths are relative to the deriving crate's manifest directory.
+fn resolve_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_owned();
+    }
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map_or_else(|| path.to_owned(), |dir| PathBuf::from(dir).join(path))
+}
+
+fn write_lock(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Leave an up-to-date file untouched so its mtime does not churn.
+    if std::fs::read_to_string(path)
+        .is_ok_and(|existing| normalize(&existing) == normalize(contents))
+    {
+        return Ok(());
+    }
+    std::fs::write(path, contents)
+}
+
+fn normalize(contents: &str) -> Vec<&str> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Lines removed from `expected` (`-`) and added in `actual` (`+`), in
+/// document order, around their longest common subsequence, so a reordered
+/// column shows up as a removal and an addition.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected = normalize(expected);
+    let actual = normalize(actual);
+    // `common[i][j]`: length of the longest common subsequence of
+    // `expected[i..]` and `actual[j..]`.
+    let mut common = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            let _ = writeln!(out, "  - {}", expected[i]);
+            i += 1;
+        } else {
+            let _ = writeln!(out, "  + {}", actual[j]);
+            j += 1;
+        }
+    }
+    out.trim_end().to_owned()
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Render the lock document: one column object per line, in column order.
+pub(super) fn render(ir: &StructIR) -> String {
+    let mut out = String::from("{\n");
+    let _ = writeln!(out, "  \"type\": {},", json_string(&ir.name.to_string()));
+    out.push_str("  \"columns\": [\n");
+    for (index, column) in ir.columns.iter().enumerate() {
+        let separator = if index + 1 == ir.columns.len() {
+            ""
+        } else {
+            ","
+        };
+        let _ = writeln!(out, "    {}{separator}", render_column(column));
+    }
+    out.push_str("  ]\n}\n");
+    out
+}
+
+/// An array length as written, e.g. `3` or `N`.
+fn len_string(len: &syn::Expr) -> String {
+    len.to_token_stream().to_string().replace(' ', "")
+}
+
+fn render_column(column: &ColumnIR) -> String {
+    let name = json_string(column.name());
+    let nullable = column
+        .nullability()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    match column.leaf_spec().route() {
+        TerminalLeafRoute::Primitive(leaf) => {
+            let mut dtype = column.leaf_logical(leaf).name();
+            let fixed_lens = column.fixed_lens();
+            for layer in (0..column.vec_depth()).rev() {
+                dtype = fixed_lens.get(layer).copied().flatten().map_or_else(
+                    || format!("List({dtype})"),
+                    |len| format!("Array({dtype}, {})", len_string(len)),
+                );
+            }
+            format!(
+                "{{\"name\": {name}, \"dtype\": {}, \"nullable\": [{nullable}]}}",
+                json_string(&dtype)
+            )
+        }
+        TerminalLeafRoute::Nested(nested) => {
+            let type_name = match nested {
+                NestedLeaf::Struct(ty) => ty.to_token_stream().to_string().replace(' ', ""),
+                NestedLeaf::Generic(ident) => ident.to_string(),
+                NestedLeaf::Map(map) => {
+                    let mut name = map.ty.to_token_stream().to_string().replace(' ', "");
+                    for (set, flag) in [(map.sort_keys, "sort_keys"), (map.split, "split_map")] {
+                        if set {
+                            name = format!("{name} + {flag}");
+                        }
+                    }
+                    name
+                }
+                NestedLeaf::DfLeaf(ty) => {
+                    format!(
+                        "{} + leaf",
+                        ty.to_token_stream().to_string().replace(' ', "")
+                    )
+                }
+                NestedLeaf::With(with) => {
+                    let source = with.source.to_token_stream().to_string().replace(' ', "");
+                    let func = with.func.to_token_stream().to_string().replace(' ', "");
+                    let mut name = format!("{source} + with = {func}");
+                    if let Some(cast) = column.leaf_cast() {
+                        name = format!("{name} + dtype = {}", cast.dtype.logical().name());
+                    }
+                    name
+                }
+            };
+            let fixed_lens = column.fixed_lens();
+            let array_lens = if fixed_lens.is_empty() {
+                String::new()
+            } else {
+                let lens = fixed_lens
+                    .iter()
+                    .map(|len| {
+                        len.map_or_else(|| "null".to_owned(), |len| json_string(&len_string(len)))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(", \"array_lens\": [{lens}]")
+            };
+            format!(
+                "{{\"name\": {name}, \"nested\": {}, \"list_depth\": {}{array_lens}, \"nullable\": [{nullable}]}}",
+                json_string(&type_name),
+                column.vec_depth()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lists_changed_lines_in_order() {
+        assert_eq!(line_diff("a\nb\nc", "a\nx\nc"), "  - b\n  + x");
+        assert_eq!(line_diff("a\nb", "a\nb\nc"), "  + c");
+    }
+
+    #[test]
+    fn diff_shows_reordered_lines() {
+        assert_eq!(line_diff("a\nb\nc", "a\nc\nb"), "  - b\n  + b");
+    }
+}
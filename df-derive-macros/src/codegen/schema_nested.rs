@@ -23,11 +23,30 @@ fn gen_wrap_dtype_layers(layers: usize, paths: &ExternalPaths) -> TokenStream {
     super::external_paths::wrap_list_layers_runtime(pp, &wrapped, layers)
 }
 
+/// Runtime name of an inner column of a nested field: `parent.inner`, or just
+/// `parent` when the inner column is unnamed. Primitive leaf types used as
+/// generic payloads report a single unnamed column, so such a field keeps its
+/// own name. The field of a `transparent` struct has an empty `parent` and
+/// passes the inner names through.
+pub(in crate::codegen) fn prefixed_column_name(parent: &str, inner: &TokenStream) -> TokenStream {
+    if parent.is_empty() {
+        return quote! { ::std::format!("{}", #inner) };
+    }
+    quote! {
+        if #inner.is_empty() {
+            ::std::string::String::from(#parent)
+        } else {
+            ::std::format!("{}.{}", #parent, #inner)
+        }
+    }
+}
+
 pub fn nested_empty_series_row(
     type_path: &TokenStream,
     to_df_trait: &syn::Path,
     name: &str,
     list_layers: usize,
+    fixed_lens: Option<&TokenStream>,
     paths: &ExternalPaths,
 ) -> TokenStream {
     generate_for_struct(
@@ -35,6 +54,7 @@ pub fn nested_empty_series_row(
         to_df_trait,
         name,
         list_layers,
+        fixed_lens,
         EmitMode::EmptyRows,
         paths,
     )
@@ -47,6 +67,7 @@ pub fn generate_schema_entries_for_struct(
     to_df_trait: &syn::Path,
     column_name: &str,
     list_layers: usize,
+    fixed_lens: Option<&TokenStream>,
     paths: &ExternalPaths,
 ) -> TokenStream {
     generate_for_struct(
@@ -54,6 +75,7 @@ pub fn generate_schema_entries_for_struct(
         to_df_trait,
         column_name,
         list_layers,
+        fixed_lens,
         EmitMode::SchemaEntries,
         paths,
     )
@@ -64,24 +86,33 @@ pub fn generate_schema_entries_for_struct(
 /// outer field name, build a per-iteration runtime `DataType` wrapped in
 /// `list_layers` `List<>` envelopes, and push the result into a per-mode
 /// accumulator. Only the accumulator type/name and the per-iteration push
-/// expression vary, captured by [`EmitMode`].
+/// expression vary, captured by [`EmitMode`]. `fixed_lens` turns the
+/// envelopes of array layers into fixed-width `Array`s.
 fn generate_for_struct(
     type_path: &TokenStream,
     to_df_trait: &syn::Path,
     column_name: &str,
     list_layers: usize,
+    fixed_lens: Option<&TokenStream>,
     mode: EmitMode,
     paths: &ExternalPaths,
 ) -> TokenStream {
     let pp = paths.prelude();
-    let wrap_layers = gen_wrap_dtype_layers(list_layers, paths);
     let wrapped = idents::schema_wrapped_dtype();
+    let mut wrap_layers = gen_wrap_dtype_layers(list_layers, paths);
+    if let Some(fixed_lens) = fixed_lens {
+        let array_dtype = idents::array_dtype();
+        wrap_layers.extend(quote! {
+            #wrapped = #array_dtype(#wrapped, #fixed_lens);
+        });
+    }
+    let prefixed_name = prefixed_column_name(column_name, &quote! { inner_name });
     match mode {
         EmitMode::SchemaEntries => quote! {
             {
                 let mut nested_fields: ::std::vec::Vec<(::std::string::String, #pp::DataType)> = ::std::vec::Vec::new();
                 for (inner_name, inner_dtype) in <#type_path as #to_df_trait>::schema()? {
-                    let prefixed_name = ::std::format!("{}.{}", #column_name, inner_name);
+                    let prefixed_name = #prefixed_name;
                     let mut #wrapped: #pp::DataType = inner_dtype;
                     #wrap_layers
                     nested_fields.push((prefixed_name, #wrapped));
@@ -93,7 +124,7 @@ fn generate_for_struct(
             {
                 let mut nested_series: ::std::vec::Vec<#pp::Column> = ::std::vec::Vec::new();
                 for (inner_name, inner_dtype) in <#type_path as #to_df_trait>::schema()? {
-                    let prefixed_name = ::std::format!("{}.{}", #column_name, inner_name);
+                    let prefixed_name = #prefixed_name;
                     let mut #wrapped: #pp::DataType = inner_dtype;
                     #wrap_layers
                     let empty_series = #pp::Series::new_empty(prefixed_name.as_str().into(), &#wrapped);
@@ -104,3 +135,65 @@ fn generate_for_struct(
         },
     }
 }
+
+/// Runtime emitter for a nested column's Arrow fields. Iterates
+/// `T::arrow_schema()?`, prefixes each inner name with the outer field name,
+/// and wraps the inner field in the outer column's list layers, keeping any
+/// nullability the inner field already carries.
+pub fn generate_arrow_fields_for_struct(
+    type_path: &TokenStream,
+    to_df_trait: &syn::Path,
+    column_name: &str,
+    nullability: &[bool],
+    fixed_lens: Option<&TokenStream>,
+    runtime: &TokenStream,
+    paths: &ExternalPaths,
+) -> TokenStream {
+    let pp = paths.prelude();
+    let prefixed_name = prefixed_column_name(column_name, &quote! { inner_field.name });
+    let field = layered_arrow_field(
+        &quote! { prefixed_name.into() },
+        &quote! { ::std::clone::Clone::clone(inner_field) },
+        nullability,
+        fixed_lens,
+        runtime,
+    );
+    quote! {
+        {
+            let mut nested_fields: ::std::vec::Vec<#pp::ArrowField> = ::std::vec::Vec::new();
+            for inner_field in <#type_path as #to_df_trait>::arrow_schema()?.iter_values() {
+                let prefixed_name = #prefixed_name;
+                nested_fields.push(#field);
+            }
+            nested_fields
+        }
+    }
+}
+
+/// Arrow field `name` wrapping `leaf` in the column's list layers:
+/// `LargeList`s, or `FixedSizeList`s for the layers `fixed_lens` sizes.
+pub(in crate::codegen) fn layered_arrow_field(
+    name: &TokenStream,
+    leaf: &TokenStream,
+    nullability: &[bool],
+    fixed_lens: Option<&TokenStream>,
+    runtime: &TokenStream,
+) -> TokenStream {
+    fixed_lens.map_or_else(
+        || {
+            quote! {
+                #runtime::__private::list_arrow_field(#name, #leaf, &[#(#nullability),*])
+            }
+        },
+        |fixed_lens| {
+            quote! {
+                #runtime::__private::array_arrow_field(
+                    #name,
+                    #leaf,
+                    &[#(#nullability),*],
+                    #fixed_lens,
+                )
+            }
+        },
+    )
+}
@@ -1,5 +1,5 @@
 use super::{MacroConfig, encoder};
-use crate::ir::{StructIR, TerminalLeafRoute};
+use crate::ir::{ColumnIR, StructIR, TerminalLeafRoute};
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -7,6 +7,26 @@ fn needs_list_assembly(ir: &StructIR) -> bool {
     ir.columns.iter().any(|column| column.vec_depth() > 0)
 }
 
+fn needs_sorted_sets(ir: &StructIR) -> bool {
+    ir.columns.iter().any(ColumnIR::has_sorted_set)
+}
+
+fn needs_as_list(ir: &StructIR) -> bool {
+    ir.columns
+        .iter()
+        .any(|column| !column.as_list_types().is_empty())
+}
+
+fn needs_arrays(ir: &StructIR) -> bool {
+    ir.columns
+        .iter()
+        .any(|column| !column.fixed_lens().is_empty())
+}
+
+fn needs_leaf_casts(ir: &StructIR) -> bool {
+    ir.columns.iter().any(|column| column.leaf_cast().is_some())
+}
+
 fn needs_nested_validation(ir: &StructIR) -> bool {
     ir.columns
         .iter()
@@ -149,9 +169,337 @@ pub(in crate::codegen) fn generate_support(ir: &StructIR, config: &MacroConfig)
         TokenStream::new()
     };
 
+    let sorted_set_helpers = if needs_sorted_sets(ir) {
+        let sorted_set = encoder::idents::sorted_set();
+
+        // A `sort_keys` `HashSet` layer iterates this instead of the set. It
+        // yields the same `&T` items as `HashSet::iter`, in key order.
+        quote! {
+            struct #sorted_set<'a, T>(::std::vec::Vec<&'a T>);
+
+            impl<'a, T: ::core::cmp::Ord> #sorted_set<'a, T> {
+                fn new(set: impl ::core::iter::IntoIterator<Item = &'a T>) -> Self {
+                    let mut items: ::std::vec::Vec<&'a T> = set.into_iter().collect();
+                    items.sort_unstable();
+                    Self(items)
+                }
+
+                fn iter(&self) -> impl ::core::iter::Iterator<Item = &'a T> + '_ {
+                    self.0.iter().copied()
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let as_list_helpers = if needs_as_list(ir) {
+        let as_list = encoder::idents::as_list();
+
+        // An `as_list` layer iterates this instead of the collection, so any
+        // type whose reference is `IntoIterator` works. Exact-size iterators
+        // report their length through `size_hint`; others are counted.
+        quote! {
+            struct #as_list<'a, L: ?::core::marker::Sized>(&'a L);
+
+            impl<'a, L: ?::core::marker::Sized> #as_list<'a, L>
+            where
+                &'a L: ::core::iter::IntoIterator,
+            {
+                const fn new(list: &'a L) -> Self {
+                    Self(list)
+                }
+
+                fn iter(&self) -> <&'a L as ::core::iter::IntoIterator>::IntoIter {
+                    ::core::iter::IntoIterator::into_iter(self.0)
+                }
+
+                fn len(&self) -> usize {
+                    let iter = self.iter();
+                    match ::core::iter::Iterator::size_hint(&iter) {
+                        (lower, ::core::option::Option::Some(upper)) if lower == upper => lower,
+                        _ => ::core::iter::Iterator::count(iter),
+                    }
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let array_helpers = if needs_arrays(ir) {
+        let array_dtype = encoder::idents::array_dtype();
+        let fixed_size_arrays = encoder::idents::fixed_size_arrays();
+        let to_array_columns = encoder::idents::to_array_columns();
+
+        // Array layers are encoded as lists like any other collection, then
+        // rebuilt as fixed-width arrays once the column is assembled. `lens`
+        // holds the length of each outer list layer, outermost first, or
+        // `None` for a layer that stays a `List`. A layer without null rows
+        // reuses the list's values as is; null rows hold no values in the
+        // list, so a layer with nulls copies the runs of valid rows and pads
+        // each null row with `len` nulls. Polars' own list-to-array cast
+        // gathers every value one index at a time in that case.
+        quote! {
+            #[allow(non_snake_case)]
+            fn #array_dtype(
+                dtype: #pp::DataType,
+                lens: &[::core::option::Option<usize>],
+            ) -> #pp::DataType {
+                let ::core::option::Option::Some((len, rest)) = lens.split_first() else {
+                    return dtype;
+                };
+                let #pp::DataType::List(inner) = dtype else {
+                    return dtype;
+                };
+                let inner = ::std::boxed::Box::new(#array_dtype(*inner, rest));
+                match len {
+                    ::core::option::Option::Some(len) => #pp::DataType::Array(inner, *len),
+                    ::core::option::Option::None => #pp::DataType::List(inner),
+                }
+            }
+
+            #[allow(non_snake_case)]
+            fn #fixed_size_arrays(
+                array: &dyn #pa_root::array::Array,
+                arrow_dtype: &#pa_root::datatypes::ArrowDataType,
+                lens: &[::core::option::Option<usize>],
+            ) -> #pp::PolarsResult<#pp::ArrayRef> {
+                use #pa_root::array::Array as _;
+                use #pa_root::datatypes::ArrowDataType;
+
+                let ::core::option::Option::Some((len, rest)) = lens.split_first() else {
+                    return ::std::result::Result::Ok(array.to_boxed());
+                };
+                let (::core::option::Option::Some(list), ArrowDataType::LargeList(item) | ArrowDataType::FixedSizeList(item, _)) = (
+                    array.as_any().downcast_ref::<#pp::LargeListArray>(),
+                    arrow_dtype,
+                ) else {
+                    return ::std::result::Result::Err(#pp::polars_err!(
+                        ComputeError:
+                        "df-derive: array layer expected a list, got Arrow dtype {:?}",
+                        array.dtype(),
+                    ));
+                };
+                let values = #fixed_size_arrays(list.values().as_ref(), &item.dtype, rest)?;
+                let validity = list.validity().cloned();
+                let ::core::option::Option::Some(len) = *len else {
+                    return ::std::result::Result::Ok(::std::boxed::Box::new(
+                        #pp::LargeListArray::try_new(
+                            arrow_dtype.clone(),
+                            list.offsets().clone(),
+                            values,
+                            validity,
+                        )?,
+                    ));
+                };
+
+                let offsets = list.offsets().as_slice();
+                let offset = |row: usize| -> usize {
+                    usize::try_from(offsets[row]).expect("list offsets are non-negative")
+                };
+                for row in 0..list.len() {
+                    let width = offset(row + 1) - offset(row);
+                    if width != len && !list.is_null(row) {
+                        return ::std::result::Result::Err(#pp::polars_err!(
+                            ComputeError:
+                            "df-derive: array row {} has {} values, expected {}",
+                            row,
+                            width,
+                            len,
+                        ));
+                    }
+                }
+                let values = if list.null_count() == 0 {
+                    values.sliced(offset(0), list.len() * len)
+                } else {
+                    use #pa_root::array::builder::{ArrayBuilder as _, ShareStrategy, make_builder};
+
+                    let mut builder = make_builder(values.dtype());
+                    builder.reserve(list.len() * len);
+                    let mut run_start = offset(0);
+                    for row in 0..list.len() {
+                        if list.is_null(row) {
+                            let run_end = offset(row);
+                            builder.subslice_extend(
+                                values.as_ref(),
+                                run_start,
+                                run_end - run_start,
+                                ShareStrategy::Always,
+                            );
+                            builder.extend_nulls(len);
+                            run_start = offset(row + 1);
+                        }
+                    }
+                    let run_end = offset(list.len());
+                    builder.subslice_extend(
+                        values.as_ref(),
+                        run_start,
+                        run_end - run_start,
+                        ShareStrategy::Always,
+                    );
+                    builder.freeze()
+                };
+                ::std::result::Result::Ok(::std::boxed::Box::new(
+                    #pa_root::array::FixedSizeListArray::try_new(
+                        arrow_dtype.clone(),
+                        list.len(),
+                        values,
+                        validity,
+                    )?,
+                ))
+            }
+
+            #[allow(non_snake_case)]
+            fn #to_array_columns(
+                columns: &mut [#pp::Column],
+                lens: &[::core::option::Option<usize>],
+            ) -> #pp::PolarsResult<()> {
+                for column in columns {
+                    let dtype = #array_dtype(column.dtype().clone(), lens);
+                    let arrow_dtype = dtype.to_physical().to_arrow(#pp::CompatLevel::newest());
+                    let series = column.as_materialized_series();
+                    let chunks = series
+                        .chunks()
+                        .iter()
+                        .map(|chunk| #fixed_size_arrays(chunk.as_ref(), &arrow_dtype, lens))
+                        .collect::<#pp::PolarsResult<::std::vec::Vec<_>>>()?;
+                    // SAFETY: every chunk was built against `arrow_dtype`,
+                    // the physical Arrow dtype of `dtype`, and the
+                    // `try_new` constructors above reject values of any
+                    // other dtype.
+                    let series = unsafe {
+                        #pp::Series::from_chunks_and_dtype_unchecked(
+                            series.name().clone(),
+                            chunks,
+                            &dtype,
+                        )
+                    };
+                    *column = series.into();
+                }
+                ::std::result::Result::Ok(())
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let leaf_cast_helpers = if needs_leaf_casts(ir) {
+        let cast_leaf_dtype = encoder::idents::cast_leaf_dtype();
+        let leaf_values = encoder::idents::leaf_values();
+        let null_f32_overflow = encoder::idents::null_f32_overflow();
+        let cast_leaf_columns = encoder::idents::cast_leaf_columns();
+
+        // A `dtype` field's columns are built with the leaf's own dtype and
+        // then cast, before any array layer is applied, so only `List`
+        // layers sit above the leaf here. A non-strict cast turns values the
+        // target cannot hold into nulls, except that a finite float too
+        // large for `Float32` becomes infinite; fields that write overflow
+        // as null get those values nulled too. Otherwise a leaf that gained
+        // nulls, or a finite float that became infinite, fails the
+        // conversion.
+        quote! {
+            #[allow(non_snake_case)]
+            fn #cast_leaf_dtype(dtype: &#pp::DataType, leaf: &#pp::DataType) -> #pp::DataType {
+                match dtype {
+                    #pp::DataType::List(inner) => #pp::DataType::List(
+                        ::std::boxed::Box::new(#cast_leaf_dtype(inner, leaf)),
+                    ),
+                    _ => leaf.clone(),
+                }
+            }
+
+            #[allow(non_snake_case)]
+            fn #leaf_values(series: &#pp::Series) -> #pp::PolarsResult<#pp::Series> {
+                match series.dtype() {
+                    #pp::DataType::List(_) => #leaf_values(&series.list()?.get_inner()),
+                    _ => ::std::result::Result::Ok(series.clone()),
+                }
+            }
+
+            #[allow(non_snake_case)]
+            fn #null_f32_overflow(series: &#pp::Series) -> #pp::PolarsResult<#pp::Series> {
+                if let #pp::DataType::List(_) = series.dtype() {
+                    let list = series
+                        .list()?
+                        .apply_to_inner(&|inner| #null_f32_overflow(&inner))?;
+                    return ::std::result::Result::Ok(#pp::IntoSeries::into_series(list));
+                }
+                let wide = series.cast(&#pp::DataType::Float64)?;
+                let narrow = wide.cast(&#pp::DataType::Float32)?;
+                let values: #pp::Float32Chunked = narrow
+                    .f32()?
+                    .iter()
+                    .zip(wide.f64()?.iter())
+                    .map(|pair| match pair {
+                        (::core::option::Option::Some(narrow), ::core::option::Option::Some(wide))
+                            if wide.is_finite() && narrow.is_infinite() =>
+                        {
+                            ::core::option::Option::None
+                        }
+                        (narrow, _) => narrow,
+                    })
+                    .collect();
+                ::std::result::Result::Ok(#pp::IntoSeries::into_series(
+                    values.with_name(series.name().clone()),
+                ))
+            }
+
+            #[allow(non_snake_case)]
+            fn #cast_leaf_columns(
+                columns: &mut [#pp::Column],
+                leaf: &#pp::DataType,
+                overflow_to_null: bool,
+            ) -> #pp::PolarsResult<()> {
+                for column in columns {
+                    let cast = if overflow_to_null && leaf == &#pp::DataType::Float32 {
+                        #pp::Column::from(#null_f32_overflow(column.as_materialized_series())?)
+                    } else {
+                        column.cast(&#cast_leaf_dtype(column.dtype(), leaf))?
+                    };
+                    if !overflow_to_null {
+                        let before = #leaf_values(column.as_materialized_series())?;
+                        let after = #leaf_values(cast.as_materialized_series())?;
+                        let mut overflowed = after.null_count() > before.null_count();
+                        if !overflowed && leaf == &#pp::DataType::Float32 {
+                            let before = before.cast(&#pp::DataType::Float64)?;
+                            overflowed = before.f64()?.iter().zip(after.f32()?.iter()).any(
+                                |pair| ::core::matches!(
+                                    pair,
+                                    (::core::option::Option::Some(before), ::core::option::Option::Some(after))
+                                        if before.is_finite() && after.is_infinite()
+                                ),
+                            );
+                        }
+                        if overflowed {
+                            return ::std::result::Result::Err(#pp::polars_err!(
+                                ComputeError:
+                                "df-derive: column `{}` holds a value that does not fit {:?}",
+                                column.name(),
+                                leaf,
+                            ));
+                        }
+                    }
+                    *column = cast;
+                }
+                ::std::result::Result::Ok(())
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
     quote! {
         #list_assembly_helpers
 
         #nested_validation_helpers
+
+        #sorted_set_helpers
+
+        #as_list_helpers
+
+        #leaf_cast_helpers
+
+        #array_helpers
     }
 }
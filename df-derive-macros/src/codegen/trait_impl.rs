@@ -40,6 +40,44 @@ pub fn generate_trait_impl(ir: &StructIR, config: &super::MacroConfig) -> TokenS
         .map(|column| super::schema::build_schema_entries(column, config))
         .collect();
 
+    let arrow_schema_fn = config.builtin_runtime.as_ref().map(|runtime| {
+        let arrow_field_entries = ir
+            .columns
+            .iter()
+            .map(|column| super::schema::build_arrow_field_entries(column, config, runtime));
+        let column_metadata_entries: Vec<TokenStream> = ir
+            .columns
+            .iter()
+            .filter_map(|column| {
+                super::schema::build_column_metadata_entries(column, config, runtime)
+            })
+            .collect();
+        let column_metadata_body = if column_metadata_entries.is_empty() {
+            quote! { ::std::result::Result::Ok(::std::vec::Vec::new()) }
+        } else {
+            quote! {
+                let mut entries: ::std::vec::Vec<(::std::string::String, #runtime::ColumnMetadata)> = ::std::vec::Vec::new();
+                #(
+                    entries.extend(#column_metadata_entries);
+                )*
+                ::std::result::Result::Ok(entries)
+            }
+        };
+        quote! {
+            fn arrow_schema() -> #pp::PolarsResult<#pp::ArrowSchema> {
+                let mut fields: ::std::vec::Vec<#pp::ArrowField> = ::std::vec::Vec::new();
+                #(
+                    fields.extend(#arrow_field_entries);
+                )*
+                ::std::result::Result::Ok(#runtime::__private::arrow_schema_from_fields(fields))
+            }
+
+            fn column_metadata() -> #pp::PolarsResult<::std::vec::Vec<(::std::string::String, #runtime::ColumnMetadata)>> {
+                #column_metadata_body
+            }
+        }
+    });
+
     // `to_dataframe(&self)` delegates to the `Columnar::columnar_from_refs`
     // trait method with a single-element ref slice. There is no parallel
     // per-row codegen path — the trait method is the one source of truth
@@ -50,6 +88,8 @@ pub fn generate_trait_impl(ir: &StructIR, config: &super::MacroConfig) -> TokenS
     // never take a `&self` — they're shape-only operations. Routing
     // `empty_dataframe` through `columnar_from_refs(&[])` would recurse,
     // since that helper delegates to `empty_dataframe` on empty input.
+    // `arrow_schema` and `column_metadata` are only overridden on the
+    // built-in runtime; custom runtimes keep the three-method trait surface.
     quote! {
         #[automatically_derived]
         impl #impl_generics #to_df_trait for #struct_name #ty_generics #where_clause {
@@ -72,6 +112,8 @@ pub fn generate_trait_impl(ir: &StructIR, config: &super::MacroConfig) -> TokenS
                 )*
                 ::std::result::Result::Ok(fields)
             }
+
+            #arrow_schema_fn
         }
     }
 }
@@ -1,11 +1,11 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 
-use crate::ir::{DateTimeUnit, NumericKind, PrimitiveLeaf};
+use crate::ir::{CastDtype, ColumnIR, DateTimeUnit, NumericKind, PrimitiveLeaf};
 
 use crate::codegen::external_paths::ExternalPaths;
 
-use super::numeric::numeric_info_for;
+use super::numeric::{numeric_downcast_name, numeric_dtype_name, numeric_info_for};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(in crate::codegen) enum LogicalPrimitive {
@@ -21,6 +21,14 @@ pub(in crate::codegen) enum LogicalPrimitive {
     Decimal { precision: u8, scale: u8 },
 }
 
+const fn time_unit_suffix(unit: DateTimeUnit) -> &'static str {
+    match unit {
+        DateTimeUnit::Milliseconds => "ms",
+        DateTimeUnit::Microseconds => "us",
+        DateTimeUnit::Nanoseconds => "ns",
+    }
+}
+
 pub(in crate::codegen) fn time_unit_tokens(
     unit: DateTimeUnit,
     paths: &ExternalPaths,
@@ -59,6 +67,89 @@ impl LogicalPrimitive {
             }
         }
     }
+
+    /// Chunked array type a column of this dtype downcasts to, and the
+    /// `fn(&Column) -> PolarsResult<&Chunked>` that performs the downcast.
+    pub(in crate::codegen) fn chunked_downcast(
+        self,
+        paths: &ExternalPaths,
+        runtime: &TokenStream,
+    ) -> (TokenStream, TokenStream) {
+        let pp = paths.prelude();
+        let column = quote! { #pp::Column };
+        match self {
+            Self::Numeric(kind) => {
+                let method = format_ident!("{}", numeric_downcast_name(kind));
+                (
+                    numeric_info_for(kind, paths).chunked,
+                    quote! { #column::#method },
+                )
+            }
+            Self::String => (quote! { #pp::StringChunked }, quote! { #column::str }),
+            Self::Bool => (quote! { #pp::BooleanChunked }, quote! { #column::bool }),
+            Self::Binary => (quote! { #pp::BinaryChunked }, quote! { #column::binary }),
+            Self::DateTime(_) | Self::NaiveDateTime(_) => (
+                quote! { #pp::DatetimeChunked },
+                quote! { #column::datetime },
+            ),
+            Self::NaiveDate => (quote! { #pp::DateChunked }, quote! { #column::date }),
+            Self::NaiveTime => (
+                quote! { #pp::TimeChunked },
+                quote! { #runtime::__private::time_column },
+            ),
+            Self::Duration(_) => (
+                quote! { #pp::DurationChunked },
+                quote! { #column::duration },
+            ),
+            Self::Decimal { .. } => (quote! { #pp::DecimalChunked }, quote! { #column::decimal }),
+        }
+    }
+
+    /// Value type a borrowed row view reads for this dtype, plus the physical
+    /// chunked array it is read from when that differs from the
+    /// `chunked_downcast` type. Temporal and decimal columns expose their
+    /// physical integers.
+    pub(in crate::codegen) fn view_value(
+        self,
+        paths: &ExternalPaths,
+        lifetime: &TokenStream,
+    ) -> (TokenStream, Option<TokenStream>) {
+        let pp = paths.prelude();
+        match self {
+            Self::Numeric(kind) => (numeric_info_for(kind, paths).native, None),
+            Self::String => (quote! { &#lifetime str }, None),
+            Self::Bool => (quote! { bool }, None),
+            Self::Binary => (quote! { &#lifetime [u8] }, None),
+            Self::DateTime(_) | Self::NaiveDateTime(_) | Self::NaiveTime | Self::Duration(_) => {
+                (quote! { i64 }, Some(quote! { #pp::Int64Chunked }))
+            }
+            Self::NaiveDate => (quote! { i32 }, Some(quote! { #pp::Int32Chunked })),
+            Self::Decimal { .. } => (quote! { i128 }, Some(paths.int128_chunked())),
+        }
+    }
+
+    /// Whether the `view_value` type borrows from the frame.
+    pub(in crate::codegen) const fn view_borrows(self) -> bool {
+        matches!(self, Self::String | Self::Binary)
+    }
+
+    /// Compact, stable rendering of the dtype, e.g. `Datetime(us)` or
+    /// `Decimal(12, 4)`.
+    pub(in crate::codegen) fn name(self) -> String {
+        match self {
+            Self::Numeric(kind) => numeric_dtype_name(kind).to_owned(),
+            Self::String => "String".to_owned(),
+            Self::Bool => "Boolean".to_owned(),
+            Self::Binary => "Binary".to_owned(),
+            Self::DateTime(unit) | Self::NaiveDateTime(unit) => {
+                format!("Datetime({})", time_unit_suffix(unit))
+            }
+            Self::NaiveDate => "Date".to_owned(),
+            Self::NaiveTime => "Time".to_owned(),
+            Self::Duration(unit) => format!("Duration({})", time_unit_suffix(unit)),
+            Self::Decimal { precision, scale } => format!("Decimal({precision}, {scale})"),
+        }
+    }
 }
 
 impl PrimitiveLeaf<'_> {
@@ -83,3 +174,25 @@ impl PrimitiveLeaf<'_> {
         self.logical().dtype(paths)
     }
 }
+
+impl CastDtype {
+    pub(in crate::codegen) const fn logical(self) -> LogicalPrimitive {
+        match self {
+            Self::Numeric(kind) => LogicalPrimitive::Numeric(kind),
+            Self::Date => LogicalPrimitive::NaiveDate,
+            Self::Time => LogicalPrimitive::NaiveTime,
+            Self::Datetime(unit) => LogicalPrimitive::NaiveDateTime(unit),
+            Self::Duration(unit) => LogicalPrimitive::Duration(unit),
+            Self::Decimal { precision, scale } => LogicalPrimitive::Decimal { precision, scale },
+        }
+    }
+}
+
+impl ColumnIR {
+    /// Dtype of the values in a primitive column whose leaf is `leaf`: the
+    /// `dtype = "..."` target when the field casts, else the leaf's own.
+    pub(in crate::codegen) fn leaf_logical(&self, leaf: PrimitiveLeaf<'_>) -> LogicalPrimitive {
+        self.leaf_cast()
+            .map_or_else(|| leaf.logical(), |cast| cast.dtype.logical())
+    }
+}
@@ -17,31 +17,66 @@ pub(in crate::codegen) fn numeric_info_for(
     paths: &ExternalPaths,
 ) -> NumericInfo {
     let pp = paths.prelude();
-    let info = |native: TokenStream, variant: &str, widen_from: Option<TokenStream>| {
-        let chunked_ident = format_ident!("{}Chunked", variant);
-        let dtype_ident = format_ident!("{}", variant);
-        NumericInfo {
-            native,
-            dtype: quote! { #pp::DataType::#dtype_ident },
-            chunked: quote! { #pp::#chunked_ident },
-            widen_from,
-        }
+    let variant = numeric_dtype_name(kind);
+    let chunked_ident = format_ident!("{}Chunked", variant);
+    let dtype_ident = format_ident!("{}", variant);
+    let (native, widen_from) = match kind.storage_kind() {
+        StorageNumericKind::I8 => (quote! { i8 }, None),
+        StorageNumericKind::I16 => (quote! { i16 }, None),
+        StorageNumericKind::I32 => (quote! { i32 }, None),
+        StorageNumericKind::I64 => (quote! { i64 }, None),
+        StorageNumericKind::I128 => (quote! { i128 }, None),
+        StorageNumericKind::U8 => (quote! { u8 }, None),
+        StorageNumericKind::U16 => (quote! { u16 }, None),
+        StorageNumericKind::U32 => (quote! { u32 }, None),
+        StorageNumericKind::U64 => (quote! { u64 }, None),
+        StorageNumericKind::U128 => (quote! { u128 }, None),
+        StorageNumericKind::F32 => (quote! { f32 }, None),
+        StorageNumericKind::F64 => (quote! { f64 }, None),
+        StorageNumericKind::ISize => (quote! { i64 }, Some(quote! { isize })),
+        StorageNumericKind::USize => (quote! { u64 }, Some(quote! { usize })),
     };
+    NumericInfo {
+        native,
+        dtype: quote! { #pp::DataType::#dtype_ident },
+        chunked: quote! { #pp::#chunked_ident },
+        widen_from,
+    }
+}
+
+/// Polars `DataType` variant name for a numeric kind's storage lane.
+pub(in crate::codegen) const fn numeric_dtype_name(kind: NumericKind) -> &'static str {
+    match kind.storage_kind() {
+        StorageNumericKind::I8 => "Int8",
+        StorageNumericKind::I16 => "Int16",
+        StorageNumericKind::I32 => "Int32",
+        StorageNumericKind::I64 | StorageNumericKind::ISize => "Int64",
+        StorageNumericKind::I128 => "Int128",
+        StorageNumericKind::U8 => "UInt8",
+        StorageNumericKind::U16 => "UInt16",
+        StorageNumericKind::U32 => "UInt32",
+        StorageNumericKind::U64 | StorageNumericKind::USize => "UInt64",
+        StorageNumericKind::U128 => "UInt128",
+        StorageNumericKind::F32 => "Float32",
+        StorageNumericKind::F64 => "Float64",
+    }
+}
+
+/// Name of the `Column` downcast method for a numeric kind's storage lane.
+pub(in crate::codegen) const fn numeric_downcast_name(kind: NumericKind) -> &'static str {
     match kind.storage_kind() {
-        StorageNumericKind::I8 => info(quote! { i8 }, "Int8", None),
-        StorageNumericKind::I16 => info(quote! { i16 }, "Int16", None),
-        StorageNumericKind::I32 => info(quote! { i32 }, "Int32", None),
-        StorageNumericKind::I64 => info(quote! { i64 }, "Int64", None),
-        StorageNumericKind::I128 => info(quote! { i128 }, "Int128", None),
-        StorageNumericKind::U8 => info(quote! { u8 }, "UInt8", None),
-        StorageNumericKind::U16 => info(quote! { u16 }, "UInt16", None),
-        StorageNumericKind::U32 => info(quote! { u32 }, "UInt32", None),
-        StorageNumericKind::U64 => info(quote! { u64 }, "UInt64", None),
-        StorageNumericKind::U128 => info(quote! { u128 }, "UInt128", None),
-        StorageNumericKind::F32 => info(quote! { f32 }, "Float32", None),
-        StorageNumericKind::F64 => info(quote! { f64 }, "Float64", None),
-        StorageNumericKind::ISize => info(quote! { i64 }, "Int64", Some(quote! { isize })),
-        StorageNumericKind::USize => info(quote! { u64 }, "UInt64", Some(quote! { usize })),
+        StorageNumericKind::I8 => "i8",
+        StorageNumericKind::I16 => "i16",
+        StorageNumericKind::I32 => "i32",
+        StorageNumericKind::I64 | StorageNumericKind::ISize => "i64",
+        StorageNumericKind::I128 => "i128",
+        StorageNumericKind::U8 => "u8",
+        StorageNumericKind::U16 => "u16",
+        StorageNumericKind::U32 => "u32",
+        StorageNumericKind::U64 | StorageNumericKind::USize => "u64",
+        StorageNumericKind::U128 => "u128",
+        StorageNumericKind::F32 => "f32",
+        StorageNumericKind::F64 => "f64",
     }
 }
 
@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::ir::{DateTimeUnit, DurationSource};
+use crate::ir::{DateTimeUnit, DecimalSource, DurationSource};
 
 use crate::codegen::external_paths::ExternalPaths;
 
@@ -20,6 +20,7 @@ pub(in crate::codegen) enum ScalarTransform {
     Decimal {
         precision: u8,
         scale: u8,
+        source: DecimalSource,
     },
 }
 
@@ -31,7 +32,9 @@ impl ScalarTransform {
             Self::NaiveDate => LogicalPrimitive::NaiveDate,
             Self::NaiveTime => LogicalPrimitive::NaiveTime,
             Self::Duration { unit, source: _ } => LogicalPrimitive::Duration(unit),
-            Self::Decimal { precision, scale } => LogicalPrimitive::Decimal { precision, scale },
+            Self::Decimal {
+                precision, scale, ..
+            } => LogicalPrimitive::Decimal { precision, scale },
         }
     }
 
@@ -55,6 +58,14 @@ impl PrimitiveExprReceiver {
             Self::RefRef => quote! { *(#var) },
         }
     }
+
+    fn copy_value(self, var: &TokenStream) -> TokenStream {
+        match self {
+            Self::Place => quote! { (#var) },
+            Self::Ref => quote! { *(#var) },
+            Self::RefRef => quote! { **(#var) },
+        }
+    }
 }
 
 #[allow(clippy::too_many_lines)]
@@ -144,37 +155,89 @@ pub(in crate::codegen) fn map_primitive_expr(
                 },
             }
         }
-        ScalarTransform::Decimal { precision, scale } => {
-            // Decimal backends own rescaling through `Decimal128Encode`; the
-            // result must match Polars decimal mantissa semantics.
-            let target = u32::from(scale);
+        ScalarTransform::Decimal {
+            precision,
+            scale,
+            source,
+        } => {
+            let mantissa =
+                decimal_mantissa_expr(var, receiver, source, scale, decimal128_encode_trait, paths);
             let precision = u32::from(precision);
             let scale = u32::from(scale);
             let pp = paths.prelude();
-            let decimal_receiver = receiver.decimal_receiver(var);
             quote! {{
-                match #decimal128_encode_trait::try_to_i128_mantissa(#decimal_receiver, #target) {
-                    ::std::option::Option::Some(__df_m) => {
-                        if __df_m.unsigned_abs() >= 10u128.pow(#precision) {
-                            return ::std::result::Result::Err(
-                                #pp::polars_err!(ComputeError:
-                                    "df-derive: decimal mantissa {} exceeds declared precision {} for Decimal({}, {})",
-                                    __df_m,
-                                    #precision,
-                                    #precision,
-                                    #scale,
-                                )
-                            );
-                        }
-                        __df_m
-                    }
+                let __df_m: i128 = #mantissa;
+                if __df_m.unsigned_abs() >= 10u128.pow(#precision) {
+                    return ::std::result::Result::Err(
+                        #pp::polars_err!(ComputeError:
+                            "df-derive: decimal mantissa {} exceeds declared precision {} for Decimal({}, {})",
+                            __df_m,
+                            #precision,
+                            #precision,
+                            #scale,
+                        )
+                    );
+                }
+                __df_m
+            }}
+        }
+    }
+}
+
+/// `i128` mantissa of one decimal value at `scale`, before the precision
+/// check.
+fn decimal_mantissa_expr(
+    var: &TokenStream,
+    receiver: PrimitiveExprReceiver,
+    source: DecimalSource,
+    scale: u8,
+    decimal128_encode_trait: &syn::Path,
+    paths: &ExternalPaths,
+) -> TokenStream {
+    let pp = paths.prelude();
+    match source {
+        // Decimal backends own rescaling through `Decimal128Encode`; the
+        // result must match Polars decimal mantissa semantics.
+        DecimalSource::Encode => {
+            let scale = u32::from(scale);
+            let decimal_receiver = receiver.decimal_receiver(var);
+            quote! {
+                match #decimal128_encode_trait::try_to_i128_mantissa(#decimal_receiver, #scale) {
+                    ::std::option::Option::Some(__df_m) => __df_m,
                     ::std::option::Option::None => return ::std::result::Result::Err(
                         #pp::polars_err!(ComputeError:
                             "df-derive: decimal mantissa rescale to scale {} failed (overflow or precision loss)",
-                            #target
+                            #scale
                         )
                     ),
                 }
+            }
+        }
+        DecimalSource::Mantissa(kind) => {
+            let native = super::numeric_info_for(kind, paths).native;
+            let value = super::numeric_stored_value(kind, receiver.copy_value(var), &native);
+            quote! {
+                <i128 as ::core::convert::TryFrom<_>>::try_from(#value).map_err(|_| #pp::polars_err!(
+                    ComputeError: "df-derive: decimal mantissa {} does not fit i128", #value
+                ))?
+            }
+        }
+        // Scale in `f64`, then round half-to-even like `Decimal128Encode`
+        // impls do on scale-down.
+        DecimalSource::Float(_) => {
+            let exponent = i32::from(scale);
+            let value = receiver.copy_value(var);
+            quote! {{
+                let __df_value = ::core::convert::Into::<f64>::into(#value);
+                let __df_scaled = (__df_value * 10f64.powi(#exponent)).round_ties_even();
+                if __df_scaled.is_nan() || __df_scaled.abs() >= 1e38 {
+                    return ::std::result::Result::Err(#pp::polars_err!(
+                        ComputeError: "df-derive: float {} cannot be written as a decimal at scale {}",
+                        __df_value,
+                        #scale,
+                    ));
+                }
+                __df_scaled as i128
             }}
         }
     }
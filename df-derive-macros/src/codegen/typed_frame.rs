@@ -0,0 +1,95 @@
+use crate::codegen::encoder::idents;
+use crate::codegen::external_paths::ExternalPaths;
+use crate::ir::{ColumnIR, StructIR, TerminalLeafRoute};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::column_handles::accessor_ident;
+
+/// Return type and downcast for a primitive column's accessor; `Vec`
+/// columns of any depth are `ListChunked`, and columns whose outermost
+/// layer is an array are `ArrayChunked`.
+fn column_downcast(
+    column: &ColumnIR,
+    paths: &ExternalPaths,
+    runtime: &TokenStream,
+) -> Option<(TokenStream, TokenStream)> {
+    let TerminalLeafRoute::Primitive(leaf) = column.leaf_spec().route() else {
+        return None;
+    };
+    if column.vec_depth() > 0 {
+        let pp = paths.prelude();
+        if column.fixed_lens().first().is_some_and(Option::is_some) {
+            return Some((quote! { #pp::ArrayChunked }, quote! { #pp::Column::array }));
+        }
+        return Some((quote! { #pp::ListChunked }, quote! { #pp::Column::list }));
+    }
+    Some(column.leaf_logical(leaf).chunked_downcast(paths, runtime))
+}
+
+/// The generated accessor type behind `TypedDataFrame<T>` and its
+/// `TypedFrame` impl. Only primitive columns get accessors: nested and
+/// generic columns have no single chunked type known at expansion time. The
+/// unnamed column of a `transparent` struct has no accessor name either.
+/// Accessors are inherent methods, so one named like a `DataFrame` method
+/// (`height`, `columns`, ...) shadows the method reached through `Deref`.
+pub fn generate_typed_frame(ir: &StructIR, config: &super::MacroConfig) -> TokenStream {
+    let Some(runtime) = &config.builtin_runtime else {
+        return TokenStream::new();
+    };
+    let paths = &config.external_paths;
+    let pp = paths.prelude();
+    let struct_name = &ir.name;
+    let (impl_generics, ty_generics, where_clause) = ir.generics.split_for_impl();
+    let frame = idents::typed_frame();
+
+    let mut accessors = Vec::new();
+    for column in &ir.columns {
+        let name = column.name();
+        if name.is_empty() {
+            continue;
+        }
+        let Some((chunked, downcast)) = column_downcast(column, paths, runtime) else {
+            continue;
+        };
+        let ident = accessor_ident(name);
+        accessors.push(quote! {
+            pub fn #ident(&self) -> &#chunked {
+                #runtime::__private::typed_column(&self.frame, #name, #downcast)
+            }
+        });
+    }
+
+    quote! {
+        pub struct #frame {
+            frame: #pp::DataFrame,
+        }
+
+        impl ::core::ops::Deref for #frame {
+            type Target = #pp::DataFrame;
+
+            fn deref(&self) -> &Self::Target {
+                &self.frame
+            }
+        }
+
+        impl #runtime::__private::FrameAccessors for #frame {
+            fn from_validated(frame: #pp::DataFrame) -> Self {
+                Self { frame }
+            }
+
+            fn into_dataframe(self) -> #pp::DataFrame {
+                self.frame
+            }
+        }
+
+        impl #frame {
+            #(#accessors)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #runtime::typed::TypedFrame for #struct_name #ty_generics #where_clause {
+            type Frame = #frame;
+        }
+    }
+}
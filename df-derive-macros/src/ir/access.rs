@@ -6,6 +6,14 @@
 pub enum AccessStep {
     Option,
     SmartPtr,
+    /// Collect the `HashSet` reached so far and sort it, so the list layer
+    /// that follows iterates in key order. Always the last step before its
+    /// list layer.
+    SortedSet,
+    /// Wrap the `as_list` collection reached so far in an adapter that
+    /// iterates it through `IntoIterator` and counts it. Always the last step
+    /// before its list layer.
+    AsList,
 }
 
 /// Transparent access steps between two semantic wrapper boundaries: from the
@@ -36,6 +44,29 @@ impl AccessChain {
         self.steps == [AccessStep::Option]
     }
 
+    pub fn is_sorted_set(&self) -> bool {
+        self.steps.last() == Some(&AccessStep::SortedSet)
+    }
+
+    /// Whether the list layer this chain leads to is iterated through a
+    /// generated adapter rather than the collection's own `iter()`.
+    pub fn ends_in_adapter(&self) -> bool {
+        matches!(
+            self.steps.last(),
+            Some(AccessStep::SortedSet | AccessStep::AsList)
+        )
+    }
+
+    /// The same chain without a trailing `SortedSet` step, for walks that
+    /// only count elements.
+    pub fn unsorted(&self) -> Self {
+        let mut steps = self.steps.clone();
+        if steps.last() == Some(&AccessStep::SortedSet) {
+            steps.pop();
+        }
+        Self { steps }
+    }
+
     pub fn is_only_options(&self) -> bool {
         self.steps
             .iter()
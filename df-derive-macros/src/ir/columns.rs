@@ -1,6 +1,8 @@
 use syn::Ident;
 
-use super::{AccessChain, NonEmpty, TerminalLeafSpec, VecLayers, WrapperShape};
+use super::{
+    AccessChain, AsListTypes, LeafCast, NonEmpty, TerminalLeafSpec, VecLayers, WrapperShape,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ColumnIR {
@@ -79,6 +81,26 @@ impl ColumnIR {
         self.common().name()
     }
 
+    /// Attach the source field's metadata. Tuple projections share the
+    /// metadata of the field they were projected from.
+    pub(crate) fn set_metadata(&mut self, metadata: Vec<(String, String)>) {
+        self.common_mut().metadata = metadata;
+    }
+
+    pub fn metadata(&self) -> &[(String, String)] {
+        &self.common().metadata
+    }
+
+    /// Attach the source field's `dtype` cast. Tuple projections share the
+    /// cast of the field they were projected from.
+    pub(crate) const fn set_cast(&mut self, cast: LeafCast) {
+        self.common_mut().cast = Some(cast);
+    }
+
+    pub const fn leaf_cast(&self) -> Option<&LeafCast> {
+        self.common().cast.as_ref()
+    }
+
     pub const fn leaf_spec(&self) -> &TerminalLeafSpec {
         self.common().leaf_spec()
     }
@@ -92,6 +114,61 @@ impl ColumnIR {
         }
     }
 
+    /// Whether any list layer above this column's leaf is a sorted `HashSet`.
+    pub fn has_sorted_set(&self) -> bool {
+        match self {
+            Self::Field(column) => column.wrapper_shape.has_sorted_set(),
+            Self::TupleStatic(column) => column.wrapper_shape.has_sorted_set(),
+            Self::TupleParentOption(column) => column.wrapper_shape.has_sorted_set(),
+            Self::TupleParentVec(column) => column.wrapper_shape.has_sorted_set(),
+        }
+    }
+
+    /// Length of each list layer that is a fixed-width `[T; N]` array,
+    /// outermost first; `None` for variable-length layers. Empty when no
+    /// layer is fixed.
+    pub fn fixed_lens(&self) -> Vec<Option<&syn::Expr>> {
+        let lens = match self {
+            Self::Field(column) => column.wrapper_shape.fixed_lens(),
+            Self::TupleStatic(column) => column.wrapper_shape.fixed_lens(),
+            Self::TupleParentOption(column) => column.wrapper_shape.fixed_lens(),
+            Self::TupleParentVec(column) => column.wrapper_shape.fixed_lens(),
+        };
+        if lens.iter().any(Option::is_some) {
+            lens
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Types of the column's `as_list` layers.
+    pub fn as_list_types(&self) -> Vec<&AsListTypes> {
+        match self {
+            Self::Field(column) => column.wrapper_shape.as_list_types(),
+            Self::TupleStatic(column) => column.wrapper_shape.as_list_types(),
+            Self::TupleParentOption(column) => column.wrapper_shape.as_list_types(),
+            Self::TupleParentVec(column) => column.wrapper_shape.as_list_types(),
+        }
+    }
+
+    /// Whether each layer may hold nulls, outermost first and the leaf
+    /// last. A cast that writes overflowing values as nulls makes the leaf
+    /// nullable.
+    pub fn nullability(&self) -> Vec<bool> {
+        let mut nullability = match self {
+            Self::Field(column) => column.wrapper_shape.nullability(),
+            Self::TupleStatic(column) => column.wrapper_shape.nullability(),
+            Self::TupleParentOption(column) => column.wrapper_shape.nullability(),
+            Self::TupleParentVec(column) => column.wrapper_shape.nullability(),
+        };
+        if self.leaf_cast().is_some_and(|cast| cast.overflow_to_null)
+            && let Some(leaf) = nullability.last_mut()
+        {
+            *leaf = true;
+        }
+        nullability
+    }
+
     const fn common(&self) -> &ColumnCommon {
         match self {
             Self::Field(column) => &column.common,
@@ -100,17 +177,33 @@ impl ColumnIR {
             Self::TupleParentVec(column) => &column.common,
         }
     }
+
+    const fn common_mut(&mut self) -> &mut ColumnCommon {
+        match self {
+            Self::Field(column) => &mut column.common,
+            Self::TupleStatic(column) => &mut column.common,
+            Self::TupleParentOption(column) => &mut column.common,
+            Self::TupleParentVec(column) => &mut column.common,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ColumnCommon {
     name: String,
     leaf_spec: TerminalLeafSpec,
+    metadata: Vec<(String, String)>,
+    cast: Option<LeafCast>,
 }
 
 impl ColumnCommon {
     const fn new(name: String, leaf_spec: TerminalLeafSpec) -> Self {
-        Self { name, leaf_spec }
+        Self {
+            name,
+            leaf_spec,
+            metadata: Vec::new(),
+            cast: None,
+        }
     }
 
     pub fn name(&self) -> &str {
@@ -73,6 +73,13 @@ impl NumericKind {
         }
     }
 
+    pub const fn is_float(self) -> bool {
+        matches!(
+            self.storage_kind(),
+            StorageNumericKind::F32 | StorageNumericKind::F64
+        )
+    }
+
     pub const fn is_widened(self) -> bool {
         matches!(
             self.storage_kind(),
@@ -123,6 +130,8 @@ pub enum StringyBase {
     String,
     BorrowedStr,
     CowStr,
+    /// `Box<str>` / `Rc<str>` / `Arc<str>`, dereferenced to `str`.
+    Str,
     Struct(Type),
     Generic(Ident),
 }
@@ -145,6 +154,85 @@ pub enum DecimalBackend {
     RuntimeKnown,
     Generic(Ident),
     Struct(Type),
+    /// `decimal(..., mantissa)` on an integer: the value is the mantissa at
+    /// the declared scale.
+    Mantissa(NumericKind),
+    /// `decimal(...)` on a float: the value is rounded to the declared scale.
+    Float(NumericKind),
+}
+
+impl DecimalBackend {
+    pub const fn source(&self) -> DecimalSource {
+        match self {
+            Self::RuntimeKnown | Self::Generic(_) | Self::Struct(_) => DecimalSource::Encode,
+            Self::Mantissa(kind) => DecimalSource::Mantissa(*kind),
+            Self::Float(kind) => DecimalSource::Float(*kind),
+        }
+    }
+}
+
+/// How a decimal leaf's values become `i128` mantissas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecimalSource {
+    /// Rescaled by the backend's `Decimal128Encode` impl.
+    Encode,
+    /// Integer value used as the mantissa unchanged.
+    Mantissa(NumericKind),
+    /// Float value scaled and rounded half-to-even.
+    Float(NumericKind),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapKind {
+    Hash,
+    BTree,
+}
+
+/// `HashMap` / `BTreeMap` leaf with its entry order and column layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapLeaf {
+    /// Map type as written at the field use site.
+    pub ty: Type,
+    pub kind: MapKind,
+    /// `#[df_derive(sort_keys)]`: write entries in key order.
+    pub sort_keys: bool,
+    /// `#[df_derive(split_map)]`: write `keys` / `values` list columns
+    /// instead of one `List(Struct{key, value})` column.
+    pub split: bool,
+}
+
+/// Output dtype chosen by `#[df_derive(dtype = "...")]`. Numeric targets
+/// use the storage kinds `I8`..`U128`, `F32`, and `F64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastDtype {
+    Numeric(NumericKind),
+    Date,
+    Time,
+    Datetime(DateTimeUnit),
+    Duration(DateTimeUnit),
+    Decimal { precision: u8, scale: u8 },
+}
+
+/// Cast applied to a column's leaf values after they are encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeafCast {
+    pub dtype: CastDtype,
+    /// `on_overflow = "null"`: values the target cannot hold become nulls
+    /// instead of failing the conversion.
+    pub overflow_to_null: bool,
+}
+
+/// Leaf converted through `#[df_derive(with = "path")]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithLeaf {
+    /// Type the function reads, after wrapper peeling.
+    pub source: Type,
+    pub func: syn::ExprPath,
+    /// Field the leaf belongs to, which names its generated marker type.
+    pub field: Ident,
+    /// The deriving struct's generics, carried by the marker type because
+    /// `source` may use them.
+    pub generics: syn::Generics,
 }
 
 /// Per-leaf semantic shape after type analysis and field overrides have been
@@ -172,6 +260,11 @@ pub enum LeafSpec {
     Binary,
     Struct(Type),
     Generic(Ident),
+    Map(MapLeaf),
+    With(Box<WithLeaf>),
+    /// `#[df_derive(leaf)]`: a type written as one column by its `DfLeaf`
+    /// impl.
+    DfLeaf(Type),
     Tuple(Vec<TupleElement>),
 }
 
@@ -193,12 +286,28 @@ pub enum PrimitiveLeaf<'a> {
     Decimal {
         precision: u8,
         scale: u8,
+        source: DecimalSource,
     },
     AsString,
     AsStr(&'a StringyBase),
 }
 
 impl PrimitiveLeaf<'_> {
+    /// Whether `#[df_derive(dtype = "...")]` may cast this leaf: numeric,
+    /// temporal, and decimal values.
+    pub const fn is_castable(self) -> bool {
+        matches!(
+            self,
+            Self::Numeric(_)
+                | Self::DateTime(_)
+                | Self::NaiveDateTime(_)
+                | Self::NaiveDate
+                | Self::NaiveTime
+                | Self::Duration { .. }
+                | Self::Decimal { .. }
+        )
+    }
+
     pub const fn is_copy(self) -> bool {
         matches!(
             self,
@@ -217,6 +326,9 @@ impl PrimitiveLeaf<'_> {
 pub enum NestedLeaf<'a> {
     Struct(&'a Type),
     Generic(&'a Ident),
+    Map(&'a MapLeaf),
+    With(&'a WithLeaf),
+    DfLeaf(&'a Type),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -268,10 +380,13 @@ impl LeafSpec {
                 }))
             }
             Self::Decimal {
-                precision, scale, ..
+                precision,
+                scale,
+                backend,
             } => Some(TerminalLeafRoute::Primitive(PrimitiveLeaf::Decimal {
                 precision: *precision,
                 scale: *scale,
+                source: backend.source(),
             })),
             Self::AsString(_) => Some(TerminalLeafRoute::Primitive(PrimitiveLeaf::AsString)),
             Self::AsStr(stringy) => {
@@ -280,6 +395,9 @@ impl LeafSpec {
             Self::Binary => Some(TerminalLeafRoute::Primitive(PrimitiveLeaf::Binary)),
             Self::Struct(ty) => Some(TerminalLeafRoute::Nested(NestedLeaf::Struct(ty))),
             Self::Generic(ident) => Some(TerminalLeafRoute::Nested(NestedLeaf::Generic(ident))),
+            Self::Map(map) => Some(TerminalLeafRoute::Nested(NestedLeaf::Map(map))),
+            Self::With(with) => Some(TerminalLeafRoute::Nested(NestedLeaf::With(with))),
+            Self::DfLeaf(ty) => Some(TerminalLeafRoute::Nested(NestedLeaf::DfLeaf(ty))),
             Self::Tuple(_) => None,
         }
     }
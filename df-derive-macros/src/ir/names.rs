@@ -2,3 +2,13 @@ pub fn column_name_for_ident(ident: &syn::Ident) -> String {
     let name = ident.to_string();
     name.strip_prefix("r#").unwrap_or(&name).to_owned()
 }
+
+/// `prefix.name`, or `name` alone under the empty prefix of a transparent
+/// struct's field.
+pub fn nested_column_name(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
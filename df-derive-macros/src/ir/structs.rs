@@ -1,9 +1,10 @@
 use syn::Ident;
 
-use super::{ColumnIR, LeafSpec, WrapperShape};
+use super::{ColumnIR, LeafCast, LeafSpec, WrapperShape};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StructIR {
+    pub vis: syn::Visibility,
     pub name: Ident,
     pub generics: syn::Generics,
     pub columns: Vec<ColumnIR>,
@@ -16,4 +17,9 @@ pub struct FieldIR {
     pub leaf_spec: LeafSpec,
     pub wrapper_shape: WrapperShape,
     pub outer_smart_ptr_depth: usize,
+    /// Column metadata from the field's doc comment and `description`,
+    /// `unit`, and `metadata(...)` attributes.
+    pub metadata: Vec<(String, String)>,
+    /// `dtype = "..."` cast of every column the field projects to.
+    pub cast: Option<LeafCast>,
 }
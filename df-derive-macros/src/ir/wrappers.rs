@@ -27,11 +27,24 @@ impl LeafShape {
     }
 }
 
+/// Collection and element type of an `as_list` layer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsListTypes {
+    pub collection: syn::Type,
+    pub elem: syn::Type,
+}
+
 /// Polars folds consecutive `Option`s at a list level into one validity bit.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VecLayerSpec {
     pub option_layers_above: usize,
     pub access: AccessChain,
+    /// Length of a `[T; N]` layer, which is written as a fixed-width
+    /// `Array` instead of a `List`.
+    pub fixed_len: Option<Box<syn::Expr>>,
+    /// Types of an `as_list` layer, which the impl bounds as iterable by
+    /// reference into its elements.
+    pub as_list: Option<Box<AsListTypes>>,
 }
 
 impl VecLayerSpec {
@@ -56,9 +69,45 @@ impl VecLayers {
         self.layers.iter().any(VecLayerSpec::has_outer_validity)
     }
 
+    pub fn has_sorted_set(&self) -> bool {
+        self.layers.iter().any(|layer| layer.access.is_sorted_set())
+    }
+
+    pub fn has_adapter(&self) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.access.ends_in_adapter())
+    }
+
+    pub fn as_list_types(&self) -> Vec<&AsListTypes> {
+        self.layers
+            .iter()
+            .filter_map(|layer| layer.as_list.as_deref())
+            .collect()
+    }
+
+    /// Length of each fixed-width layer, outermost first; `None` for
+    /// variable-length layers.
+    pub fn fixed_lens(&self) -> Vec<Option<&syn::Expr>> {
+        self.layers
+            .iter()
+            .map(|layer| layer.fixed_len.as_deref())
+            .collect()
+    }
+
     pub const fn has_inner_option(&self) -> bool {
         self.inner_option_layers > 0
     }
+
+    /// Validity of each list layer, outermost first, followed by the
+    /// validity of the innermost elements.
+    pub fn nullability(&self) -> Vec<bool> {
+        self.layers
+            .iter()
+            .map(VecLayerSpec::has_outer_validity)
+            .chain(std::iter::once(self.has_inner_option()))
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -74,4 +123,34 @@ impl WrapperShape {
             Self::Vec(v) => v.depth(),
         }
     }
+
+    pub fn has_sorted_set(&self) -> bool {
+        match self {
+            Self::Leaf(_) => false,
+            Self::Vec(v) => v.has_sorted_set(),
+        }
+    }
+
+    pub fn as_list_types(&self) -> Vec<&AsListTypes> {
+        match self {
+            Self::Leaf(_) => Vec::new(),
+            Self::Vec(v) => v.as_list_types(),
+        }
+    }
+
+    pub fn fixed_lens(&self) -> Vec<Option<&syn::Expr>> {
+        match self {
+            Self::Leaf(_) => Vec::new(),
+            Self::Vec(v) => v.fixed_lens(),
+        }
+    }
+
+    /// Whether the column itself can be null, followed by the element
+    /// nullability of each list layer, outermost first.
+    pub fn nullability(&self) -> Vec<bool> {
+        match self {
+            Self::Leaf(shape) => vec![shape.option_layers() > 0],
+            Self::Vec(v) => v.nullability(),
+        }
+    }
 }
@@ -40,6 +40,21 @@ use syn::{DeriveInput, parse_macro_input};
 ///   The direct slice method avoids the trait default's temporary ref-vector
 ///   allocation on top-level batch conversion; the borrowed method remains
 ///   available for nested and generic composition.
+/// - When targeting the built-in `df_derive`/`df_derive_core` runtime, an
+///   override of `fn arrow_schema() -> PolarsResult<ArrowSchema>` whose field
+///   `nullable` flags follow each field's `Option` wrappers, including list
+///   elements and flattened nested fields, and of
+///   `fn column_metadata() -> PolarsResult<Vec<(String, ColumnMetadata)>>`
+///   carrying each field's doc comment and metadata attributes.
+/// - `pub const COLUMN_NAMES: &[&str]` on `T` when every column name is known at
+///   expansion time (no nested-struct or generic fields), and on the built-in
+///   runtime an inherent `T::cols()` returning typed column handles: one accessor per
+///   column returning a `ColumnRef`, with nested fields returning the nested type's
+///   handles (`Trade::cols().quotes().close()`).
+/// - On the built-in runtime, an `impl TypedFrame for T` whose accessor type gives
+///   `TypedDataFrame<T>` one method per primitive column returning its chunked array
+///   (`.price() -> &Float64Chunked`, `Vec` fields as `&ListChunked`, arrays as
+///   `&ArrayChunked`).
 ///
 /// Supported shapes and types:
 ///
@@ -47,8 +62,14 @@ use syn::{DeriveInput, parse_macro_input};
 /// - Nested structs are flattened using dot notation (e.g., `outer.inner`)
 /// - Wrappers `Option<T>` and `Vec<T>` in any nesting order, with `Vec<Struct>` producing multiple
 ///   list columns with a `vec_field.subfield` prefix
-/// - Primitive types: `String`, `bool`, integer types including `i128`/`u128`,
-///   `std::num::NonZero*` integer types, `f32`, `f64`
+/// - `HashSet<T>`, `BTreeSet<T>`, `VecDeque<T>`, and `LinkedList<T>` anywhere a `Vec<T>` may
+///   appear; each is a list layer written in the collection's iteration order
+/// - `[T; N]` (including a const generic `N`) anywhere a `Vec<T>` may appear, as a fixed-width
+///   `Array(dtype, N)` layer
+/// - `Box<[T]>`, `Rc<[T]>`, `Arc<[T]>`, `&[T]`, and `Cow<'_, [T]>` for non-`u8` `T` anywhere a
+///   `Vec<T>` may appear, with the same columns as `Vec<T>`
+/// - Primitive types: `String` (or `&str`, `Box<str>`, `Rc<str>`, `Arc<str>`), `bool`, integer
+///   types including `i128`/`u128`, `std::num::NonZero*` integer types, `f32`, `f64`
 /// - `chrono::DateTime<Tz>` and `chrono::NaiveDateTime` (default:
 ///   `Datetime(Milliseconds, None)`; override with `#[df_derive(time_unit = "ms"|"us"|"ns")]`).
 ///   `DateTime<Tz>` stores the UTC instant; use `as_string` when the textual timezone/offset
@@ -62,6 +83,8 @@ use syn::{DeriveInput, parse_macro_input};
 ///   (default: `Decimal(38, 10)`; override with
 ///   `#[df_derive(decimal(precision = N, scale = N))]`). Custom backends opt in
 ///   with explicit `decimal(...)` and a `Decimal128Encode` impl.
+/// - `HashMap<K, V>` and `BTreeMap<K, V>` (built-in runtime only) → one
+///   `List(Struct{key, value})` column; keys and values are any generic payload type.
 ///
 /// Attributes:
 ///
@@ -77,6 +100,20 @@ use syn::{DeriveInput, parse_macro_input};
 ///   `df_derive_core::dataframe::ToDataFrame` keep using the default runtime's hidden
 ///   dependency re-exports and cannot be paired with a custom `columnar` path;
 ///   other explicit trait paths are treated as custom runtimes.
+/// - Container-level: `#[df_derive(schema_lock = "schemas/row.json")]` to compare the
+///   derived column names, dtypes, and nullability against a lock file (relative to
+///   `CARGO_MANIFEST_DIR`) at expansion time, failing with a diff on mismatch. Building
+///   with `DF_DERIVE_UPDATE_SCHEMAS=1` writes the file instead. Nested and generic fields
+///   are locked by type name and list depth; lock their columns on the nested type.
+/// - Container-level: `#[df_derive(views)]` (built-in runtime, non-generic structs) to
+///   generate a `{T}View<'df>` struct with one public field per column and
+///   `T::iter_views(&df)`, which checks the frame once and yields borrowed row views.
+///   Primitive, `Option`, single-level `Vec`, and non-optional nested struct fields
+///   are supported; nested structs must also use `views`.
+/// - Container-level: `#[df_derive(transparent)]` on a struct with exactly one non-`skip`
+///   field to convert it as that field's type. The field's columns drop its name, so a
+///   `price: Price` field of `struct Price(f64)` is a `price` column rather than
+///   `price.field_0`. Not combinable with `views`.
 /// - Field-level: `#[df_derive(skip)]` to omit a field from generated schema
 ///   and `DataFrame` output. Skipped fields are not type-analyzed, so this can
 ///   be used for caches, handles, source metadata, or other helper values that
@@ -88,18 +125,21 @@ use syn::{DeriveInput, parse_macro_input};
 /// - Field-level: `#[df_derive(as_str)]` to borrow `&str` via `AsRef<str>` for the duration of the
 ///   conversion. Same column type as `as_string` but avoids `Display` formatting and the
 ///   intermediate scratch buffer. The two attributes are mutually exclusive on a given field.
-/// - Field-level: `#[df_derive(as_binary)]` to route a `Vec<u8>`, `&[u8]`, or
-///   `Cow<'_, [u8]>` field through a Polars `Binary` column instead of the default
-///   `List(UInt8)` for `Vec<u8>`. Accepted shapes:
+/// - Field-level: `#[df_derive(as_binary)]` to route a `Vec<u8>`, `[u8; N]`, `&[u8]`,
+///   `Cow<'_, [u8]>`, or `Box`/`Rc`/`Arc<[u8]>` field through a Polars `Binary` column instead
+///   of the default `List(UInt8)` for `Vec<u8>`. Accepted shapes:
 ///   `Vec<u8>`, `Option<Vec<u8>>`, `Vec<Vec<u8>>`, `Vec<Option<Vec<u8>>>`,
-///   `Option<Vec<Vec<u8>>>`, and the same scalar/list shapes over `&[u8]` and `Cow<'_, [u8]>` —
+///   `Option<Vec<Vec<u8>>>`, and the same scalar/list shapes over `[u8; N]`, `&[u8]`, and
+///   `Cow<'_, [u8]>` —
 ///   bare `u8`, `Option<u8>`, `Vec<Option<u8>>` (`BinaryView` cannot carry per-byte nulls), and
 ///   non-`u8` leaves are rejected at parse time. Mutually exclusive with `as_str`,
 ///   `as_string`, `decimal(...)`, and `time_unit = "..."`.
 /// - Field-level: `#[df_derive(decimal(precision = N, scale = N))]` to choose the
 ///   `Decimal(precision, scale)` dtype for a built-in decimal path or to explicitly opt a
 ///   custom/generic decimal backend into `Decimal128Encode` dispatch. Polars requires
-///   `1 <= precision <= 38`; `scale` may not exceed `precision`.
+///   `1 <= precision <= 38`; `scale` may not exceed `precision`. On `f32`/`f64` fields the
+///   value is scaled by `10^scale` in `f64` and rounded half-to-even. Integer fields need
+///   `decimal(precision = N, scale = N, mantissa)` and are written as the mantissa at `scale`.
 /// - Field-level: `#[df_derive(time_unit = "ms"|"us"|"ns")]` to choose the
 ///   `Datetime(unit, None)` / `Duration(unit)` dtype for a temporal field. Accepted bases are
 ///   `chrono::DateTime<Tz>`, `chrono::NaiveDateTime`, `std::time::Duration`,
@@ -111,6 +151,34 @@ use syn::{DeriveInput, parse_macro_input};
 ///   fallible (the value type is `u128`). All failures surface as `PolarsError::ComputeError`
 ///   rather than silently corrupting data. `time_unit` is rejected on `chrono::NaiveDate` and
 ///   `chrono::NaiveTime` (both have fixed encodings).
+/// - Field-level: `#[df_derive(sort_keys)]` to sort a `HashMap` field's entries, or the
+///   elements of every `HashSet` layer, by key (`BTreeMap`/`BTreeSet` are already in key order,
+///   so it is rejected there), and `#[df_derive(split_map)]` to emit a map field as parallel
+///   `field.keys` / `field.values` list columns. `split_map` is rejected on non-map fields.
+/// - Field-level: `#[df_derive(as_list)]` to read a third-party collection such as
+///   `SmallVec<[T; N]>` or `im::Vector<T>` as a list layer. The element type is the
+///   collection's first generic argument (or that argument's array element), and
+///   `&Collection` must implement `IntoIterator<Item = &T>`.
+/// - Field-level: `#[df_derive(with = "path")]` to convert each value through a function
+///   `fn(&T) -> U`, where `U` is any `ToDataFrame + Columnar` type (a primitive leaf,
+///   `Option<U>` for nullable results, or a derived struct). The function is applied per
+///   element after `Option`, list, and pointer wrappers are peeled, composes with `as_list`
+///   and `dtype`, and is mutually exclusive with the other conversion attributes. Requires
+///   the built-in runtime.
+/// - Field-level: `#[df_derive(leaf)]` to write a type implementing `DfLeaf` as one column
+///   of its `DfLeaf::dtype()`, encoding each value with `DfLeaf::encode`. It keeps the field's
+///   `Option`, list, and pointer wrappers, composes with `as_list`, and is mutually exclusive
+///   with the other conversion attributes. Requires the built-in runtime.
+/// - Field-level: `#[df_derive(dtype = "Int64")]` to cast a numeric, temporal, or decimal
+///   field's leaf values to another dtype (`"Int8"`..`"UInt128"`, `"Float32"`, `"Float64"`,
+///   `"Date"`, `"Time"`, `"Datetime(unit)"`, `"Duration(unit)"`, `"Decimal(p, s)"`) inside
+///   every wrapper layer. Values the target cannot hold fail the conversion, or become nulls
+///   with `on_overflow = "null"`; casts that only lose precision, such as float to integer,
+///   succeed. On a `with` field it casts the function's single-column result.
+/// - Field-level: `#[df_derive(description = "...")]`, `#[df_derive(unit = "...")]`, and
+///   `#[df_derive(metadata(key = "value", ...))]` to attach column metadata. A field's `///`
+///   doc comment supplies `description` unless one is given explicitly. Metadata keys may
+///   be combined with any other field attribute; declaring the same key twice is rejected.
 /// - The `decimal(...)` attribute can only be applied to decimal backend candidates: type paths
 ///   named `Decimal`, custom struct types, or generic type parameters that implement
 ///   `Decimal128Encode`. It cannot be combined with `as_str`/`as_string`/`time_unit` on the same
@@ -143,7 +211,7 @@ pub fn to_dataframe_derive(input: TokenStream) -> TokenStream {
     };
 
     // Build the intermediate representation
-    let ir = match parser::parse_to_ir(&ast) {
+    let ir = match parser::parse_to_ir(&ast, config.transparent) {
         Ok(ir) => ir,
         Err(e) => return e.to_compile_error().into(),
     };
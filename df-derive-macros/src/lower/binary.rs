@@ -1,5 +1,5 @@
 use crate::ir::{LeafSpec, NumericKind};
-use crate::type_analysis::{AnalyzedBase, RawWrapper};
+use crate::type_analysis::{AnalyzedBase, ListCollection, RawWrapper};
 
 use super::errors;
 
@@ -12,12 +12,6 @@ pub(super) fn parse_as_binary_shape(
     if matches!(base, AnalyzedBase::CowBytes | AnalyzedBase::BorrowedBytes) {
         return Ok((LeafSpec::Binary, wrappers.to_vec()));
     }
-    if matches!(base, AnalyzedBase::BorrowedSlice) {
-        return Err(errors::binary_borrowed_slice(field, field_display_name));
-    }
-    if matches!(base, AnalyzedBase::CowSlice) {
-        return Err(errors::binary_cow_slice(field, field_display_name));
-    }
     if !matches!(base, AnalyzedBase::Numeric(NumericKind::U8)) {
         return Err(errors::binary_wrong_base(field, field_display_name));
     }
@@ -30,11 +24,14 @@ pub(super) fn parse_as_binary_shape(
                 Err(errors::binary_inner_option(field, field_display_name))
             }
         }
-        Some(RawWrapper::Vec) => {
+        Some(RawWrapper::List(
+            ListCollection::Vec | ListCollection::Array(_) | ListCollection::Slice,
+        )) => {
             let mut trimmed = wrappers.to_vec();
             trimmed.pop();
             Ok((LeafSpec::Binary, trimmed))
         }
+        Some(RawWrapper::List(_)) => Err(errors::binary_non_vec_bytes(field, field_display_name)),
         Some(RawWrapper::SmartPtr) => Err(errors::binary_wrong_base(field, field_display_name)),
     }
 }
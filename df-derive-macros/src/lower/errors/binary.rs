@@ -33,25 +33,17 @@ pub fn binary_wrong_base<S: ToTokens + ?Sized>(span: &S, field_display_name: &st
     )
 }
 
-pub fn binary_cow_slice<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
-    syn::Error::new_spanned(
-        span,
-        format!(
-            "field `{field_display_name}` has `as_binary` on `Cow<'_, [T]>`, but \
-             `as_binary` only supports `Cow<'_, [u8]>`; use `Vec<T>` for list columns"
-        ),
-    )
-}
-
-pub fn binary_borrowed_slice<S: ToTokens + ?Sized>(
+pub fn binary_non_vec_bytes<S: ToTokens + ?Sized>(
     span: &S,
     field_display_name: &str,
 ) -> syn::Error {
     syn::Error::new_spanned(
         span,
         format!(
-            "field `{field_display_name}` has `as_binary` on `&[T]`, but \
-             `as_binary` only supports `&[u8]`; use `Vec<T>` for list columns"
+            "field `{field_display_name}` has `as_binary` but its bytes are not held in a \
+             `Vec<u8>`, `[u8; N]`, or `[u8]` slice; the innermost `u8` collection becomes the \
+             Binary blob and must be a `Vec`, an array, or a slice. Collect the bytes into a \
+             `Vec<u8>` or drop the attribute."
         ),
     )
 }
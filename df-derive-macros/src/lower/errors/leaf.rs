@@ -1,3 +1,4 @@
+use proc_macro2::Span;
 use quote::ToTokens;
 
 pub fn unannotated_cow_bytes<S: ToTokens + ?Sized>(
@@ -44,35 +45,13 @@ pub fn unannotated_borrowed_bytes<S: ToTokens + ?Sized>(
     syn::Error::new_spanned(span, message)
 }
 
-pub fn borrowed_slice<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
-    syn::Error::new_spanned(
-        span,
-        format!(
-            "field `{field_display_name}` uses `&[T]`, but df-derive only \
-             supports `&[u8]` with `#[df_derive(as_binary)]`; use `Vec<T>` \
-             for list columns"
-        ),
-    )
-}
-
-pub fn cow_slice<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
-    syn::Error::new_spanned(
-        span,
-        format!(
-            "field `{field_display_name}` uses `Cow<'_, [T]>`, but df-derive only \
-             supports `Cow<'_, [u8]>` with `#[df_derive(as_binary)]`; use `Vec<T>` \
-             for list columns"
-        ),
-    )
-}
-
 pub fn as_str_wrong_base<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
     syn::Error::new_spanned(
         span,
         format!(
             "field `{field_display_name}` has `as_str` but its base type does not implement \
              `AsRef<str>`; `as_str` only applies to `String`, `&str`, `Cow<'_, str>`, \
-             custom struct types, or generic type parameters — drop the attribute or \
+             `Box<str>`/`Rc<str>`/`Arc<str>`, custom struct types, or generic type parameters — drop the attribute or \
              change the field type"
         ),
     )
@@ -106,26 +85,80 @@ pub fn as_string_bytes<S: ToTokens + ?Sized>(span: &S, field_display_name: &str)
     )
 }
 
-pub fn as_string_slice<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
+pub fn as_string_tuple<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
     syn::Error::new_spanned(
         span,
         format!(
-            "field `{field_display_name}` has `as_string`, but borrowed slices \
-             (`&[T]`/`Cow<'_, [T]>`) do not implement `Display`; use `Vec<T>` \
-             for list columns, or wrap the value in a custom type that \
-             implements `Display`"
+            "field `{field_display_name}` has `as_string` but its type is a tuple; \
+             field-level attributes do not apply to multi-column tuple fields. \
+             Hoist the tuple into a named struct that derives `ToDataFrame` if \
+             you need per-element attributes."
         ),
     )
 }
 
-pub fn as_string_tuple<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
+pub fn as_string_map<S: ToTokens + ?Sized>(span: &S, field_display_name: &str) -> syn::Error {
     syn::Error::new_spanned(
         span,
         format!(
-            "field `{field_display_name}` has `as_string` but its type is a tuple; \
-             field-level attributes do not apply to multi-column tuple fields. \
-             Hoist the tuple into a named struct that derives `ToDataFrame` if \
-             you need per-element attributes."
+            "field `{field_display_name}` has `as_string`, but maps do not \
+             implement `Display`; drop the attribute to write the entries as a \
+             list column, or wrap the map in a custom type that implements \
+             `Display`"
+        ),
+    )
+}
+
+pub fn sort_keys_wrong_base(span: Span, field_display_name: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "field `{field_display_name}` has `sort_keys` but is neither a `HashMap` field \
+             nor a `HashSet` list; `sort_keys` only applies to hash-ordered collections"
+        ),
+    )
+}
+
+pub fn as_list_wrong_base(span: Span, field_display_name: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "field `{field_display_name}` has `as_list` but does not end in a collection \
+             df-derive can read as a list; `as_list` applies to a type such as \
+             `SmallVec<[T; N]>` or `im::Vector<T>` whose first generic argument is the \
+             element type, and is not needed on `Vec`, sets, or arrays"
+        ),
+    )
+}
+
+pub fn dtype_wrong_base(span: Span, field_display_name: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "field `{field_display_name}` has `dtype = \"...\"` but its values are not \
+             numeric, temporal, or decimal; `dtype` casts integer, float, `chrono`, \
+             `Duration`, and decimal leaves and cannot be combined with `as_str`, \
+             `as_string`, `as_binary`, or `leaf`"
+        ),
+    )
+}
+
+pub fn split_map_wrong_base(span: Span, field_display_name: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "field `{field_display_name}` has `split_map` but is not a `HashMap` or \
+             `BTreeMap` field; `split_map` only applies to map fields"
+        ),
+    )
+}
+
+pub fn sort_keys_on_btree_map(span: Span, field_display_name: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "field `{field_display_name}` has `sort_keys`, but `BTreeMap` entries \
+             are already written in key order; drop the attribute"
         ),
     )
 }
@@ -137,7 +170,36 @@ pub fn decimal_wrong_base<S: ToTokens + ?Sized>(span: &S, field_display_name: &s
             "field `{field_display_name}` has `decimal(...)` but its base type is not \
              a decimal backend candidate; `decimal(...)` applies to types named \
              `Decimal`, custom struct types, or generic type parameters that \
-             implement `Decimal128Encode`"
+             implement `Decimal128Encode`, to `f32`/`f64`, and to integers with \
+             `mantissa`"
+        ),
+    )
+}
+
+pub fn decimal_integer_without_mantissa<S: ToTokens + ?Sized>(
+    span: &S,
+    field_display_name: &str,
+) -> syn::Error {
+    syn::Error::new_spanned(
+        span,
+        format!(
+            "field `{field_display_name}` has `decimal(...)` on an integer; add `mantissa` \
+             to write the integer as the decimal's mantissa at the declared scale, or use \
+             `dtype = \"Decimal(precision, scale)\"` to write its whole-number value"
+        ),
+    )
+}
+
+pub fn decimal_mantissa_wrong_base<S: ToTokens + ?Sized>(
+    span: &S,
+    field_display_name: &str,
+) -> syn::Error {
+    syn::Error::new_spanned(
+        span,
+        format!(
+            "field `{field_display_name}` has `decimal(..., mantissa)` but its base type is \
+             not an integer; `mantissa` applies to integer fields holding a decimal's \
+             unscaled value"
         ),
     )
 }
@@ -1,15 +1,19 @@
 use crate::attrs::{
-    FieldConversion, FieldDisposition, LeafOverride, Spanned, parse_field_disposition,
+    FieldAttrs, FieldConversion, FieldDisposition, LeafOverride, Spanned, parse_field_attrs,
 };
-use crate::ir::FieldIR;
+use crate::ir::{FieldIR, LeafCast, LeafSpec, TerminalLeafRoute, WithLeaf};
 use crate::lower::binary::parse_as_binary_shape;
-use crate::lower::leaf::parse_leaf_spec;
+use crate::lower::errors;
+use crate::lower::leaf::{apply_map_options, parse_leaf_spec};
 use crate::lower::tuple::{
     FieldAttrRef, reject_attrs_on_tuple, reject_unsupported_wrapped_nested_tuples,
 };
 use crate::lower::validation::reject_direct_self_reference;
 use crate::lower::wrappers::normalize_wrappers;
-use crate::type_analysis::{AnalyzedBase, analyze_type};
+use crate::type_analysis::{
+    AnalyzedBase, ListCollection, RawWrapper, analyze_as_list, analyze_opaque_as_list,
+    analyze_opaque_leaf, analyze_type,
+};
 use syn::Ident;
 
 pub fn lower_field(
@@ -17,15 +21,29 @@ pub fn lower_field(
     name_ident: Ident,
     field_index: Option<usize>,
     struct_name: &Ident,
+    generics: &syn::Generics,
     generic_params: &[Ident],
 ) -> Result<Option<FieldIR>, syn::Error> {
     let display_name = name_ident.to_string();
-    let disposition = parse_field_disposition(field, &display_name)?;
-    if matches!(disposition, FieldDisposition::Skip) {
-        return Ok(None);
+    let attrs = parse_field_attrs(field, &display_name)?;
+    match &attrs.disposition {
+        FieldDisposition::Skip => return Ok(None),
+        FieldDisposition::Include(
+            conversion @ (FieldConversion::With(_) | FieldConversion::DfLeaf),
+        ) => {
+            let conversion = conversion.clone();
+            return lower_opaque_field(field, name_ident, field_index, generics, conversion, attrs)
+                .map(Some);
+        }
+        FieldDisposition::Include(_) => {}
     }
+    let disposition = attrs.disposition;
 
-    let analyzed = analyze_type(&field.ty, generic_params)?;
+    let mut analyzed = analyze_type(&field.ty, generic_params)?;
+    if let Some(span) = attrs.as_list {
+        analyzed = analyze_as_list(analyzed, generic_params)?
+            .ok_or_else(|| errors::as_list_wrong_base(span, &display_name))?;
+    }
     reject_direct_self_reference(&analyzed, &display_name, struct_name)?;
     reject_unsupported_wrapped_nested_tuples(&analyzed, &display_name)?;
 
@@ -37,8 +55,15 @@ pub fn lower_field(
     let leaf_override: Option<&Spanned<LeafOverride>> = match conversion {
         FieldConversion::Default | FieldConversion::Binary { .. } => None,
         FieldConversion::LeafOverride(override_) => Some(override_),
+        FieldConversion::With(_) | FieldConversion::DfLeaf => {
+            unreachable!("`with` and `leaf` fields are lowered above")
+        }
     };
     let leaf_override_value = leaf_override.map(|override_| &override_.value);
+    let sort_hash_sets = attrs.map.sort_keys.is_some()
+        && analyzed
+            .wrappers
+            .contains(&RawWrapper::List(ListCollection::HashSet));
 
     let (leaf_spec, wrapper_shape) = match conversion {
         FieldConversion::Binary { span } => {
@@ -51,7 +76,7 @@ pub fn lower_field(
             }
             let (leaf, trimmed) =
                 parse_as_binary_shape(field, &display_name, &analyzed.base, &analyzed.wrappers)?;
-            (leaf, normalize_wrappers(&trimmed))
+            (leaf, normalize_wrappers(&trimmed, sort_hash_sets))
         }
         FieldConversion::Default | FieldConversion::LeafOverride(_) => {
             let leaf_override_span = leaf_override.map(|override_| override_.span);
@@ -62,15 +87,90 @@ pub fn lower_field(
                 leaf_override_span,
                 analyzed.base,
             )?;
-            (leaf, normalize_wrappers(&analyzed.wrappers))
+            (leaf, normalize_wrappers(&analyzed.wrappers, sort_hash_sets))
+        }
+        FieldConversion::With(_) | FieldConversion::DfLeaf => {
+            unreachable!("`with` and `leaf` fields are lowered above")
         }
     };
 
+    let leaf_spec = apply_map_options(leaf_spec, &display_name, attrs.map, sort_hash_sets)?;
+    let cast = check_cast(&leaf_spec, attrs.cast, &display_name)?;
+
     Ok(Some(FieldIR {
         name: name_ident,
         field_index,
         leaf_spec,
         wrapper_shape,
         outer_smart_ptr_depth,
+        metadata: attrs.metadata.into_entries(),
+        cast,
     }))
 }
+
+/// Lower a `with` or `leaf` field, whose leaf type is kept as written
+/// rather than analyzed.
+fn lower_opaque_field(
+    field: &syn::Field,
+    name_ident: Ident,
+    field_index: Option<usize>,
+    generics: &syn::Generics,
+    conversion: FieldConversion,
+    attrs: FieldAttrs,
+) -> Result<FieldIR, syn::Error> {
+    let display_name = name_ident.to_string();
+    let mut leaf = analyze_opaque_leaf(&field.ty)?;
+    if let Some(span) = attrs.as_list {
+        leaf = analyze_opaque_as_list(leaf)?
+            .ok_or_else(|| errors::as_list_wrong_base(span, &display_name))?;
+    }
+    let sort_hash_sets = attrs.map.sort_keys.is_some()
+        && leaf
+            .wrappers
+            .contains(&RawWrapper::List(ListCollection::HashSet));
+    let leaf_spec = match conversion {
+        FieldConversion::With(with) => LeafSpec::With(Box::new(WithLeaf {
+            source: leaf.ty,
+            func: with.value,
+            field: name_ident.clone(),
+            generics: generics.clone(),
+        })),
+        _ => LeafSpec::DfLeaf(leaf.ty),
+    };
+    let cast = check_cast(&leaf_spec, attrs.cast, &display_name)?;
+    Ok(FieldIR {
+        leaf_spec: apply_map_options(leaf_spec, &display_name, attrs.map, sort_hash_sets)?,
+        wrapper_shape: normalize_wrappers(&leaf.wrappers, sort_hash_sets),
+        name: name_ident,
+        field_index,
+        outer_smart_ptr_depth: leaf.outer_smart_ptr_depth,
+        metadata: attrs.metadata.into_entries(),
+        cast,
+    })
+}
+
+/// Every leaf a `dtype` cast applies to, including each tuple element, must
+/// be a numeric, temporal, or decimal primitive. A `with` function's result
+/// type is not known here, so the runtime checks it when the cast runs.
+fn check_cast(
+    leaf_spec: &LeafSpec,
+    cast: Option<Spanned<LeafCast>>,
+    display_name: &str,
+) -> Result<Option<LeafCast>, syn::Error> {
+    let Some(cast) = cast else {
+        return Ok(None);
+    };
+    let mut castable = true;
+    leaf_spec.walk_terminal_leaves(&mut |leaf| {
+        castable &= matches!(leaf, LeafSpec::With(_))
+            || matches!(
+                leaf.terminal_route(),
+                Some(TerminalLeafRoute::Primitive(leaf)) if leaf.is_castable()
+            );
+    });
+    if castable {
+        Ok(Some(cast.value))
+    } else {
+        Err(errors::dtype_wrong_base(cast.span, display_name))
+    }
+}
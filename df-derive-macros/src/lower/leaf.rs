@@ -1,5 +1,8 @@
-use crate::attrs::LeafOverride;
-use crate::ir::{DateTimeUnit, DecimalBackend, DisplayBase, DurationSource, LeafSpec, StringyBase};
+use crate::attrs::{LeafOverride, MapOptions};
+use crate::ir::{
+    DateTimeUnit, DecimalBackend, DisplayBase, DurationSource, LeafSpec, MapKind, MapLeaf,
+    StringyBase,
+};
 use crate::lower::tuple::{FieldAttrRef, analyzed_to_tuple_element, reject_attrs_on_tuple};
 use crate::type_analysis::{
     AnalyzedBase, DEFAULT_DATETIME_UNIT, DEFAULT_DECIMAL_PRECISION, DEFAULT_DECIMAL_SCALE,
@@ -34,9 +37,18 @@ pub(super) fn parse_leaf_spec(
         None => default_leaf_for_base(field, field_display_name, base, true),
         Some(LeafOverride::AsString) => parse_leaf_as_string(field, field_display_name, &base),
         Some(LeafOverride::AsStr) => parse_leaf_as_str(field, field_display_name, base),
-        Some(LeafOverride::Decimal { precision, scale }) => {
-            parse_leaf_decimal(field, field_display_name, &base, *precision, *scale)
-        }
+        Some(LeafOverride::Decimal {
+            precision,
+            scale,
+            mantissa,
+        }) => parse_leaf_decimal(
+            field,
+            field_display_name,
+            &base,
+            *precision,
+            *scale,
+            *mantissa,
+        ),
         Some(LeafOverride::TimeUnit(unit)) => {
             parse_leaf_time_unit(field, field_display_name, &base, *unit)
         }
@@ -64,8 +76,7 @@ pub(super) fn default_leaf_for_base<S: ToTokens + ?Sized>(
             field_display_name,
             can_add_as_binary,
         )),
-        AnalyzedBase::BorrowedSlice => Err(errors::borrowed_slice(span, field_display_name)),
-        AnalyzedBase::CowSlice => Err(errors::cow_slice(span, field_display_name)),
+        AnalyzedBase::Str => Ok(LeafSpec::AsStr(StringyBase::Str)),
         AnalyzedBase::Bool => Ok(LeafSpec::Bool),
         AnalyzedBase::DateTimeTz => Ok(LeafSpec::DateTime(DEFAULT_DATETIME_UNIT)),
         AnalyzedBase::NaiveDate => Ok(LeafSpec::NaiveDate),
@@ -86,6 +97,12 @@ pub(super) fn default_leaf_for_base<S: ToTokens + ?Sized>(
         }),
         AnalyzedBase::Struct(ty) => Ok(LeafSpec::Struct(ty)),
         AnalyzedBase::Generic(ident) => Ok(LeafSpec::Generic(ident)),
+        AnalyzedBase::Map { kind, ty } => Ok(LeafSpec::Map(MapLeaf {
+            ty,
+            kind,
+            sort_keys: false,
+            split: false,
+        })),
         AnalyzedBase::Tuple(elements) => {
             let lowered: Result<Vec<_>, _> = elements
                 .into_iter()
@@ -96,6 +113,35 @@ pub(super) fn default_leaf_for_base<S: ToTokens + ?Sized>(
     }
 }
 
+/// Apply `sort_keys` / `split_map` to a map leaf, rejecting them on any other
+/// field.
+pub(super) fn apply_map_options(
+    leaf: LeafSpec,
+    field_display_name: &str,
+    options: MapOptions,
+    sorts_hash_set: bool,
+) -> Result<LeafSpec, syn::Error> {
+    let LeafSpec::Map(mut map) = leaf else {
+        if let Some(span) = options.sort_keys
+            && !sorts_hash_set
+        {
+            return Err(errors::sort_keys_wrong_base(span, field_display_name));
+        }
+        if let Some(span) = options.split {
+            return Err(errors::split_map_wrong_base(span, field_display_name));
+        }
+        return Ok(leaf);
+    };
+    if let Some(span) = options.sort_keys
+        && map.kind == MapKind::BTree
+    {
+        return Err(errors::sort_keys_on_btree_map(span, field_display_name));
+    }
+    map.sort_keys = options.sort_keys.is_some();
+    map.split = options.split.is_some();
+    Ok(LeafSpec::Map(map))
+}
+
 fn parse_leaf_as_str(
     field: &syn::Field,
     field_display_name: &str,
@@ -105,14 +151,14 @@ fn parse_leaf_as_str(
         AnalyzedBase::String => Ok(LeafSpec::AsStr(StringyBase::String)),
         AnalyzedBase::BorrowedStr => Ok(LeafSpec::AsStr(StringyBase::BorrowedStr)),
         AnalyzedBase::CowStr => Ok(LeafSpec::AsStr(StringyBase::CowStr)),
+        AnalyzedBase::Str => Ok(LeafSpec::AsStr(StringyBase::Str)),
         AnalyzedBase::Struct(ty) => Ok(LeafSpec::AsStr(StringyBase::Struct(ty))),
         AnalyzedBase::Generic(ident) => Ok(LeafSpec::AsStr(StringyBase::Generic(ident))),
         AnalyzedBase::Tuple(_)
+        | AnalyzedBase::Map { .. }
         | AnalyzedBase::Numeric(_)
         | AnalyzedBase::BorrowedBytes
         | AnalyzedBase::CowBytes
-        | AnalyzedBase::BorrowedSlice
-        | AnalyzedBase::CowSlice
         | AnalyzedBase::Bool
         | AnalyzedBase::DateTimeTz
         | AnalyzedBase::NaiveDate
@@ -142,6 +188,7 @@ fn display_base_for_as_string(
         | AnalyzedBase::String
         | AnalyzedBase::BorrowedStr
         | AnalyzedBase::CowStr
+        | AnalyzedBase::Str
         | AnalyzedBase::Bool
         | AnalyzedBase::DateTimeTz
         | AnalyzedBase::NaiveDate
@@ -155,10 +202,8 @@ fn display_base_for_as_string(
         AnalyzedBase::BorrowedBytes | AnalyzedBase::CowBytes => {
             Err(errors::as_string_bytes(field, field_display_name))
         }
-        AnalyzedBase::BorrowedSlice | AnalyzedBase::CowSlice => {
-            Err(errors::as_string_slice(field, field_display_name))
-        }
         AnalyzedBase::Tuple(_) => Err(errors::as_string_tuple(field, field_display_name)),
+        AnalyzedBase::Map { .. } => Err(errors::as_string_map(field, field_display_name)),
     }
 }
 
@@ -168,25 +213,33 @@ fn parse_leaf_decimal(
     base: &AnalyzedBase,
     precision: u8,
     scale: u8,
+    mantissa: bool,
 ) -> Result<LeafSpec, syn::Error> {
-    match base {
-        AnalyzedBase::Decimal => Ok(LeafSpec::Decimal {
-            precision,
-            scale,
-            backend: DecimalBackend::RuntimeKnown,
-        }),
-        AnalyzedBase::Struct(ty) => Ok(LeafSpec::Decimal {
-            precision,
-            scale,
-            backend: DecimalBackend::Struct(ty.clone()),
-        }),
-        AnalyzedBase::Generic(ident) => Ok(LeafSpec::Decimal {
-            precision,
-            scale,
-            backend: DecimalBackend::Generic(ident.clone()),
-        }),
-        _ => Err(errors::decimal_wrong_base(field, field_display_name)),
-    }
+    let backend = match (base, mantissa) {
+        (AnalyzedBase::Numeric(kind), false) if kind.is_float() => DecimalBackend::Float(*kind),
+        (AnalyzedBase::Numeric(kind), true) if !kind.is_float() => DecimalBackend::Mantissa(*kind),
+        (AnalyzedBase::Numeric(_), false) => {
+            return Err(errors::decimal_integer_without_mantissa(
+                field,
+                field_display_name,
+            ));
+        }
+        (_, true) => {
+            return Err(errors::decimal_mantissa_wrong_base(
+                field,
+                field_display_name,
+            ));
+        }
+        (AnalyzedBase::Decimal, false) => DecimalBackend::RuntimeKnown,
+        (AnalyzedBase::Struct(ty), false) => DecimalBackend::Struct(ty.clone()),
+        (AnalyzedBase::Generic(ident), false) => DecimalBackend::Generic(ident.clone()),
+        _ => return Err(errors::decimal_wrong_base(field, field_display_name)),
+    };
+    Ok(LeafSpec::Decimal {
+        precision,
+        scale,
+        backend,
+    })
 }
 
 fn parse_leaf_time_unit(
@@ -1,24 +1,32 @@
 use crate::ir::{
     AccessChain, AccessStep, ColumnIR, FieldIR, FieldSource, LeafShape, LeafSpec, TerminalLeafSpec,
     TupleElement, TupleProjectionPath, TupleProjectionStep, VecLayers, WrapperShape,
-    column_name_for_ident,
+    column_name_for_ident, nested_column_name,
 };
 
-pub fn project_fields_to_columns(fields: Vec<FieldIR>) -> Vec<ColumnIR> {
+/// Project lowered fields into columns. `transparent` leaves the field name
+/// out of the column names, so a transparent struct's single field produces
+/// the columns its type would produce on its own.
+pub fn project_fields_to_columns(fields: Vec<FieldIR>, transparent: bool) -> Vec<ColumnIR> {
     let mut columns = Vec::new();
     for field in fields {
-        project_field(field, &mut columns);
+        project_field(field, transparent, &mut columns);
     }
     columns
 }
 
-fn project_field(field: FieldIR, columns: &mut Vec<ColumnIR>) {
+fn project_field(field: FieldIR, transparent: bool, columns: &mut Vec<ColumnIR>) {
     let root = FieldSource {
         name: field.name.clone(),
         field_index: field.field_index,
         outer_smart_ptr_depth: field.outer_smart_ptr_depth,
     };
-    let name = column_name_for_ident(&field.name);
+    let name = if transparent {
+        String::new()
+    } else {
+        column_name_for_ident(&field.name)
+    };
+    let first_column = columns.len();
     match field.leaf_spec {
         LeafSpec::Tuple(elements) => {
             project_tuple_elements(columns, &root, &name, &field.wrapper_shape, &elements, &[]);
@@ -30,6 +38,16 @@ fn project_field(field: FieldIR, columns: &mut Vec<ColumnIR>) {
             field.wrapper_shape,
         )),
     }
+    if !field.metadata.is_empty() {
+        for column in &mut columns[first_column..] {
+            column.set_metadata(field.metadata.clone());
+        }
+    }
+    if let Some(cast) = field.cast {
+        for column in &mut columns[first_column..] {
+            column.set_cast(cast);
+        }
+    }
 }
 
 fn project_tuple_elements(
@@ -47,7 +65,7 @@ fn project_tuple_elements(
             outer_smart_ptr_depth: element.outer_smart_ptr_depth,
         };
         path.push(step);
-        let name = format!("{column_prefix}.field_{index}");
+        let name = nested_column_name(column_prefix, &format!("field_{index}"));
         if let LeafSpec::Tuple(inner) = &element.leaf_spec {
             debug_assert!(
                 !matches!(parent_wrapper, WrapperShape::Vec(_)),
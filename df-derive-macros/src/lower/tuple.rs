@@ -55,7 +55,7 @@ pub(super) fn analyzed_to_tuple_element(
 ) -> Result<TupleElement, syn::Error> {
     let leaf_spec =
         default_leaf_for_base(&analyzed.field_ty, field_display_name, analyzed.base, false)?;
-    let wrapper_shape = normalize_wrappers(&analyzed.wrappers);
+    let wrapper_shape = normalize_wrappers(&analyzed.wrappers, false);
     Ok(TupleElement {
         leaf_spec,
         wrapper_shape,
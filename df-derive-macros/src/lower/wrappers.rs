@@ -1,7 +1,7 @@
 use crate::ir::{
     AccessChain, AccessStep, LeafShape, NonEmpty, VecLayerSpec, VecLayers, WrapperShape,
 };
-use crate::type_analysis::RawWrapper;
+use crate::type_analysis::{ListCollection, RawWrapper};
 
 /// Normalize the raw outer-to-inner `RawWrapper` sequence into a
 /// `WrapperShape` the encoder consumes directly. `Option` and smart-pointer
@@ -9,8 +9,11 @@ use crate::type_analysis::RawWrapper;
 /// each `Vec`, immediately surrounding the leaf, or for the leaf-only path.
 /// Polars folds consecutive `Option`s into a single validity bit per
 /// position, so the count is also cached to choose the direct single-Option
-/// path versus the collapsed multi-Option path.
-pub fn normalize_wrappers(wrappers: &[RawWrapper]) -> WrapperShape {
+/// path versus the collapsed multi-Option path. With `sort_hash_sets`, each
+/// `HashSet` layer ends its access chain with a `SortedSet` step. Each
+/// `as_list` layer ends its chain with an `AsList` step and records its
+/// types, and each array layer records its length.
+pub fn normalize_wrappers(wrappers: &[RawWrapper], sort_hash_sets: bool) -> WrapperShape {
     let mut layers: Vec<VecLayerSpec> = Vec::new();
     let mut pending_access = AccessChain::empty();
     for w in wrappers {
@@ -21,11 +24,27 @@ pub fn normalize_wrappers(wrappers: &[RawWrapper]) -> WrapperShape {
             RawWrapper::SmartPtr => {
                 pending_access.steps.push(AccessStep::SmartPtr);
             }
-            RawWrapper::Vec => {
+            RawWrapper::List(collection) => {
+                if sort_hash_sets && *collection == ListCollection::HashSet {
+                    pending_access.steps.push(AccessStep::SortedSet);
+                }
+                if matches!(collection, ListCollection::AsList(_)) {
+                    pending_access.steps.push(AccessStep::AsList);
+                }
                 let option_layers_above = pending_access.option_layers();
+                let fixed_len = match collection {
+                    ListCollection::Array(len) => Some(Box::new(len.clone())),
+                    _ => None,
+                };
+                let as_list = match collection {
+                    ListCollection::AsList(types) => Some(types.clone()),
+                    _ => None,
+                };
                 layers.push(VecLayerSpec {
                     option_layers_above,
                     access: std::mem::take(&mut pending_access),
+                    fixed_len,
+                    as_list,
                 });
             }
         }
@@ -1,5 +1,6 @@
 use crate::ir::{FieldIR, StructIR};
 use crate::lower::{lower_field, project_fields_to_columns};
+use proc_macro2::Span;
 use quote::format_ident;
 use syn::{Data, DeriveInput, Fields, Ident};
 
@@ -18,11 +19,26 @@ fn validate_struct_input(input: &DeriveInput) -> Result<&syn::DataStruct, syn::E
     }
 }
 
+fn validate_transparent(span: Span, fields: &[FieldIR]) -> Result<(), syn::Error> {
+    if fields.len() == 1 {
+        return Ok(());
+    }
+    Err(syn::Error::new(
+        span,
+        format!(
+            "`transparent` requires exactly one field that is not `skip`ped, found {}",
+            fields.len()
+        ),
+    ))
+}
+
 /// Parse a `syn::DeriveInput` into the IR consumed by codegen.
 ///
 /// Returns a `syn::Error` for non-struct inputs (enums, unions). Tuple structs
-/// and unit structs are supported.
-pub fn parse_to_ir(input: &DeriveInput) -> Result<StructIR, syn::Error> {
+/// and unit structs are supported. With `transparent` (the span of
+/// `#[df_derive(transparent)]`), the single remaining field's columns are
+/// named as if the field's type stood in for the struct.
+pub fn parse_to_ir(input: &DeriveInput, transparent: Option<Span>) -> Result<StructIR, syn::Error> {
     let name = input.ident.clone();
     let generics = input.generics.clone();
     let generic_params: Vec<Ident> = generics.type_params().map(|tp| tp.ident.clone()).collect();
@@ -39,7 +55,7 @@ pub fn parse_to_ir(input: &DeriveInput) -> Result<StructIR, syn::Error> {
                     .expect("named fields must have ident")
                     .clone();
                 if let Some(field_ir) =
-                    lower_field(field, name_ident, None, &name, &generic_params)?
+                    lower_field(field, name_ident, None, &name, &generics, &generic_params)?
                 {
                     fields_ir.push(field_ir);
                 }
@@ -49,19 +65,29 @@ pub fn parse_to_ir(input: &DeriveInput) -> Result<StructIR, syn::Error> {
         Fields::Unnamed(unnamed) => {
             for (index, field) in unnamed.unnamed.iter().enumerate() {
                 let name_ident = format_ident!("field_{}", index);
-                if let Some(field_ir) =
-                    lower_field(field, name_ident, Some(index), &name, &generic_params)?
-                {
+                if let Some(field_ir) = lower_field(
+                    field,
+                    name_ident,
+                    Some(index),
+                    &name,
+                    &generics,
+                    &generic_params,
+                )? {
                     fields_ir.push(field_ir);
                 }
             }
         }
     }
 
+    if let Some(span) = transparent {
+        validate_transparent(span, &fields_ir)?;
+    }
+
     Ok(StructIR {
+        vis: input.vis.clone(),
         name,
         generics,
-        columns: project_fields_to_columns(fields_ir),
+        columns: project_fields_to_columns(fields_ir, transparent.is_some()),
     })
 }
 
@@ -71,7 +97,7 @@ mod tests {
     use crate::ir::{ColumnIR, DecimalBackend, LeafShape, LeafSpec, NumericKind, WrapperShape};
 
     fn parse(input: &DeriveInput) -> StructIR {
-        parse_to_ir(input).expect("input should lower to IR")
+        parse_to_ir(input, None).expect("input should lower to IR")
     }
 
     fn column<'a>(ir: &'a StructIR, name: &str) -> &'a ColumnIR {
@@ -191,6 +217,31 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn transparent_field_columns_drop_the_field_name() {
+        let input: DeriveInput = syn::parse_quote! {
+            struct Spread {
+                pair: (f64, Option<f64>),
+                #[df_derive(skip)]
+                cache: u64,
+            }
+        };
+        let ir = parse_to_ir(&input, Some(Span::call_site())).expect("input should lower to IR");
+        let names: Vec<&str> = ir.columns.iter().map(ColumnIR::name).collect();
+        assert_eq!(names, ["field_0", "field_1"]);
+
+        let input: DeriveInput = syn::parse_quote! {
+            struct Price(f64);
+        };
+        let ir = parse_to_ir(&input, Some(Span::call_site())).expect("input should lower to IR");
+        assert_eq!(ir.columns[0].name(), "");
+
+        let input: DeriveInput = syn::parse_quote! {
+            struct Quote(f64, f64);
+        };
+        assert!(parse_to_ir(&input, Some(Span::call_site())).is_err());
+    }
+
     #[test]
     fn decimal_backend_is_part_of_leaf_spec() {
         let ir = parse(&syn::parse_quote! {
@@ -200,6 +251,10 @@ mod tests {
                 generic: T,
                 #[df_derive(decimal(precision = 18, scale = 4))]
                 custom: CustomDecimal,
+                #[df_derive(decimal(precision = 18, scale = 4, mantissa))]
+                ticks: i64,
+                #[df_derive(decimal(precision = 12, scale = 3))]
+                rate: f32,
             }
         });
 
@@ -229,6 +284,24 @@ mod tests {
                 backend: DecimalBackend::Struct(_),
             }
         ));
+
+        assert!(matches!(
+            column(&ir, "ticks").leaf_spec().as_leaf_spec(),
+            LeafSpec::Decimal {
+                precision: 18,
+                scale: 4,
+                backend: DecimalBackend::Mantissa(NumericKind::I64),
+            }
+        ));
+
+        assert!(matches!(
+            column(&ir, "rate").leaf_spec().as_leaf_spec(),
+            LeafSpec::Decimal {
+                precision: 12,
+                scale: 3,
+                backend: DecimalBackend::Float(NumericKind::F32),
+            }
+        ));
     }
 
     #[test]
@@ -1,8 +1,10 @@
-use crate::ir::NumericKind;
+use crate::ir::{MapKind, NumericKind};
 use syn::{GenericArgument, PathArguments, Type, TypePath};
 
 use super::AnalyzedBase;
-use super::path_match::{PathView, path_is_exact_no_args, path_is_exact_with_leaf_args};
+use super::path_match::{
+    PathView, path_is_bare_or_std_collection, path_is_exact_no_args, path_is_exact_with_leaf_args,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) enum KnownBase {
@@ -69,6 +71,31 @@ pub(super) fn classify_known_base(type_path: &TypePath) -> Option<KnownBase> {
     None
 }
 
+/// `HashMap<K, V>` (optionally with a hasher) or `BTreeMap<K, V>`, bare or
+/// under `std::collections`.
+pub(super) fn map_kind(type_path: &TypePath) -> Option<MapKind> {
+    let kind = if path_is_bare_or_std_collection(type_path, "HashMap") {
+        MapKind::Hash
+    } else if path_is_bare_or_std_collection(type_path, "BTreeMap") {
+        MapKind::BTree
+    } else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(args) = &type_path.path.segments.last()?.arguments else {
+        return None;
+    };
+    let type_args = args
+        .args
+        .iter()
+        .filter(|arg| matches!(arg, GenericArgument::Type(_)))
+        .count();
+    let max_args = match kind {
+        MapKind::Hash => 3,
+        MapKind::BTree => 2,
+    };
+    (2..=max_args).contains(&type_args).then_some(kind)
+}
+
 fn bare_numeric_kind(type_path: &TypePath) -> Option<NumericKind> {
     if type_path.qself.is_some() || type_path.path.segments.len() != 1 {
         return None;
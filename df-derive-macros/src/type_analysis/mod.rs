@@ -3,14 +3,14 @@ mod path_match;
 mod rejections;
 mod wrappers;
 
-use crate::ir::{DateTimeUnit, NumericKind};
+use crate::ir::{AsListTypes, DateTimeUnit, MapKind, NumericKind};
 use syn::{Ident, PathArguments, Type};
 
-use known_types::classify_known_base;
-use rejections::{
-    reject_bare_duration, reject_bare_unsized_leaf, reject_unsupported_collection_type,
+use known_types::{classify_known_base, is_bare_str_type, map_kind};
+use rejections::{reject_bare_duration, reject_bare_unsized_leaf};
+use wrappers::{
+    analyze_cow_base, borrowed_reference_base, peel_opaque_leaf_wrappers, peel_type_wrappers,
 };
-use wrappers::{analyze_cow_base, borrowed_reference_base, peel_type_wrappers};
 
 /// Default `Datetime` precision for `chrono::DateTime<Tz>` and
 /// `chrono::NaiveDateTime` fields without an explicit `time_unit` override.
@@ -27,13 +27,34 @@ pub const DEFAULT_DECIMAL_SCALE: u8 = 10;
 
 /// Raw wrapper position before normalization. The parser collapses these
 /// into a `WrapperShape` after type analysis.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RawWrapper {
     Option,
-    Vec,
+    /// One list layer: a `Vec`, a std set/deque collection, an array, or a
+    /// slice behind a pointer.
+    List(ListCollection),
     SmartPtr,
 }
 
+/// Collection behind a list layer. Every collection is written in its own
+/// iteration order; only `HashSet` layers can opt into sorting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListCollection {
+    Vec,
+    HashSet,
+    BTreeSet,
+    VecDeque,
+    LinkedList,
+    /// `[T; N]`, carrying its length expression. Written as a fixed-width
+    /// Polars `Array` instead of a `List`.
+    Array(syn::Expr),
+    /// Unsized `[T]` behind `Box` / `Rc` / `Arc` / `Cow` or a reference.
+    Slice,
+    /// A third-party collection opted in with `as_list`, iterated by
+    /// reference through `IntoIterator`.
+    AsList(Box<AsListTypes>),
+}
+
 /// Analyzed base type before parser-time override fusion into a `LeafSpec`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AnalyzedBase {
@@ -49,10 +70,8 @@ pub enum AnalyzedBase {
     BorrowedBytes,
     /// `Cow<'_, [u8]>` — supported only with `#[df_derive(as_binary)]`.
     CowBytes,
-    /// `&[T]` for non-`u8` element types.
-    BorrowedSlice,
-    /// `Cow<'_, [T]>` for non-`u8` element types.
-    CowSlice,
+    /// Unsized `str` behind `Box` / `Rc` / `Arc`, read through `AsRef<str>`.
+    Str,
     Bool,
     /// `chrono::DateTime<Tz>`.
     DateTimeTz,
@@ -72,6 +91,11 @@ pub enum AnalyzedBase {
     Struct(Type),
     /// Generic type parameter declared on the enclosing struct.
     Generic(Ident),
+    /// `HashMap` / `BTreeMap`, written as one list-of-entries column.
+    Map {
+        kind: MapKind,
+        ty: Type,
+    },
     /// Tuple-typed base, with each element recursively analyzed.
     Tuple(Vec<AnalyzedType>),
 }
@@ -87,6 +111,15 @@ pub struct AnalyzedType {
     pub outer_smart_ptr_depth: usize,
 }
 
+/// A `with` or `leaf` field after wrapper peeling: the type its function or
+/// `DfLeaf` impl reads, with no leaf classification of its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpaqueLeaf {
+    pub ty: Type,
+    pub wrappers: Vec<RawWrapper>,
+    pub outer_smart_ptr_depth: usize,
+}
+
 fn bare_generic_param_ident(ty: &Type, generic_params: &[Ident]) -> Option<Ident> {
     let Type::Path(type_path) = ty else {
         return None;
@@ -108,12 +141,11 @@ fn bare_generic_param_ident(ty: &Type, generic_params: &[Ident]) -> Option<Ident
 }
 
 pub fn analyze_type(ty: &Type, generic_params: &[Ident]) -> Result<AnalyzedType, syn::Error> {
+    reject_bare_unsized_leaf(ty)?;
     let peeled = peel_type_wrappers(ty)?;
 
     if bare_generic_param_ident(peeled.current_type, generic_params).is_none() {
-        reject_unsupported_collection_type(peeled.current_type)?;
         reject_bare_duration(peeled.current_type, generic_params)?;
-        reject_bare_unsized_leaf(peeled.current_type)?;
     }
 
     let base = analyze_base_type(peeled.current_type, generic_params)?;
@@ -126,6 +158,111 @@ pub fn analyze_type(ty: &Type, generic_params: &[Ident]) -> Result<AnalyzedType,
     })
 }
 
+/// Read the user type an `as_list` field stopped at as one more list layer.
+/// The element is the collection's first generic argument, or that
+/// argument's element when it is an inline array (`SmallVec<[T; 4]>`).
+/// Returns `None` when the base is not such a collection.
+pub fn analyze_as_list(
+    analyzed: AnalyzedType,
+    generic_params: &[Ident],
+) -> Result<Option<AnalyzedType>, syn::Error> {
+    let AnalyzedBase::Struct(collection) = &analyzed.base else {
+        return Ok(None);
+    };
+    let Some(elem_ty) = collection_elem_type(collection) else {
+        return Ok(None);
+    };
+    let elem = analyze_type(elem_ty, generic_params)?;
+
+    let wrappers = as_list_wrappers(
+        analyzed.wrappers,
+        collection,
+        elem_ty,
+        elem.outer_smart_ptr_depth,
+        elem.wrappers,
+    );
+    Ok(Some(AnalyzedType {
+        base: elem.base,
+        wrappers,
+        field_ty: analyzed.field_ty,
+        outer_smart_ptr_depth: analyzed.outer_smart_ptr_depth,
+    }))
+}
+
+/// Peel a `with` or `leaf` field's wrappers without classifying what is
+/// left: the function or the type's `DfLeaf` impl decides the column, so any
+/// type can be the leaf.
+pub fn analyze_opaque_leaf(ty: &Type) -> Result<OpaqueLeaf, syn::Error> {
+    reject_bare_unsized_leaf(ty)?;
+    let peeled = peel_opaque_leaf_wrappers(ty)?;
+    Ok(OpaqueLeaf {
+        ty: peeled.current_type.clone(),
+        wrappers: peeled.wrappers,
+        outer_smart_ptr_depth: peeled.outer_smart_ptr_depth,
+    })
+}
+
+/// [`analyze_as_list`] for a `with` or `leaf` field: the leaf is the
+/// collection's element.
+pub fn analyze_opaque_as_list(source: OpaqueLeaf) -> Result<Option<OpaqueLeaf>, syn::Error> {
+    let Some(elem_ty) = collection_elem_type(&source.ty) else {
+        return Ok(None);
+    };
+    let elem = analyze_opaque_leaf(elem_ty)?;
+    let wrappers = as_list_wrappers(
+        source.wrappers,
+        &source.ty,
+        elem_ty,
+        elem.outer_smart_ptr_depth,
+        elem.wrappers,
+    );
+    Ok(Some(OpaqueLeaf {
+        ty: elem.ty,
+        wrappers,
+        outer_smart_ptr_depth: source.outer_smart_ptr_depth,
+    }))
+}
+
+/// The collection's outer wrappers, its `as_list` layer, and the element's
+/// own wrappers, with the element's pointers as inner pointer layers.
+fn as_list_wrappers(
+    mut wrappers: Vec<RawWrapper>,
+    collection: &Type,
+    elem_ty: &Type,
+    elem_smart_ptr_depth: usize,
+    elem_wrappers: Vec<RawWrapper>,
+) -> Vec<RawWrapper> {
+    wrappers.push(RawWrapper::List(ListCollection::AsList(Box::new(
+        AsListTypes {
+            collection: collection.clone(),
+            elem: elem_ty.clone(),
+        },
+    ))));
+    wrappers.extend(std::iter::repeat_n(
+        RawWrapper::SmartPtr,
+        elem_smart_ptr_depth,
+    ));
+    wrappers.extend(elem_wrappers);
+    wrappers
+}
+
+fn collection_elem_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(args) = &type_path.path.segments.last()?.arguments else {
+        return None;
+    };
+    let elem = args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })?;
+    match elem {
+        Type::Array(array) => Some(&array.elem),
+        _ => Some(elem),
+    }
+}
+
 fn analyze_base_type(ty: &Type, generic_params: &[Ident]) -> Result<AnalyzedBase, syn::Error> {
     if let Some(tuple) = analyze_tuple_base(ty, generic_params)? {
         return Ok(tuple);
@@ -141,6 +278,10 @@ fn analyze_base_type(ty: &Type, generic_params: &[Ident]) -> Result<AnalyzedBase
         return Ok(AnalyzedBase::Generic(ident));
     }
 
+    if is_bare_str_type(ty) {
+        return Ok(AnalyzedBase::Str);
+    }
+
     if let Type::Path(type_path) = ty {
         if let Some(base) = analyze_cow_base(type_path) {
             return Ok(base);
@@ -148,6 +289,12 @@ fn analyze_base_type(ty: &Type, generic_params: &[Ident]) -> Result<AnalyzedBase
         if let Some(known) = classify_known_base(type_path) {
             return Ok(known.into_analyzed_base());
         }
+        if let Some(kind) = map_kind(type_path) {
+            return Ok(AnalyzedBase::Map {
+                kind,
+                ty: ty.clone(),
+            });
+        }
         return Ok(AnalyzedBase::Struct(ty.clone()));
     }
 
@@ -225,10 +372,38 @@ mod tests {
             analyze(&syn::parse_quote!(std::borrow::Cow<'a, [u8]>)).base,
             AnalyzedBase::CowBytes
         );
+    }
+
+    #[test]
+    fn peels_pointer_wrapped_unsized_leaves() {
+        for ty in [
+            syn::parse_quote!(Box<str>),
+            syn::parse_quote!(std::sync::Arc<str>),
+            syn::parse_quote!(Option<std::rc::Rc<str>>),
+        ] {
+            assert_eq!(analyze(&ty).base, AnalyzedBase::Str);
+        }
+
+        let borrowed = analyze_with_generics(&syn::parse_quote!(&'a [T]), &["T"]);
+        assert_eq!(borrowed.wrappers, [RawWrapper::List(ListCollection::Slice)]);
+        assert_eq!(borrowed.outer_smart_ptr_depth, 1);
+        assert!(matches!(borrowed.base, AnalyzedBase::Generic(ref ident) if ident == "T"));
+
+        let shared = analyze(&syn::parse_quote!(Vec<Cow<'a, [Arc<[u8]>]>>));
         assert_eq!(
-            analyze_with_generics(&syn::parse_quote!(&'a [T]), &["T"]).base,
-            AnalyzedBase::BorrowedSlice
+            shared.wrappers,
+            [
+                RawWrapper::List(ListCollection::Vec),
+                RawWrapper::SmartPtr,
+                RawWrapper::List(ListCollection::Slice),
+                RawWrapper::SmartPtr,
+                RawWrapper::List(ListCollection::Slice)
+            ]
         );
+        assert_eq!(shared.base, AnalyzedBase::Numeric(NumericKind::U8));
+
+        assert!(analyze_type(&syn::parse_quote!(str), &[]).is_err());
+        assert!(analyze_type(&syn::parse_quote!([i32]), &[]).is_err());
     }
 
     #[test]
@@ -256,18 +431,95 @@ mod tests {
         let opt_vec_u8 = analyze(&syn::parse_quote!(Option<Vec<u8>>));
         assert!(matches!(
             opt_vec_u8.wrappers.as_slice(),
-            [RawWrapper::Option, RawWrapper::Vec]
+            [RawWrapper::Option, RawWrapper::List(ListCollection::Vec)]
         ));
         assert_eq!(opt_vec_u8.base, AnalyzedBase::Numeric(NumericKind::U8));
 
         let nested = analyze_with_generics(&syn::parse_quote!(Vec<Option<Vec<T>>>), &["T"]);
         assert!(matches!(
             nested.wrappers.as_slice(),
-            [RawWrapper::Vec, RawWrapper::Option, RawWrapper::Vec]
+            [
+                RawWrapper::List(ListCollection::Vec),
+                RawWrapper::Option,
+                RawWrapper::List(ListCollection::Vec)
+            ]
         ));
         assert!(matches!(nested.base, AnalyzedBase::Generic(ref ident) if ident == "T"));
     }
 
+    #[test]
+    fn peels_set_and_deque_collections_as_list_layers() {
+        let sets = analyze(&syn::parse_quote!(
+            Option<std::collections::HashSet<BTreeSet<i32>, RandomState>>
+        ));
+        assert_eq!(
+            sets.wrappers,
+            [
+                RawWrapper::Option,
+                RawWrapper::List(ListCollection::HashSet),
+                RawWrapper::List(ListCollection::BTreeSet)
+            ]
+        );
+        assert_eq!(sets.base, AnalyzedBase::Numeric(NumericKind::I32));
+
+        let deque = analyze(&syn::parse_quote!(VecDeque<LinkedList<String>>));
+        assert_eq!(
+            deque.wrappers,
+            [
+                RawWrapper::List(ListCollection::VecDeque),
+                RawWrapper::List(ListCollection::LinkedList)
+            ]
+        );
+
+        let foreign = analyze(&syn::parse_quote!(other::HashSet<i32>));
+        assert!(foreign.wrappers.is_empty());
+        assert!(matches!(foreign.base, AnalyzedBase::Struct(_)));
+    }
+
+    #[test]
+    fn peels_arrays_as_fixed_list_layers() {
+        let analyzed = analyze(&syn::parse_quote!(Option<[[f64; 3]; N]>));
+        assert_eq!(
+            analyzed.wrappers,
+            [
+                RawWrapper::Option,
+                RawWrapper::List(ListCollection::Array(syn::parse_quote!(N))),
+                RawWrapper::List(ListCollection::Array(syn::parse_quote!(3)))
+            ]
+        );
+        assert_eq!(analyzed.base, AnalyzedBase::Numeric(NumericKind::F64));
+    }
+
+    #[test]
+    fn as_list_reads_the_collection_element_as_a_list_layer() {
+        let as_list =
+            |ty: &Type| analyze_as_list(analyze(ty), &[]).expect("element should analyze");
+
+        let inline = as_list(&syn::parse_quote!(Option<SmallVec<[Option<Box<i32>>; 4]>>))
+            .expect("SmallVec should read as a list");
+        assert_eq!(
+            inline.wrappers,
+            [
+                RawWrapper::Option,
+                RawWrapper::List(ListCollection::AsList(Box::new(AsListTypes {
+                    collection: syn::parse_quote!(SmallVec<[Option<Box<i32>>; 4]>),
+                    elem: syn::parse_quote!(Option<Box<i32>>),
+                }))),
+                RawWrapper::Option,
+                RawWrapper::SmartPtr
+            ]
+        );
+        assert_eq!(inline.base, AnalyzedBase::Numeric(NumericKind::I32));
+
+        let nested = as_list(&syn::parse_quote!(Vec<ArrayVec<Vec<String>, 8>>))
+            .expect("ArrayVec should read as a list");
+        assert_eq!(nested.wrappers.len(), 3);
+        assert_eq!(nested.base, AnalyzedBase::String);
+
+        assert!(as_list(&syn::parse_quote!(Vec<i32>)).is_none());
+        assert!(as_list(&syn::parse_quote!(Points)).is_none());
+    }
+
     #[test]
     fn does_not_peel_wrappers_with_extra_generic_arguments() {
         let analyzed = analyze_with_generics(&syn::parse_quote!(Option<T, U>), &["T", "U"]);
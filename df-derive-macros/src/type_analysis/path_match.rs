@@ -72,3 +72,17 @@ pub(super) fn wrapper_path_matches(type_path: &TypePath, bare: &str, qualified:
         path.exact_with_leaf_args(&[bare]) || path.exact_with_leaf_args(qualified)
     })
 }
+
+/// `leaf` written bare or as `std::collections::leaf`.
+pub(super) fn path_is_bare_or_std_collection(type_path: &TypePath, leaf: &str) -> bool {
+    let Some(path) = PathView::from_type_path(type_path) else {
+        return false;
+    };
+    let Some(segment) = path.leaf() else {
+        return false;
+    };
+
+    segment.ident == leaf
+        && (path.len() == 1
+            || (path.len() == 3 && path_prefix_is_no_args(type_path, &["std", "collections"])))
+}
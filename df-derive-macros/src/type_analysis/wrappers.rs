@@ -1,8 +1,10 @@
 use syn::{AngleBracketedGenericArguments, GenericArgument, PathArguments, Type, TypePath};
 
 use super::known_types::{is_bare_str_type, is_u8_type};
-use super::path_match::{path_is_exact_with_leaf_args, wrapper_path_matches};
-use super::{AnalyzedBase, RawWrapper};
+use super::path_match::{
+    path_is_bare_or_std_collection, path_is_exact_with_leaf_args, wrapper_path_matches,
+};
+use super::{AnalyzedBase, ListCollection, RawWrapper};
 
 pub(super) struct PeeledType<'a> {
     pub(super) wrappers: Vec<RawWrapper>,
@@ -28,6 +30,45 @@ fn peel_vec(ty: &Type) -> Option<&Type> {
         .or_else(|| extract_inner_type(ty, "Vec", &["alloc", "vec", "Vec"]))
 }
 
+/// A `Vec`, an array, a slice, or a set/deque collection written bare or as
+/// `std::collections::Name`. `HashSet` may carry a hasher argument. Slices
+/// only reach here behind a pointer; bare `[T]` fields are rejected up front.
+fn peel_list(ty: &Type) -> Option<(ListCollection, &Type)> {
+    if let Some(inner_ty) = peel_vec(ty) {
+        return Some((ListCollection::Vec, inner_ty));
+    }
+    if let Type::Array(array) = ty {
+        return Some((ListCollection::Array(array.len.clone()), &array.elem));
+    }
+    if let Type::Slice(slice) = ty {
+        return Some((ListCollection::Slice, &slice.elem));
+    }
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    [
+        (ListCollection::HashSet, "HashSet", 2),
+        (ListCollection::BTreeSet, "BTreeSet", 1),
+        (ListCollection::VecDeque, "VecDeque", 1),
+        (ListCollection::LinkedList, "LinkedList", 1),
+    ]
+    .into_iter()
+    .find(|(_, name, _)| path_is_bare_or_std_collection(type_path, name))
+    .and_then(|(collection, _, max_args)| {
+        let segment = type_path.path.segments.last()?;
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        if args.args.is_empty() || args.args.len() > max_args {
+            return None;
+        }
+        match args.args.first()? {
+            GenericArgument::Type(inner_ty) => Some((collection, inner_ty)),
+            _ => None,
+        }
+    })
+}
+
 fn peel_smart_ptr(ty: &Type) -> Option<&Type> {
     extract_inner_type(ty, "Box", &["std", "boxed", "Box"])
         .or_else(|| extract_inner_type(ty, "Box", &["alloc", "boxed", "Box"]))
@@ -37,7 +78,7 @@ fn peel_smart_ptr(ty: &Type) -> Option<&Type> {
         .or_else(|| extract_inner_type(ty, "Arc", &["alloc", "sync", "Arc"]))
 }
 
-fn peel_reference(ty: &Type) -> Result<Option<&Type>, syn::Error> {
+fn peel_reference(ty: &Type, keep_semantic_bases: bool) -> Result<Option<&Type>, syn::Error> {
     let Type::Reference(reference) = ty else {
         return Ok(None);
     };
@@ -49,7 +90,7 @@ fn peel_reference(ty: &Type) -> Result<Option<&Type>, syn::Error> {
              or mark the field `#[df_derive(skip)]`",
         ));
     }
-    if borrowed_reference_base(reference).is_some() {
+    if keep_semantic_bases && borrowed_reference_base(reference).is_some() {
         return Ok(None);
     }
 
@@ -57,6 +98,17 @@ fn peel_reference(ty: &Type) -> Result<Option<&Type>, syn::Error> {
 }
 
 pub(super) fn peel_type_wrappers(ty: &Type) -> Result<PeeledType<'_>, syn::Error> {
+    peel_wrappers(ty, true)
+}
+
+/// Peel a `with` or `leaf` field's wrappers. `&str`, `Cow<'_, str>`, and
+/// the byte forms are not leaves of their own here: their pointers peel like
+/// any other, so the function or `DfLeaf` impl reads the pointee.
+pub(super) fn peel_opaque_leaf_wrappers(ty: &Type) -> Result<PeeledType<'_>, syn::Error> {
+    peel_wrappers(ty, false)
+}
+
+fn peel_wrappers(ty: &Type, keep_semantic_bases: bool) -> Result<PeeledType<'_>, syn::Error> {
     let mut wrappers: Vec<RawWrapper> = Vec::new();
     let mut outer_smart_ptr_depth: usize = 0;
     let mut current_type = ty;
@@ -67,8 +119,8 @@ pub(super) fn peel_type_wrappers(ty: &Type) -> Result<PeeledType<'_>, syn::Error
             current_type = inner_ty;
             continue;
         }
-        if let Some(inner_ty) = peel_vec(current_type) {
-            wrappers.push(RawWrapper::Vec);
+        if let Some((collection, inner_ty)) = peel_list(current_type) {
+            wrappers.push(RawWrapper::List(collection));
             current_type = inner_ty;
             continue;
         }
@@ -84,10 +136,15 @@ pub(super) fn peel_type_wrappers(ty: &Type) -> Result<PeeledType<'_>, syn::Error
                     current_type = inner;
                     continue;
                 }
-                CowPeel::KeepAsSemanticBase => break,
+                CowPeel::KeepAsSemanticBase(inner) if !keep_semantic_bases => {
+                    record_smart_ptr_layer(&mut outer_smart_ptr_depth, &mut wrappers);
+                    current_type = inner;
+                    continue;
+                }
+                CowPeel::KeepAsSemanticBase(_) => break,
             }
         }
-        if let Some(inner_ty) = peel_reference(current_type)? {
+        if let Some(inner_ty) = peel_reference(current_type, keep_semantic_bases)? {
             record_smart_ptr_layer(&mut outer_smart_ptr_depth, &mut wrappers);
             current_type = inner_ty;
             continue;
@@ -128,7 +185,7 @@ fn single_type_arg(args: &AngleBracketedGenericArguments) -> Option<&Type> {
 
 enum CowPeel<'a> {
     Rebind(&'a Type),
-    KeepAsSemanticBase,
+    KeepAsSemanticBase(&'a Type),
 }
 
 fn peel_cow(ty: &Type) -> Option<CowPeel<'_>> {
@@ -139,11 +196,8 @@ fn peel_cow(ty: &Type) -> Option<CowPeel<'_>> {
         return None;
     }
     let inner_ty = cow_inner_type(type_path)?;
-    if is_bare_str_type(inner_ty) {
-        return Some(CowPeel::KeepAsSemanticBase);
-    }
-    if matches!(inner_ty, Type::Slice(_)) {
-        return Some(CowPeel::KeepAsSemanticBase);
+    if is_bare_str_type(inner_ty) || is_u8_slice(inner_ty) {
+        return Some(CowPeel::KeepAsSemanticBase(inner_ty));
     }
     Some(CowPeel::Rebind(inner_ty))
 }
@@ -177,20 +231,18 @@ fn cow_inner_type(type_path: &TypePath) -> Option<&Type> {
     inner_ty
 }
 
+/// `&[u8]` and `Cow<'_, [u8]>` stay semantic byte bases; other slices peel
+/// into list layers.
+fn is_u8_slice(ty: &Type) -> bool {
+    matches!(ty, Type::Slice(slice) if is_u8_type(&slice.elem))
+}
+
 pub(super) fn borrowed_reference_base(reference: &syn::TypeReference) -> Option<AnalyzedBase> {
     let inner_ty = reference.elem.as_ref();
     if is_bare_str_type(inner_ty) {
         return Some(AnalyzedBase::BorrowedStr);
     }
-    if let Type::Slice(slice) = inner_ty {
-        if is_u8_type(&slice.elem) {
-            Some(AnalyzedBase::BorrowedBytes)
-        } else {
-            Some(AnalyzedBase::BorrowedSlice)
-        }
-    } else {
-        None
-    }
+    is_u8_slice(inner_ty).then_some(AnalyzedBase::BorrowedBytes)
 }
 
 pub(super) fn analyze_cow_base(type_path: &TypePath) -> Option<AnalyzedBase> {
@@ -198,13 +250,5 @@ pub(super) fn analyze_cow_base(type_path: &TypePath) -> Option<AnalyzedBase> {
     if is_bare_str_type(inner_ty) {
         return Some(AnalyzedBase::CowStr);
     }
-    if let Type::Slice(slice) = inner_ty {
-        if is_u8_type(&slice.elem) {
-            Some(AnalyzedBase::CowBytes)
-        } else {
-            Some(AnalyzedBase::CowSlice)
-        }
-    } else {
-        None
-    }
+    is_u8_slice(inner_ty).then_some(AnalyzedBase::CowBytes)
 }
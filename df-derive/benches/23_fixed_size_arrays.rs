@@ -0,0 +1,148 @@
+// Empirical validation that fixed-size array fields stay close to the
+// equivalent `Vec` shape. `[T; N]` columns are encoded as lists and then
+// rebuilt as `Array(T, N)` from the list's offsets: without null rows the
+// values buffer is reused, and with null rows the valid runs are copied and
+// each null row padded. The `Option` pair measures that padding, so this
+// bench keeps the array and list variants visible side by side.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use df_derive::ToDataFrame;
+
+#[path = "support/mod.rs"]
+mod bench_support;
+#[path = "../tests/common.rs"]
+mod core;
+use crate::bench_support::configure_criterion;
+use crate::core::dataframe::ToDataFrameVec;
+
+const N_ROWS: usize = 100_000;
+
+// --- Pair 1: bare [f64; 3] vs. Vec<f64> of length 3 ---
+
+#[derive(ToDataFrame, Clone)]
+struct PointArray {
+    xyz: [f64; 3],
+}
+
+#[derive(ToDataFrame, Clone)]
+struct PointList {
+    xyz: Vec<f64>,
+}
+
+// --- Pair 2: Option<[f64; 3]> vs. Option<Vec<f64>> ---
+
+#[derive(ToDataFrame, Clone)]
+struct MaybePointArray {
+    xyz: Option<[f64; 3]>,
+}
+
+#[derive(ToDataFrame, Clone)]
+struct MaybePointList {
+    xyz: Option<Vec<f64>>,
+}
+
+// --- Pair 3: Vec<[f64; 3]> vs. Vec<Vec<f64>> ---
+
+#[derive(ToDataFrame, Clone)]
+struct PathArray {
+    points: Vec<[f64; 3]>,
+}
+
+#[derive(ToDataFrame, Clone)]
+struct PathList {
+    points: Vec<Vec<f64>>,
+}
+
+fn point(i: usize) -> [f64; 3] {
+    let f = f64::from(u32::try_from(i).unwrap());
+    [f, f * 0.5, f * 0.25]
+}
+
+fn make_point_array() -> Vec<PointArray> {
+    (0..N_ROWS).map(|i| PointArray { xyz: point(i) }).collect()
+}
+
+fn make_point_list() -> Vec<PointList> {
+    (0..N_ROWS)
+        .map(|i| PointList {
+            xyz: point(i).to_vec(),
+        })
+        .collect()
+}
+
+fn make_maybe_point_array() -> Vec<MaybePointArray> {
+    (0..N_ROWS)
+        .map(|i| MaybePointArray {
+            xyz: (i % 4 != 0).then(|| point(i)),
+        })
+        .collect()
+}
+
+fn make_maybe_point_list() -> Vec<MaybePointList> {
+    (0..N_ROWS)
+        .map(|i| MaybePointList {
+            xyz: (i % 4 != 0).then(|| point(i).to_vec()),
+        })
+        .collect()
+}
+
+fn make_path_array() -> Vec<PathArray> {
+    (0..N_ROWS)
+        .map(|i| PathArray {
+            points: (i..i + 4).map(point).collect(),
+        })
+        .collect()
+}
+
+fn make_path_list() -> Vec<PathList> {
+    (0..N_ROWS)
+        .map(|i| PathList {
+            points: (i..i + 4).map(|j| point(j).to_vec()).collect(),
+        })
+        .collect()
+}
+
+fn bench_fixed_size_arrays(c: &mut Criterion) {
+    let point_array = make_point_array();
+    let point_list = make_point_list();
+    let maybe_point_array = make_maybe_point_array();
+    let maybe_point_list = make_maybe_point_list();
+    let path_array = make_path_array();
+    let path_list = make_path_list();
+
+    let mut g = c.benchmark_group("fixed_size_arrays");
+    g.bench_function("point_array", |b| {
+        b.iter(|| std::hint::black_box(&point_array).to_dataframe().unwrap());
+    });
+    g.bench_function("point_list", |b| {
+        b.iter(|| std::hint::black_box(&point_list).to_dataframe().unwrap());
+    });
+    g.bench_function("maybe_point_array", |b| {
+        b.iter(|| {
+            std::hint::black_box(&maybe_point_array)
+                .to_dataframe()
+                .unwrap()
+        });
+    });
+    g.bench_function("maybe_point_list", |b| {
+        b.iter(|| {
+            std::hint::black_box(&maybe_point_list)
+                .to_dataframe()
+                .unwrap()
+        });
+    });
+    g.bench_function("path_array", |b| {
+        b.iter(|| std::hint::black_box(&path_array).to_dataframe().unwrap());
+    });
+    g.bench_function("path_list", |b| {
+        b.iter(|| std::hint::black_box(&path_list).to_dataframe().unwrap());
+    });
+    g.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = configure_criterion();
+    targets = bench_fixed_size_arrays
+}
+criterion_main!(benches);
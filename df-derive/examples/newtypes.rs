@@ -0,0 +1,57 @@
+//! Demonstrates `#[df_derive(transparent)]` domain newtypes.
+//!
+//! Without the attribute, a nested `struct Price(f64)` contributes a
+//! `price.field_0` column. With it, the newtype converts as its single
+//! field, so `Price`, `Option<Price>`, and `Vec<Price>` fields produce the
+//! same `price`, `bid`, and `fills` columns as `f64`, `Option<f64>`, and
+//! `Vec<f64>` would. Field attributes on the inner field still apply.
+//!
+//! Uses the default `df-derive` facade runtime.
+
+use df_derive::ToDataFrame;
+use df_derive::dataframe::ToDataFrameVec as _;
+
+#[derive(ToDataFrame)]
+#[df_derive(transparent)]
+struct Price(f64);
+
+#[derive(ToDataFrame)]
+#[df_derive(transparent)]
+struct Symbol(#[df_derive(as_str)] String);
+
+#[derive(ToDataFrame)]
+struct Trade {
+    symbol: Symbol,
+    price: Price,
+    bid: Option<Price>,
+    fills: Vec<Price>,
+}
+
+fn main() -> polars::prelude::PolarsResult<()> {
+    let trades = vec![
+        Trade {
+            symbol: Symbol("AAPL".into()),
+            price: Price(187.5),
+            bid: Some(Price(187.4)),
+            fills: vec![Price(187.5), Price(187.6)],
+        },
+        Trade {
+            symbol: Symbol("MSFT".into()),
+            price: Price(411.2),
+            bid: None,
+            fills: vec![],
+        },
+    ];
+
+    println!("Trades:\n{}", trades.as_slice().to_dataframe()?);
+
+    let schema = <Trade as df_derive::dataframe::ToDataFrame>::schema()?;
+    println!("\nSchema (newtypes keep the field name and the inner dtype):");
+    for (name, dtype) in schema {
+        println!("  {name}: {dtype:?}");
+    }
+
+    println!("\nHandle for `price`: {}", Trade::cols().price());
+
+    Ok(())
+}
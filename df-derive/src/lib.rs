@@ -42,6 +42,10 @@
 pub use df_derive_core::dataframe;
 pub use df_derive_macros::ToDataFrame;
 
+/// `serde` conversions for types that do not derive `ToDataFrame`.
+#[cfg(feature = "serde")]
+pub use df_derive_core::dataframe::serde;
+
 /// Common imports for normal users.
 ///
 /// This includes the derive macro and the runtime traits. The trait
@@ -50,7 +54,8 @@ pub use df_derive_macros::ToDataFrame;
 pub mod prelude {
     pub use crate::ToDataFrame;
     pub use crate::dataframe::{
-        Columnar, Decimal128Encode, ToDataFrame, ToDataFrame as ToDataFrameTrait, ToDataFrameVec,
+        Columnar, Decimal128Encode, DfLeaf, ToDataFrame, ToDataFrame as ToDataFrameTrait,
+        ToDataFrameVec,
     };
 }
 
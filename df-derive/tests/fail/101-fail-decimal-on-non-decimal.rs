@@ -5,7 +5,7 @@ mod core;
 #[derive(ToDataFrame)]
 struct Bad {
     #[df_derive(decimal(precision = 38, scale = 18))]
-    not_a_decimal: f64,
+    not_a_decimal: bool,
 }
 
 fn main() {}
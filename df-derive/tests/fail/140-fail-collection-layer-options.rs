@@ -0,0 +1,26 @@
+use df_derive::ToDataFrame;
+use std::collections::{BTreeSet, VecDeque};
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+struct SortedBTreeSet {
+    // BTreeSet elements are already written in key order
+    #[df_derive(sort_keys)]
+    values: BTreeSet<String>,
+}
+
+#[derive(ToDataFrame)]
+struct SortedVec {
+    #[df_derive(sort_keys)]
+    values: Vec<i32>,
+}
+
+#[derive(ToDataFrame)]
+struct DequeBytes {
+    // The Binary blob must be a `Vec<u8>`
+    #[df_derive(as_binary)]
+    payload: VecDeque<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+struct BareStr {
+    id: i32,
+    label: str,
+}
+
+#[derive(ToDataFrame)]
+struct BareSlice {
+    id: i32,
+    values: [i32],
+}
+
+fn main() {}
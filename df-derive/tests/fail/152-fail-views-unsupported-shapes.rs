@@ -0,0 +1,41 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct Inner {
+    value: i32,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct NestedLists {
+    matrix: Vec<Vec<f64>>,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct FixedWidth {
+    coords: [f64; 3],
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct OptionalNested {
+    inner: Option<Inner>,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct Generic<T> {
+    payload: T,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views, views)]
+struct Duplicate {
+    value: i32,
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use df_derive::ToDataFrame;
+use std::collections::{BTreeMap, HashMap};
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+struct SortedBTreeMap {
+    // BTreeMap entries are already in key order
+    #[df_derive(sort_keys)]
+    values: BTreeMap<String, i32>,
+}
+
+#[derive(ToDataFrame)]
+struct SplitVec {
+    #[df_derive(split_map)]
+    values: Vec<i32>,
+}
+
+#[derive(ToDataFrame)]
+struct DuplicateSortKeys {
+    #[df_derive(sort_keys, sort_keys)]
+    values: HashMap<String, i32>,
+}
+
+mod custom_runtime {
+    pub trait MyToDataFrame {}
+    pub trait MyColumnar {}
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(
+    trait = "custom_runtime::MyToDataFrame",
+    columnar = "custom_runtime::MyColumnar"
+)]
+struct CustomRuntimeMap {
+    values: BTreeMap<String, i32>,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+struct AlreadyAList {
+    #[df_derive(as_list)]
+    ids: Vec<i32>,
+}
+
+struct Flat;
+
+#[derive(ToDataFrame)]
+struct NoElement {
+    #[df_derive(as_list)]
+    flat: Flat,
+}
+
+fn main() {}
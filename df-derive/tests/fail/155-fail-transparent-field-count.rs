@@ -0,0 +1,23 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+#[df_derive(transparent)]
+struct TwoFields {
+    bid: f64,
+    ask: f64,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(transparent)]
+struct AllSkipped {
+    #[df_derive(skip)]
+    cache: u64,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(transparent, transparent)]
+struct Duplicate(f64);
+
+fn main() {}
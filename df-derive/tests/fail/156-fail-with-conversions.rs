@@ -0,0 +1,43 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+struct Money {
+    units: i64,
+}
+
+impl Money {
+    fn cents(&self) -> i64 {
+        self.units * 100
+    }
+
+    fn copy(&self) -> Self {
+        Self { units: self.units }
+    }
+}
+
+#[derive(ToDataFrame)]
+struct WithAndAsString {
+    #[df_derive(with = "Money::cents", as_string)]
+    price: Money,
+}
+
+#[derive(ToDataFrame)]
+struct DuplicateWith {
+    #[df_derive(with = "Money::cents", with = "Money::copy")]
+    price: Money,
+}
+
+#[derive(ToDataFrame)]
+struct NotAPath {
+    #[df_derive(with = "|money| money.units")]
+    price: Money,
+}
+
+#[derive(ToDataFrame)]
+struct UnsupportedResult {
+    #[df_derive(with = "Money::copy")]
+    price: Money,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+struct Ticks(i64);
+
+#[derive(ToDataFrame)]
+struct LeafAndAsString {
+    #[df_derive(leaf, as_string)]
+    bid: Ticks,
+}
+
+#[derive(ToDataFrame)]
+struct LeafAndWith {
+    #[df_derive(leaf, with = "Ticks::clone")]
+    bid: Ticks,
+}
+
+#[derive(ToDataFrame)]
+struct MissingDfLeaf {
+    #[df_derive(leaf)]
+    bid: Option<Ticks>,
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+struct StringField {
+    #[df_derive(dtype = "Int64")]
+    name: String,
+}
+
+#[derive(ToDataFrame)]
+struct UnknownDtype {
+    #[df_derive(dtype = "Int65")]
+    id: u64,
+}
+
+#[derive(ToDataFrame)]
+struct OverflowWithoutDtype {
+    #[df_derive(on_overflow = "null")]
+    id: u64,
+}
+
+#[derive(ToDataFrame)]
+struct BadOverflowPolicy {
+    #[df_derive(dtype = "Int64", on_overflow = "wrap")]
+    id: u64,
+}
+
+#[derive(ToDataFrame)]
+struct DtypeAndAsString {
+    #[df_derive(dtype = "Int64", as_string)]
+    id: u64,
+}
+
+fn main() {}
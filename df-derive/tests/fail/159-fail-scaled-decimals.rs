@@ -0,0 +1,29 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+struct IntegerWithoutMantissa {
+    #[df_derive(decimal(precision = 18, scale = 4))]
+    price: i64,
+}
+
+#[derive(ToDataFrame)]
+struct MantissaOnFloat {
+    #[df_derive(decimal(precision = 18, scale = 4, mantissa))]
+    price: f64,
+}
+
+#[derive(ToDataFrame)]
+struct MantissaOnString {
+    #[df_derive(decimal(precision = 18, scale = 4, mantissa))]
+    price: String,
+}
+
+#[derive(ToDataFrame)]
+struct DuplicateMantissa {
+    #[df_derive(decimal(precision = 18, scale = 4, mantissa, mantissa))]
+    price: i64,
+}
+
+fn main() {}
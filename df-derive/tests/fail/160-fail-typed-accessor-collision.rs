@@ -1,11 +1,11 @@
 use df_derive::ToDataFrame;
-
 #[path = "../common.rs"]
 mod core;
 
 #[derive(ToDataFrame)]
-struct Bad<'a> {
-    items: &'a [i32],
+struct Collides {
+    pair: (i32, i32),
+    pair_field_0: i32,
 }
 
 fn main() {}
@@ -0,0 +1,16 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+#[derive(ToDataFrame)]
+struct Quote {
+    bid: f64,
+}
+
+#[derive(ToDataFrame)]
+struct Collides {
+    spread: (Quote, f64),
+    spread_field_0: f64,
+}
+
+fn main() {}
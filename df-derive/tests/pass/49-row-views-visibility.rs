@@ -0,0 +1,49 @@
+use df_derive::ToDataFrame;
+#[path = "../common.rs"]
+mod core;
+
+pub mod model {
+    use df_derive::ToDataFrame;
+
+    #[derive(ToDataFrame)]
+    #[df_derive(views)]
+    pub struct Venue {
+        pub code: String,
+    }
+
+    #[derive(ToDataFrame)]
+    #[df_derive(views)]
+    pub(crate) struct Order {
+        pub id: u64,
+        pub venue: Venue,
+    }
+
+    #[derive(ToDataFrame)]
+    #[df_derive(views)]
+    struct Internal {
+        flag: bool,
+    }
+
+    pub fn internal_flags(frame: &polars::prelude::DataFrame) -> usize {
+        Internal::iter_views(frame)
+            .map(|views| views.filter(|view| view.flag).count())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct Local {
+    order: model::Order,
+    weights: Vec<f32>,
+}
+
+fn main() {
+    let frame = polars::prelude::DataFrame::empty();
+    let _: Option<model::VenueView<'_>> = model::Venue::iter_views(&frame)
+        .ok()
+        .and_then(|mut views| views.next());
+    let _: Option<model::OrderView<'_>> = None;
+    let _: Option<LocalView<'_>> = None;
+    let _ = model::internal_flags(&frame);
+}
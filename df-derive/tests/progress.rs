@@ -1,4 +1,5 @@
 #[test]
+#[allow(clippy::too_many_lines)]
 fn tests() {
     let t = trybuild::TestCases::new();
 
@@ -41,6 +42,7 @@ fn tests() {
     t.pass("tests/pass/46-datetime-timezones.rs");
     t.pass("tests/pass/47-option-access-chain-no-clone.rs");
     t.pass("tests/pass/48-runtime-override-combinations.rs");
+    t.pass("tests/pass/49-row-views-visibility.rs");
 
     // These files should fail to compile.
     t.compile_fail("tests/fail/96-fail-derive-on-union.rs");
@@ -67,7 +69,6 @@ fn tests() {
     t.compile_fail("tests/fail/117-fail-time-unit-on-naive-date.rs");
     t.compile_fail("tests/fail/118-fail-time-unit-on-naive-time.rs");
     t.compile_fail("tests/fail/119-fail-cow-bytes-without-as-binary.rs");
-    t.compile_fail("tests/fail/120-fail-cow-non-u8-slice.rs");
     t.compile_fail("tests/fail/121-fail-unit-tuple-field.rs");
     t.compile_fail("tests/fail/122-fail-as-str-on-tuple.rs");
     t.compile_fail("tests/fail/123-fail-as-string-on-tuple.rs");
@@ -77,7 +78,6 @@ fn tests() {
     t.compile_fail("tests/fail/127-fail-external-time-duration.rs");
     t.compile_fail("tests/fail/128-fail-cow-bytes-in-tuple.rs");
     t.compile_fail("tests/fail/129-fail-borrowed-bytes-without-as-binary.rs");
-    t.compile_fail("tests/fail/130-fail-borrowed-non-u8-slice.rs");
     t.compile_fail("tests/fail/131-fail-as-binary-borrowed-non-u8-slice.rs");
     t.compile_fail("tests/fail/132-fail-as-str-concrete-type-without-as-ref.rs");
     t.compile_fail("tests/fail/133-fail-skip-with-conversion-attr.rs");
@@ -87,16 +87,25 @@ fn tests() {
     t.compile_fail("tests/fail/137-fail-duplicate-override-attributes.rs");
     t.compile_fail("tests/fail/138-fail-columnar-without-trait.rs");
     t.compile_fail("tests/fail/139-fail-duplicate-container-attributes.rs");
-    t.compile_fail("tests/fail/140-fail-unsupported-collections.rs");
+    t.compile_fail("tests/fail/140-fail-collection-layer-options.rs");
     t.compile_fail("tests/fail/141-fail-as-string-on-std-duration.rs");
     t.compile_fail("tests/fail/142-fail-as-string-on-borrowed-bytes.rs");
-    t.compile_fail("tests/fail/143-fail-as-string-on-borrowed-slice.rs");
     t.compile_fail("tests/fail/144-fail-vec-wrapped-nested-tuple.rs");
     t.compile_fail("tests/fail/145-fail-duplicate-decimal-keys.rs");
     t.compile_fail("tests/fail/146-fail-direct-self-recursive-field.rs");
-    t.compile_fail("tests/fail/147-fail-unsized-smart-pointer-leaves.rs");
+    t.compile_fail("tests/fail/147-fail-bare-unsized-fields.rs");
     t.compile_fail("tests/fail/148-fail-mutable-references.rs");
     t.compile_fail("tests/fail/149-fail-missing-concrete-eager-bounds.rs");
     t.compile_fail("tests/fail/150-fail-skip-with-as-string.rs");
     t.compile_fail("tests/fail/151-fail-decimal-with-time-unit.rs");
+    t.compile_fail("tests/fail/152-fail-views-unsupported-shapes.rs");
+    t.compile_fail("tests/fail/153-fail-map-options.rs");
+    t.compile_fail("tests/fail/154-fail-as-list-wrong-base.rs");
+    t.compile_fail("tests/fail/155-fail-transparent-field-count.rs");
+    t.compile_fail("tests/fail/156-fail-with-conversions.rs");
+    t.compile_fail("tests/fail/157-fail-leaf-fields.rs");
+    t.compile_fail("tests/fail/158-fail-dtype-casts.rs");
+    t.compile_fail("tests/fail/159-fail-scaled-decimals.rs");
+    t.compile_fail("tests/fail/160-fail-typed-accessor-collision.rs");
+    t.compile_fail("tests/fail/161-fail-column-handle-collision.rs");
 }
@@ -0,0 +1,198 @@
+use crate::core::dataframe::arrow::{ParquetCompression, write_ipc, write_parquet};
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::*;
+use std::io::Cursor;
+
+#[derive(ToDataFrame, Clone)]
+struct Quote {
+    venue: String,
+    bid: Option<f64>,
+}
+
+#[derive(ToDataFrame, Clone)]
+struct Row {
+    price: f64,
+    bid: Option<f64>,
+    tags: Vec<String>,
+    maybe_tags: Option<Vec<Option<String>>>,
+    grid: Vec<Option<Vec<i32>>>,
+    quote: Quote,
+    maybe_quote: Option<Quote>,
+    quotes: Vec<Quote>,
+    pair: Option<(i32, Option<String>)>,
+}
+
+fn rows() -> Vec<Row> {
+    vec![
+        Row {
+            price: 1.5,
+            bid: Some(1.4),
+            tags: vec!["a".to_owned()],
+            maybe_tags: Some(vec![Some("x".to_owned()), None]),
+            grid: vec![Some(vec![1, 2]), None],
+            quote: Quote {
+                venue: "XNAS".to_owned(),
+                bid: None,
+            },
+            maybe_quote: None,
+            quotes: vec![Quote {
+                venue: "XNYS".to_owned(),
+                bid: Some(2.0),
+            }],
+            pair: Some((7, None)),
+        },
+        Row {
+            price: 2.5,
+            bid: None,
+            tags: Vec::new(),
+            maybe_tags: None,
+            grid: Vec::new(),
+            quote: Quote {
+                venue: "XLON".to_owned(),
+                bid: Some(3.0),
+            },
+            maybe_quote: Some(Quote {
+                venue: "XPAR".to_owned(),
+                bid: None,
+            }),
+            quotes: Vec::new(),
+            pair: None,
+        },
+    ]
+}
+
+fn field<'a>(schema: &'a ArrowSchema, name: &str) -> &'a ArrowField {
+    schema
+        .get(name)
+        .unwrap_or_else(|| panic!("field {name} missing"))
+}
+
+/// Nullability of the field followed by each list element's nullability.
+fn nullability_chain(field: &ArrowField) -> Vec<bool> {
+    let mut out = vec![field.is_nullable];
+    let mut dtype = &field.dtype;
+    while let ArrowDataType::LargeList(item) = dtype {
+        out.push(item.is_nullable);
+        dtype = &item.dtype;
+    }
+    out
+}
+
+fn assert_row_nullability(schema: &ArrowSchema) {
+    let expected: &[(&str, &[bool])] = &[
+        ("price", &[false]),
+        ("bid", &[true]),
+        ("tags", &[false, false]),
+        ("maybe_tags", &[true, true]),
+        ("grid", &[false, true, false]),
+        ("quote.venue", &[false]),
+        ("quote.bid", &[true]),
+        ("maybe_quote.venue", &[true]),
+        ("maybe_quote.bid", &[true]),
+        ("quotes.venue", &[false, false]),
+        ("quotes.bid", &[false, true]),
+        ("pair.field_0", &[true]),
+        ("pair.field_1", &[true]),
+    ];
+    assert_eq!(schema.len(), expected.len());
+    for (name, chain) in expected {
+        assert_eq!(
+            nullability_chain(field(schema, name)),
+            *chain,
+            "nullability of {name}"
+        );
+    }
+}
+
+#[test]
+fn arrow_schema_tracks_option_layers() {
+    let schema = Row::arrow_schema().unwrap();
+    assert_row_nullability(&schema);
+
+    let names: Vec<String> = schema.iter_names().map(ToString::to_string).collect();
+    let expected: Vec<String> = Row::schema()
+        .unwrap()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(names, expected);
+    assert_eq!(field(&schema, "price").dtype, ArrowDataType::Float64);
+    assert_eq!(field(&schema, "quote.venue").dtype, ArrowDataType::Utf8View);
+}
+
+#[test]
+fn unit_payload_has_empty_arrow_schema() {
+    assert!(<() as ToDataFrame>::arrow_schema().unwrap().is_empty());
+}
+
+#[test]
+fn parquet_writer_marks_non_option_columns_required() {
+    use polars_parquet::parquet::schema::Repetition;
+    use polars_parquet::read::{infer_schema, read_metadata};
+
+    let mut df = rows().as_slice().to_dataframe().unwrap();
+    let mut buf = Vec::new();
+    write_parquet::<Row, _>(&mut df, &mut buf, ParquetCompression::Uncompressed).unwrap();
+
+    let metadata = read_metadata(&mut Cursor::new(&buf)).unwrap();
+    assert_eq!(metadata.num_rows, 2);
+    let repetition = |name: &str| {
+        metadata
+            .schema()
+            .fields()
+            .iter()
+            .find(|field| field.name() == name)
+            .map(|field| field.get_field_info().repetition)
+            .unwrap_or_else(|| panic!("parquet field {name} missing"))
+    };
+    assert_eq!(repetition("price"), Repetition::Required);
+    assert_eq!(repetition("bid"), Repetition::Optional);
+    assert_eq!(repetition("quotes.venue"), Repetition::Required);
+    assert_eq!(repetition("maybe_quote.venue"), Repetition::Optional);
+
+    assert_row_nullability(&infer_schema(&metadata).unwrap());
+}
+
+#[test]
+fn ipc_writer_round_trips_schema_and_rows() {
+    use polars_arrow::io::ipc::read::{FileReader, read_file_metadata};
+
+    let mut df = rows().as_slice().to_dataframe().unwrap();
+    let mut buf = Vec::new();
+    write_ipc::<Row, _>(&mut df, &mut buf).unwrap();
+
+    let mut cursor = Cursor::new(&buf);
+    let metadata = read_file_metadata(&mut cursor).unwrap();
+    assert_row_nullability(&metadata.schema);
+
+    let mut total_rows = 0;
+    for batch in FileReader::new(cursor, metadata, None, None) {
+        let batch = batch.unwrap();
+        total_rows += batch.len();
+        assert_eq!(batch.arrays()[0].null_count(), 0);
+        assert_eq!(batch.arrays()[1].null_count(), 1);
+    }
+    assert_eq!(total_rows, 2);
+}
+
+#[test]
+fn writers_reject_frames_that_do_not_match_the_schema() {
+    let mut df = rows().as_slice().to_dataframe().unwrap();
+    df.rename("price", "px".into()).unwrap();
+    let err = write_ipc::<Row, _>(&mut df, Vec::new()).unwrap_err();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)), "{err}");
+
+    let mut df = df!(
+        "venue" => [Some("XNAS"), None],
+        "bid" => [Some(1.0), None],
+    )
+    .unwrap();
+    let err = write_parquet::<Quote, _>(&mut df, Vec::new(), ParquetCompression::Uncompressed)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("column `venue` is declared non-nullable but contains nulls"),
+        "{err}"
+    );
+}
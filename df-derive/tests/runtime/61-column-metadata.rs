@@ -0,0 +1,113 @@
+use crate::core::dataframe::arrow::{annotated_arrow_schema, write_ipc};
+use crate::core::dataframe::{ColumnMetadata, ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use std::io::Cursor;
+
+#[derive(ToDataFrame, Clone)]
+struct Quote {
+    /// Trading venue MIC.
+    venue: String,
+    #[df_derive(unit = "USD")]
+    bid: Option<f64>,
+}
+
+#[derive(ToDataFrame, Clone)]
+struct Trade {
+    /// Last traded price.
+    ///
+    /// Quoted per share.
+    #[df_derive(unit = "USD", metadata(source = "consolidated"))]
+    price: f64,
+    /// Ignored in favour of the explicit description.
+    #[df_derive(description = "Ticker symbol", as_str)]
+    symbol: String,
+    size: u64,
+    #[df_derive(metadata(source = "venue"))]
+    quotes: Vec<Quote>,
+    /// Bid/ask pair.
+    spread: (f64, f64),
+    #[df_derive(skip)]
+    _cache: (),
+}
+
+fn metadata(entries: &[(&str, &str)]) -> ColumnMetadata {
+    entries
+        .iter()
+        .map(|&(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+#[test]
+fn column_metadata_collects_docs_and_attributes() {
+    let entries = Trade::column_metadata().unwrap();
+    let expected = vec![
+        (
+            "price".to_owned(),
+            metadata(&[
+                ("description", "Last traded price.\n\nQuoted per share."),
+                ("unit", "USD"),
+                ("source", "consolidated"),
+            ]),
+        ),
+        (
+            "symbol".to_owned(),
+            metadata(&[("description", "Ticker symbol")]),
+        ),
+        (
+            "quotes.venue".to_owned(),
+            metadata(&[("description", "Trading venue MIC."), ("source", "venue")]),
+        ),
+        (
+            "quotes.bid".to_owned(),
+            metadata(&[("unit", "USD"), ("source", "venue")]),
+        ),
+        (
+            "spread.field_0".to_owned(),
+            metadata(&[("description", "Bid/ask pair.")]),
+        ),
+        (
+            "spread.field_1".to_owned(),
+            metadata(&[("description", "Bid/ask pair.")]),
+        ),
+    ];
+    assert_eq!(entries, expected);
+    assert!(<() as ToDataFrame>::column_metadata().unwrap().is_empty());
+}
+
+#[test]
+fn writers_store_column_metadata_on_arrow_fields() {
+    use polars_arrow::io::ipc::read::read_file_metadata;
+
+    let schema = annotated_arrow_schema::<Trade>().unwrap();
+    let size = schema.get("size").unwrap();
+    assert!(size.metadata.is_none());
+
+    let trades = vec![Trade {
+        price: 1.5,
+        symbol: "AAPL".to_owned(),
+        size: 10,
+        quotes: Vec::new(),
+        spread: (1.4, 1.6),
+        _cache: (),
+    }];
+    let mut df = trades.as_slice().to_dataframe().unwrap();
+    let mut buf = Vec::new();
+    write_ipc::<Trade, _>(&mut df, &mut buf).unwrap();
+
+    let file = read_file_metadata(&mut Cursor::new(&buf)).unwrap();
+    let price = file.schema.get("price").unwrap();
+    let price_metadata = price.metadata.as_deref().unwrap();
+    assert_eq!(price_metadata.get("unit").map(|v| v.as_str()), Some("USD"));
+    assert_eq!(
+        price_metadata.get("source").map(|v| v.as_str()),
+        Some("consolidated")
+    );
+    let bid = file.schema.get("quotes.bid").unwrap();
+    assert_eq!(
+        bid.metadata
+            .as_deref()
+            .and_then(|metadata| metadata.get("unit"))
+            .map(|v| v.as_str()),
+        Some("USD")
+    );
+}
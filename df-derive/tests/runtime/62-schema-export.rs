@@ -0,0 +1,128 @@
+use crate::core::dataframe::schema_export::{
+    SqlDialect, avro_schema, column_descriptors, json_schema, sql_ddl,
+};
+use df_derive::ToDataFrame;
+
+#[derive(ToDataFrame)]
+struct Quote {
+    venue: String,
+    bid: Option<f64>,
+}
+
+#[derive(ToDataFrame)]
+struct Trade {
+    /// Ticker symbol.
+    symbol: String,
+    #[df_derive(decimal(precision = 12, scale = 4))]
+    price: rust_decimal::Decimal,
+    size: Option<u64>,
+    #[df_derive(time_unit = "us")]
+    ts: chrono::DateTime<chrono::Utc>,
+    flags: Vec<Option<bool>>,
+    quotes: Vec<Quote>,
+}
+
+#[test]
+fn column_descriptors_pair_dtypes_with_nullability() {
+    let columns = column_descriptors::<Trade>().unwrap();
+    let summary: Vec<(&str, &[bool])> = columns
+        .iter()
+        .map(|column| (column.name.as_str(), column.nullability.as_slice()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("symbol", &[false][..]),
+            ("price", &[false][..]),
+            ("size", &[true][..]),
+            ("ts", &[false][..]),
+            ("flags", &[false, true][..]),
+            ("quotes.venue", &[false, false][..]),
+            ("quotes.bid", &[false, true][..]),
+        ]
+    );
+    assert_eq!(columns[0].description.as_deref(), Some("Ticker symbol."));
+}
+
+#[test]
+fn sql_ddl_renders_each_dialect() {
+    assert_eq!(
+        sql_ddl::<Trade>("trades", SqlDialect::Postgres).unwrap(),
+        "CREATE TABLE \"trades\" (\n    \"symbol\" TEXT NOT NULL,\n    \"price\" NUMERIC(12, 4) NOT NULL,\n    \"size\" NUMERIC(20, 0),\n    \"ts\" TIMESTAMP NOT NULL,\n    \"flags\" BOOLEAN[] NOT NULL,\n    \"quotes.venue\" TEXT[] NOT NULL,\n    \"quotes.bid\" DOUBLE PRECISION[] NOT NULL\n);\nCOMMENT ON COLUMN \"trades\".\"symbol\" IS 'Ticker symbol.';\n"
+    );
+    assert_eq!(
+        sql_ddl::<Trade>("trades", SqlDialect::DuckDb).unwrap(),
+        "CREATE TABLE \"trades\" (\n    \"symbol\" VARCHAR NOT NULL,\n    \"price\" DECIMAL(12, 4) NOT NULL,\n    \"size\" UBIGINT,\n    \"ts\" TIMESTAMP NOT NULL,\n    \"flags\" BOOLEAN[] NOT NULL,\n    \"quotes.venue\" VARCHAR[] NOT NULL,\n    \"quotes.bid\" DOUBLE[] NOT NULL\n);\nCOMMENT ON COLUMN \"trades\".\"symbol\" IS 'Ticker symbol.';\n"
+    );
+    assert_eq!(
+        sql_ddl::<Trade>("trades", SqlDialect::ClickHouse).unwrap(),
+        "CREATE TABLE `trades` (\n    `symbol` String COMMENT 'Ticker symbol.',\n    `price` Decimal(12, 4),\n    `size` Nullable(UInt64),\n    `ts` DateTime64(6),\n    `flags` Array(Nullable(Bool)),\n    `quotes.venue` Array(String),\n    `quotes.bid` Array(Nullable(Float64))\n)\nENGINE = MergeTree\nORDER BY tuple();\n"
+    );
+}
+
+#[test]
+fn json_schema_nests_dotted_columns() {
+    let schema = json_schema::<Trade>("Trade").unwrap();
+    assert_eq!(
+        schema,
+        concat!(
+            "{\"$schema\":\"https://json-schema.org/draft/2020-12/schema\",\"title\":\"Trade\",",
+            "\"type\":\"object\",\"properties\":{",
+            "\"symbol\":{\"type\":\"string\",\"description\":\"Ticker symbol.\"},",
+            "\"price\":{\"type\":\"number\"},",
+            "\"size\":{\"type\":[\"integer\",\"null\"]},",
+            "\"ts\":{\"type\":\"string\",\"format\":\"date-time\"},",
+            "\"flags\":{\"type\":\"array\",\"items\":{\"type\":[\"boolean\",\"null\"]}},",
+            "\"quotes\":{\"type\":\"object\",\"properties\":{",
+            "\"venue\":{\"type\":\"array\",\"items\":{\"type\":\"string\"}},",
+            "\"bid\":{\"type\":\"array\",\"items\":{\"type\":[\"number\",\"null\"]}}",
+            "},\"required\":[\"venue\",\"bid\"],\"additionalProperties\":false}",
+            "},\"required\":[\"symbol\",\"price\",\"ts\",\"flags\",\"quotes\"],",
+            "\"additionalProperties\":false}",
+        )
+    );
+}
+
+#[test]
+fn avro_schema_uses_nullable_unions_and_logical_types() {
+    let schema = avro_schema::<Trade>("Trade").unwrap();
+    assert_eq!(
+        schema,
+        concat!(
+            "{\"type\":\"record\",\"name\":\"Trade\",\"fields\":[",
+            "{\"name\":\"symbol\",\"type\":\"string\",\"doc\":\"Ticker symbol.\"},",
+            "{\"name\":\"price\",\"type\":{\"type\":\"bytes\",\"logicalType\":\"decimal\",\"precision\":12,\"scale\":4}},",
+            "{\"name\":\"size\",\"type\":[\"null\",{\"type\":\"bytes\",\"logicalType\":\"decimal\",\"precision\":20,\"scale\":0}],\"default\":null},",
+            "{\"name\":\"ts\",\"type\":{\"type\":\"long\",\"logicalType\":\"local-timestamp-micros\"}},",
+            "{\"name\":\"flags\",\"type\":{\"type\":\"array\",\"items\":[\"null\",\"boolean\"]}},",
+            "{\"name\":\"quotes\",\"type\":{\"type\":\"record\",\"name\":\"Trade_quotes\",\"fields\":[",
+            "{\"name\":\"venue\",\"type\":{\"type\":\"array\",\"items\":\"string\"}},",
+            "{\"name\":\"bid\",\"type\":{\"type\":\"array\",\"items\":[\"null\",\"double\"]}}",
+            "]}}",
+            "]}",
+        )
+    );
+}
+
+#[derive(ToDataFrame)]
+struct Sample {
+    coords: [f64; 3],
+    offset: Option<[i32; 2]>,
+}
+
+#[test]
+fn arrays_export_with_their_width() {
+    assert_eq!(
+        sql_ddl::<Sample>("samples", SqlDialect::DuckDb).unwrap(),
+        "CREATE TABLE \"samples\" (\n    \"coords\" DOUBLE[3] NOT NULL,\n    \"offset\" INTEGER[2]\n);\n"
+    );
+    assert_eq!(
+        sql_ddl::<Sample>("samples", SqlDialect::ClickHouse).unwrap(),
+        "CREATE TABLE `samples` (\n    `coords` Array(Float64),\n    `offset` Array(Int32)\n)\nENGINE = MergeTree\nORDER BY tuple();\n"
+    );
+    assert!(json_schema::<Sample>("Sample").unwrap().contains(
+        "\"coords\":{\"type\":\"array\",\"minItems\":3,\"maxItems\":3,\"items\":{\"type\":\"number\"}}"
+    ));
+    let columns = column_descriptors::<Sample>().unwrap();
+    assert_eq!(columns[1].nullability, [true, false]);
+}
@@ -0,0 +1,90 @@
+use crate::core::dataframe::ToDataFrame;
+use chrono::{DateTime, Utc};
+use df_derive::ToDataFrame;
+
+#[derive(ToDataFrame)]
+#[df_derive(schema_lock = "tests/schemas/quote.json")]
+struct Quote {
+    venue: String,
+    bid: Option<f64>,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(schema_lock = "tests/schemas/trade.json")]
+struct Trade {
+    symbol: String,
+    #[df_derive(time_unit = "us")]
+    ts: DateTime<Utc>,
+    size: Option<u64>,
+    flags: Vec<Option<bool>>,
+    quotes: Vec<Quote>,
+    spread: (f64, f64),
+    legs: [f64; 2],
+    venues: [Quote; 2],
+}
+
+fn lock_lines(path: &str) -> Vec<String> {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    std::fs::read_to_string(path)
+        .expect("lock file should be checked in")
+        .lines()
+        .map(|line| line.trim().trim_end_matches(',').to_owned())
+        .collect()
+}
+
+#[test]
+fn lock_files_record_each_derived_column() {
+    // The derives above only compile while their lock files match, so these
+    // assertions pin the checked-in format.
+    let trade = lock_lines("tests/schemas/trade.json");
+    for expected in [
+        r#""type": "Trade""#,
+        r#"{"name": "symbol", "dtype": "String", "nullable": [false]}"#,
+        r#"{"name": "ts", "dtype": "Datetime(us)", "nullable": [false]}"#,
+        r#"{"name": "size", "dtype": "UInt64", "nullable": [true]}"#,
+        r#"{"name": "flags", "dtype": "List(Boolean)", "nullable": [false, true]}"#,
+        r#"{"name": "quotes", "nested": "Quote", "list_depth": 1, "nullable": [false, false]}"#,
+        r#"{"name": "spread.field_0", "dtype": "Float64", "nullable": [false]}"#,
+        r#"{"name": "spread.field_1", "dtype": "Float64", "nullable": [false]}"#,
+        r#"{"name": "legs", "dtype": "Array(Float64, 2)", "nullable": [false, false]}"#,
+        r#"{"name": "venues", "nested": "Quote", "list_depth": 1, "array_lens": ["2"], "nullable": [false, false]}"#,
+    ] {
+        assert!(
+            trade.iter().any(|line| line == expected),
+            "{expected}\n{trade:#?}"
+        );
+    }
+
+    let quote = lock_lines("tests/schemas/quote.json");
+    assert!(
+        quote
+            .iter()
+            .any(|line| line == r#"{"name": "bid", "dtype": "Float64", "nullable": [true]}"#),
+        "{quote:#?}"
+    );
+
+    let trade = Trade {
+        symbol: "AAPL".to_owned(),
+        ts: DateTime::<Utc>::UNIX_EPOCH,
+        size: None,
+        flags: vec![Some(true), None],
+        quotes: vec![Quote {
+            venue: "XNAS".to_owned(),
+            bid: Some(1.5),
+        }],
+        spread: (0.1, 0.2),
+        legs: [1.0, 2.0],
+        venues: [
+            Quote {
+                venue: "XNAS".to_owned(),
+                bid: None,
+            },
+            Quote {
+                venue: "XNYS".to_owned(),
+                bid: Some(2.5),
+            },
+        ],
+    };
+    let df = trade.to_dataframe().unwrap();
+    assert_eq!(df.width(), 11);
+}
@@ -0,0 +1,173 @@
+use crate::core::dataframe::schema_diff::{ColumnChange, Compatibility, SchemaDiff};
+use df_derive::ToDataFrame;
+use polars::prelude::{DataType, Field, Schema, TimeUnit};
+
+#[derive(ToDataFrame)]
+struct TradeV1 {
+    symbol: String,
+    px: f64,
+    size: i32,
+    #[df_derive(time_unit = "ms")]
+    ts: chrono::NaiveDateTime,
+    bid: Option<f64>,
+    venue: String,
+}
+
+#[derive(ToDataFrame)]
+struct TradeV2 {
+    symbol: String,
+    price: f64,
+    size: i64,
+    #[df_derive(time_unit = "us")]
+    ts: chrono::NaiveDateTime,
+    bid: f64,
+    ask: Option<f32>,
+}
+
+#[derive(ToDataFrame)]
+struct Narrowed {
+    symbol: String,
+    size: i16,
+    qty: u32,
+}
+
+#[test]
+fn between_derived_types_classifies_each_change() {
+    let diff = SchemaDiff::between::<TradeV1, TradeV2>().unwrap();
+    assert_eq!(
+        diff.changes(),
+        &[
+            ColumnChange::Widened {
+                name: "size".to_owned(),
+                from: DataType::Int32,
+                to: DataType::Int64,
+            },
+            ColumnChange::Widened {
+                name: "ts".to_owned(),
+                from: DataType::Datetime(TimeUnit::Milliseconds, None),
+                to: DataType::Datetime(TimeUnit::Microseconds, None),
+            },
+            ColumnChange::NowRequired {
+                name: "bid".to_owned(),
+            },
+            ColumnChange::RenameCandidate {
+                from: "px".to_owned(),
+                to: "price".to_owned(),
+                dtype: DataType::Float64,
+            },
+            ColumnChange::Added {
+                name: "ask".to_owned(),
+                dtype: DataType::Float32,
+                nullable: true,
+            },
+            ColumnChange::Removed {
+                name: "venue".to_owned(),
+                dtype: DataType::String,
+            },
+        ]
+    );
+    assert!(!diff.is_compatible());
+    assert_eq!(diff.breaking_changes().count(), 2);
+    assert_eq!(
+        diff.to_string(),
+        "schema diff: 2 breaking, 4 compatible\n  \
+         [compatible] widened `size` from Int32 to Int64\n  \
+         [compatible] widened `ts` from Datetime('ms') to Datetime('μs')\n  \
+         [breaking  ] `bid` is now required\n  \
+         [breaking  ] possibly renamed `px` -> `price` (Float64)\n  \
+         [compatible] added `ask` (Float32, nullable)\n  \
+         [compatible] removed `venue` (String)"
+    );
+    let error = diff.ensure_compatible().unwrap_err().to_string();
+    assert!(
+        error.contains("possibly renamed `px` -> `price`"),
+        "{error}"
+    );
+
+    let same = SchemaDiff::between::<TradeV2, TradeV2>().unwrap();
+    assert!(same.is_empty() && same.is_compatible());
+    assert_eq!(same.to_string(), "schema diff: no changes");
+    same.ensure_compatible().unwrap();
+}
+
+#[test]
+fn against_stored_schema_flags_narrowing_and_missing_required_columns() {
+    let stored = Schema::from_iter([
+        Field::new("symbol".into(), DataType::String),
+        Field::new("size".into(), DataType::Int64),
+        Field::new("extra".into(), DataType::Boolean),
+        Field::new("legacy".into(), DataType::Boolean),
+    ]);
+    let diff = SchemaDiff::against::<Narrowed>(&stored).unwrap();
+    assert_eq!(
+        diff.changes(),
+        &[
+            ColumnChange::DtypeChanged {
+                name: "size".to_owned(),
+                from: DataType::Int64,
+                to: DataType::Int16,
+            },
+            ColumnChange::Added {
+                name: "qty".to_owned(),
+                dtype: DataType::UInt32,
+                nullable: false,
+            },
+            ColumnChange::Removed {
+                name: "extra".to_owned(),
+                dtype: DataType::Boolean,
+            },
+            ColumnChange::Removed {
+                name: "legacy".to_owned(),
+                dtype: DataType::Boolean,
+            },
+        ]
+    );
+    let compatibility: Vec<Compatibility> = diff
+        .changes()
+        .iter()
+        .map(ColumnChange::compatibility)
+        .collect();
+    assert_eq!(
+        compatibility,
+        [
+            Compatibility::Breaking,
+            Compatibility::Breaking,
+            Compatibility::Compatible,
+            Compatibility::Compatible,
+        ]
+    );
+}
+
+#[test]
+fn stored_dtypes_that_widen_into_the_derived_schema_are_compatible() {
+    #[derive(ToDataFrame)]
+    struct Target {
+        a: i64,
+        b: f64,
+        c: Vec<i64>,
+        #[df_derive(decimal(precision = 12, scale = 4))]
+        d: rust_decimal::Decimal,
+        e: Option<f64>,
+    }
+
+    let stored = Schema::from_iter([
+        Field::new("a".into(), DataType::UInt32),
+        Field::new("b".into(), DataType::Int32),
+        Field::new("c".into(), DataType::List(Box::new(DataType::Int8))),
+        Field::new("d".into(), DataType::Decimal(10, 2)),
+        Field::new("e".into(), DataType::Null),
+    ]);
+    let diff = SchemaDiff::against::<Target>(&stored).unwrap();
+    assert_eq!(diff.changes().len(), 5);
+    assert!(diff.is_compatible(), "{diff}");
+
+    let narrowing = Schema::from_iter([
+        Field::new("a".into(), DataType::UInt64),
+        Field::new("b".into(), DataType::Int64),
+        Field::new("c".into(), DataType::List(Box::new(DataType::Float64))),
+        Field::new("d".into(), DataType::Decimal(12, 6)),
+        Field::new("e".into(), DataType::Float64),
+    ]);
+    let diff = SchemaDiff::against::<Target>(&narrowing).unwrap();
+    assert_eq!(diff.breaking_changes().count(), 4, "{diff}");
+}
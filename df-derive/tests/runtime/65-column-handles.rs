@@ -0,0 +1,122 @@
+use crate::core::dataframe::ToDataFrameVec;
+use crate::core::dataframe::columns::ColumnRef;
+use df_derive::ToDataFrame;
+use polars::prelude::{Expr, IntoLazy, col};
+
+#[derive(ToDataFrame)]
+struct Quote {
+    venue: String,
+    close: f64,
+}
+
+#[derive(ToDataFrame)]
+struct Tagged<T> {
+    tag: String,
+    payload: T,
+}
+
+#[derive(ToDataFrame)]
+struct Trade {
+    symbol: String,
+    price: f64,
+    r#type: String,
+    quotes: Vec<Quote>,
+    best: Option<Quote>,
+    spread: (f64, f64),
+    tagged: Tagged<Quote>,
+}
+
+#[derive(ToDataFrame)]
+struct Flat(u32, Option<String>);
+
+fn trade() -> Trade {
+    Trade {
+        symbol: "AAPL".to_owned(),
+        price: 187.5,
+        r#type: "limit".to_owned(),
+        quotes: vec![Quote {
+            venue: "XNAS".to_owned(),
+            close: 187.0,
+        }],
+        best: None,
+        spread: (0.1, 0.2),
+        tagged: Tagged {
+            tag: "t".to_owned(),
+            payload: Quote {
+                venue: "XNYS".to_owned(),
+                close: 186.0,
+            },
+        },
+    }
+}
+
+#[test]
+fn column_names_are_listed_for_flat_types() {
+    assert_eq!(Quote::COLUMN_NAMES, ["venue", "close"]);
+    assert_eq!(Flat::COLUMN_NAMES, ["field_0", "field_1"]);
+    assert_eq!(Quote::column_names().unwrap(), Quote::COLUMN_NAMES);
+}
+
+#[test]
+fn column_names_include_nested_columns() {
+    let df = [trade()].as_slice().to_dataframe().unwrap();
+    let names: Vec<String> = df
+        .get_column_names()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(Trade::column_names().unwrap(), names);
+    assert_eq!(
+        Tagged::<Quote>::column_names().unwrap(),
+        ["tag", "payload.venue", "payload.close"]
+    );
+}
+
+#[test]
+fn handles_name_every_flattened_column() {
+    let cols = Trade::cols();
+    let names: Vec<String> = [
+        cols.symbol(),
+        cols.price(),
+        cols.r#type(),
+        cols.quotes().venue(),
+        cols.quotes().close(),
+        cols.best().close(),
+        cols.spread_field_0(),
+        cols.spread_field_1(),
+        cols.tagged().tag(),
+        cols.tagged().payload().close(),
+    ]
+    .iter()
+    .map(|column| column.name().to_owned())
+    .collect();
+
+    let df = [trade()].as_slice().to_dataframe().unwrap();
+    for name in &names {
+        assert!(df.column(name).is_ok(), "missing column `{name}`");
+    }
+    assert_eq!(names[3], "quotes.venue");
+    assert_eq!(names[9], "tagged.payload.close");
+    assert_eq!(Flat::cols().field_1().to_string(), "field_1");
+}
+
+#[test]
+fn handles_convert_to_lazy_expressions() {
+    let df = [trade()].as_slice().to_dataframe().unwrap();
+    let price: ColumnRef = Trade::cols().price();
+    let selected = df
+        .lazy()
+        .select([price.expr(), Trade::cols().best().venue().into()])
+        .collect()
+        .unwrap();
+    let names: Vec<&str> = selected
+        .get_column_names()
+        .into_iter()
+        .map(|name| name.as_str())
+        .collect();
+    assert_eq!(names, [Trade::cols().price().name(), "best.venue"]);
+    assert_eq!(
+        Expr::from(Trade::cols().quotes().close()),
+        col("quotes.close")
+    );
+}
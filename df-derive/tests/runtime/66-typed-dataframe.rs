@@ -0,0 +1,114 @@
+use crate::core::dataframe::{ToDataFrameVec, TypedDataFrame};
+use chrono::{DateTime, NaiveTime, Utc};
+use df_derive::ToDataFrame;
+use polars::prelude::{ChunkAgg, DataFrame, DataType, NamedFrom, PolarsError, Series};
+use rust_decimal::Decimal;
+
+#[derive(ToDataFrame)]
+struct Venue {
+    code: String,
+}
+
+#[derive(ToDataFrame)]
+struct Trade {
+    symbol: String,
+    price: f64,
+    size: Option<u64>,
+    fills: Vec<i32>,
+    at: DateTime<Utc>,
+    opened: NaiveTime,
+    #[df_derive(decimal(precision = 12, scale = 2))]
+    fee: Decimal,
+    venue: Venue,
+}
+
+fn trades() -> Vec<Trade> {
+    let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    vec![
+        Trade {
+            symbol: "AAPL".to_owned(),
+            price: 187.5,
+            size: Some(100),
+            fills: vec![60, 40],
+            at,
+            opened: NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+            fee: Decimal::new(125, 2),
+            venue: Venue {
+                code: "XNAS".to_owned(),
+            },
+        },
+        Trade {
+            symbol: "MSFT".to_owned(),
+            price: 410.25,
+            size: None,
+            fills: vec![],
+            at,
+            opened: NaiveTime::from_hms_opt(9, 31, 0).unwrap(),
+            fee: Decimal::new(50, 2),
+            venue: Venue {
+                code: "XNYS".to_owned(),
+            },
+        },
+    ]
+}
+
+#[test]
+fn typed_accessors_return_chunked_columns() {
+    let typed = TypedDataFrame::from_rows(&trades()).unwrap();
+
+    let symbols: Vec<Option<&str>> = typed.symbol().into_iter().collect();
+    assert_eq!(symbols, [Some("AAPL"), Some("MSFT")]);
+    assert_eq!(typed.price().sum(), Some(597.75));
+    assert_eq!(typed.size().get(1), None);
+    assert_eq!(typed.fills().get_as_series(0).unwrap().len(), 2);
+    assert_eq!(typed.at().physical().get(0), Some(1_700_000_000_000));
+    assert_eq!(typed.opened().len(), 2);
+    assert_eq!(typed.fee().physical().get(0), Some(125));
+
+    // DataFrame methods stay available through the second deref.
+    assert_eq!(typed.height(), 2);
+    assert!(typed.column("venue.code").is_ok());
+}
+
+#[test]
+fn validated_frames_are_reordered_into_schema_order() {
+    let df = trades().as_slice().to_dataframe().unwrap();
+    let mut names: Vec<_> = df.get_column_names_owned();
+    names.reverse();
+    let reversed = df.select(names).unwrap();
+
+    let typed = TypedDataFrame::<Trade>::try_from_dataframe(reversed).unwrap();
+    assert_eq!(typed.get_column_names()[0].as_str(), "symbol");
+    assert_eq!(typed.price().get(1), Some(410.25));
+
+    let back: DataFrame = typed.into_inner();
+    assert!(back.equals_missing(&df));
+}
+
+#[test]
+fn mismatched_frames_are_rejected() {
+    let df = DataFrame::new_infer_height(vec![
+        Series::new("symbol".into(), ["AAPL"]).into(),
+        Series::new("price".into(), [187_i64]).into(),
+        Series::new("extra".into(), [true]).into(),
+    ])
+    .unwrap();
+
+    let err = TypedDataFrame::<Trade>::try_from(df).unwrap_err();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)), "{err}");
+    let message = err.to_string();
+    assert!(
+        message.contains("column `price` is Int64, expected Float64"),
+        "{message}"
+    );
+    assert!(message.contains("missing column `fills`"), "{message}");
+    assert!(message.contains("unexpected column `extra`"), "{message}");
+}
+
+#[test]
+fn empty_row_slices_keep_the_schema() {
+    let typed = TypedDataFrame::<Trade>::from_rows(&[]).unwrap();
+    assert_eq!(typed.height(), 0);
+    assert_eq!(typed.price().dtype(), &DataType::Float64);
+    assert_eq!(typed.clone().symbol().len(), 0);
+}
@@ -0,0 +1,191 @@
+use crate::core::dataframe::ToDataFrameVec;
+use chrono::{DateTime, Utc};
+use df_derive::ToDataFrame;
+use polars::prelude::{DataFrame, NamedFrom, PolarsError, Series};
+use rust_decimal::Decimal;
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct Venue {
+    code: String,
+    lot: u32,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct Trade {
+    symbol: String,
+    note: Option<String>,
+    price: f64,
+    size: Option<i64>,
+    tags: Vec<String>,
+    fills: Option<Vec<Option<i32>>>,
+    at: DateTime<Utc>,
+    #[df_derive(decimal(precision = 12, scale = 2))]
+    fee: Decimal,
+    spread: (f64, f64),
+    venue: Venue,
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct Tick(u64, bool);
+
+fn trades() -> Vec<Trade> {
+    let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    vec![
+        Trade {
+            symbol: "AAPL".to_owned(),
+            note: Some("open".to_owned()),
+            price: 187.5,
+            size: Some(100),
+            tags: vec!["tech".to_owned(), "us".to_owned()],
+            fills: Some(vec![Some(60), None]),
+            at,
+            fee: Decimal::new(125, 2),
+            spread: (0.1, 0.2),
+            venue: Venue {
+                code: "XNAS".to_owned(),
+                lot: 100,
+            },
+        },
+        Trade {
+            symbol: "MSFT".to_owned(),
+            note: None,
+            price: 410.25,
+            size: None,
+            tags: vec![],
+            fills: None,
+            at,
+            fee: Decimal::new(50, 2),
+            spread: (0.3, 0.4),
+            venue: Venue {
+                code: "XNYS".to_owned(),
+                lot: 10,
+            },
+        },
+    ]
+}
+
+#[test]
+fn views_borrow_strings_and_read_scalars() {
+    let df = trades().as_slice().to_dataframe().unwrap();
+    let views: Vec<TradeView<'_>> = Trade::iter_views(&df).unwrap().collect();
+    assert_eq!(views.len(), 2);
+
+    let first = &views[0];
+    assert_eq!(first.symbol, "AAPL");
+    assert_eq!(first.note, Some("open"));
+    assert!((first.price - 187.5).abs() < f64::EPSILON);
+    assert_eq!(first.size, Some(100));
+    assert_eq!(first.at, 1_700_000_000_000);
+    assert_eq!(first.fee, 125);
+    assert!((first.spread_field_1 - 0.2).abs() < f64::EPSILON);
+    assert_eq!(first.venue.code, "XNAS");
+    assert_eq!(first.venue.lot, 100);
+
+    // The borrowed `&str` points into the frame's own buffers.
+    let stored = df.column("symbol").unwrap().str().unwrap().get(0).unwrap();
+    assert!(std::ptr::eq(first.symbol, stored));
+
+    let second = &views[1];
+    assert_eq!(second.note, None);
+    assert_eq!(second.size, None);
+    assert_eq!(second.venue.code, "XNYS");
+}
+
+#[test]
+fn list_fields_are_borrowed_iterators() {
+    let df = trades().as_slice().to_dataframe().unwrap();
+    let mut views = Trade::iter_views(&df).unwrap();
+
+    let first = views.next().unwrap();
+    assert_eq!(first.tags.len(), 2);
+    assert_eq!(first.tags.clone().collect::<Vec<_>>(), ["tech", "us"]);
+    let fills: Vec<Option<i32>> = first.fills.unwrap().collect();
+    assert_eq!(fills, [Some(60), None]);
+
+    let second = views.next().unwrap();
+    assert_eq!(second.tags.count(), 0);
+    assert!(second.fills.is_none());
+    assert!(views.next().is_none());
+}
+
+#[test]
+fn views_cover_tuple_structs_and_random_access() {
+    let ticks = vec![Tick(1, true), Tick(2, false), Tick(3, true)];
+    let df = ticks.as_slice().to_dataframe().unwrap();
+    let views = Trade::iter_views(&trades().as_slice().to_dataframe().unwrap())
+        .unwrap()
+        .len();
+    assert_eq!(views, 2);
+
+    let ticks = Tick::iter_views(&df).unwrap();
+    assert_eq!(ticks.len(), 3);
+    let last = ticks.get(2).unwrap();
+    assert_eq!(last.field_0, 3);
+    assert!(last.field_1);
+    assert!(ticks.get(3).is_none());
+    let ids: Vec<u64> = ticks.rev().map(|tick| tick.field_0).collect();
+    assert_eq!(ids, [3, 2, 1]);
+}
+
+#[test]
+fn frames_are_checked_once_before_viewing() {
+    let df = DataFrame::new_infer_height(vec![
+        Series::new("field_0".into(), [Some(1_u64), None]).into(),
+        Series::new("field_1".into(), [true, false]).into(),
+    ])
+    .unwrap();
+    let err = Tick::iter_views(&df).err().unwrap();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)), "{err}");
+    assert!(
+        err.to_string()
+            .contains("column `field_0` of a non-`Option` field has 1 null(s)"),
+        "{err}"
+    );
+
+    let df = DataFrame::new_infer_height(vec![
+        Series::new("field_0".into(), [1_i32]).into(),
+        Series::new("extra".into(), [1_i32]).into(),
+    ])
+    .unwrap();
+    let message = Tick::iter_views(&df).err().unwrap().to_string();
+    assert!(
+        message.contains("column `field_0` is Int32, expected UInt64"),
+        "{message}"
+    );
+    assert!(message.contains("missing column `field_1`"), "{message}");
+}
+
+#[derive(ToDataFrame)]
+#[df_derive(views)]
+struct Samples {
+    values: Vec<i32>,
+}
+
+#[test]
+fn null_elements_of_non_option_lists_are_rejected() {
+    let values = Series::new(
+        "values".into(),
+        [
+            Series::new("".into(), [Some(1_i32), None]),
+            Series::new("".into(), [Some(3_i32)]),
+        ],
+    );
+    let df = DataFrame::new_infer_height(vec![values.into()]).unwrap();
+    let err = Samples::iter_views(&df).err().unwrap();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)), "{err}");
+    assert!(
+        err.to_string()
+            .contains("column `values` has 1 null element(s) in a non-`Option` list"),
+        "{err}"
+    );
+
+    let df = [Samples { values: vec![1, 2] }].to_dataframe().unwrap();
+    let sums: Vec<i32> = Samples::iter_views(&df)
+        .unwrap()
+        .map(|view| view.values.sum())
+        .collect();
+    assert_eq!(sums, [3]);
+}
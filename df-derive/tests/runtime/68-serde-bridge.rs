@@ -0,0 +1,266 @@
+use crate::core::dataframe::serde::{from_dataframe, to_dataframe, to_dataframe_with_schema};
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use chrono::{DateTime, Utc};
+use df_derive::ToDataFrame;
+use polars::prelude::{DataType, PolarsError, Schema};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(ToDataFrame, Serialize)]
+struct Quote {
+    bid: f64,
+    ask: Option<f64>,
+}
+
+#[derive(ToDataFrame, Serialize)]
+struct Trade {
+    symbol: String,
+    size: Option<u64>,
+    tags: Vec<String>,
+    spread: (i32, String),
+    best: Option<Quote>,
+    quotes: Vec<Quote>,
+    at: DateTime<Utc>,
+    #[df_derive(decimal(precision = 12, scale = 2))]
+    fee: Decimal,
+}
+
+fn trades() -> Vec<Trade> {
+    let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    vec![
+        Trade {
+            symbol: "AAPL".to_owned(),
+            size: Some(100),
+            tags: vec!["tech".to_owned()],
+            spread: (1, "a".to_owned()),
+            best: None,
+            quotes: vec![],
+            at,
+            fee: Decimal::new(125, 2),
+        },
+        Trade {
+            symbol: "MSFT".to_owned(),
+            size: None,
+            tags: vec![],
+            spread: (2, "b".to_owned()),
+            best: Some(Quote {
+                bid: 1.5,
+                ask: None,
+            }),
+            quotes: vec![
+                Quote {
+                    bid: 1.0,
+                    ask: Some(1.25),
+                },
+                Quote {
+                    bid: 2.0,
+                    ask: None,
+                },
+            ],
+            at,
+            fee: Decimal::new(50, 2),
+        },
+    ]
+}
+
+#[test]
+fn serde_frames_match_derived_frames_given_the_derived_schema() {
+    let rows = trades();
+    let derived = rows.as_slice().to_dataframe().unwrap();
+    let schema: Schema = Trade::schema()
+        .unwrap()
+        .into_iter()
+        .map(|(name, dtype)| (name.into(), dtype))
+        .collect();
+
+    let bridged = to_dataframe_with_schema(&rows, &schema).unwrap();
+    assert_eq!(bridged.schema(), derived.schema());
+    assert!(bridged.equals_missing(&derived), "{bridged}\n{derived}");
+}
+
+#[test]
+fn inferred_frames_follow_derive_naming() {
+    let rows = trades();
+    let df = to_dataframe(&rows).unwrap();
+    let derived = rows.as_slice().to_dataframe().unwrap();
+
+    // Same columns in the same order, even though the first row's `best` and
+    // `quotes` hold no record to take nested names from.
+    assert_eq!(df.get_column_names(), derived.get_column_names());
+    assert_eq!(df.column("size").unwrap().dtype(), &DataType::UInt64);
+    assert_eq!(
+        df.column("spread.field_0").unwrap().dtype(),
+        &DataType::Int32
+    );
+    assert_eq!(
+        df.column("quotes.ask").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::Float64))
+    );
+    // String-serialized values stay strings without a schema.
+    assert_eq!(df.column("fee").unwrap().dtype(), &DataType::String);
+
+    for name in ["best.bid", "quotes.bid", "quotes.ask", "tags"] {
+        let inferred = df.column(name).unwrap();
+        let expected = derived.column(name).unwrap();
+        assert!(
+            inferred
+                .as_materialized_series()
+                .equals_missing(expected.as_materialized_series()),
+            "{name}: {inferred:?} vs {expected:?}"
+        );
+    }
+}
+
+#[derive(ToDataFrame, Serialize, Deserialize, Debug, PartialEq)]
+struct Level {
+    size: u64,
+    price: Option<f64>,
+}
+
+#[derive(ToDataFrame, Serialize, Deserialize, Debug, PartialEq)]
+struct Book {
+    id: u32,
+    marks: BTreeMap<String, f64>,
+    levels: BTreeMap<u32, Level>,
+}
+
+fn books() -> Vec<Book> {
+    vec![
+        Book {
+            id: 1,
+            marks: BTreeMap::from([("open".to_owned(), 1.5)]),
+            levels: BTreeMap::from([(
+                1,
+                Level {
+                    size: 10,
+                    price: Some(99.5),
+                },
+            )]),
+        },
+        Book {
+            id: 2,
+            marks: BTreeMap::from([("close".to_owned(), 2.5), ("high".to_owned(), 3.0)]),
+            levels: BTreeMap::new(),
+        },
+        Book {
+            id: 3,
+            marks: BTreeMap::new(),
+            levels: BTreeMap::from([
+                (
+                    2,
+                    Level {
+                        size: 5,
+                        price: None,
+                    },
+                ),
+                (
+                    4,
+                    Level {
+                        size: 7,
+                        price: Some(101.0),
+                    },
+                ),
+            ]),
+        },
+    ]
+}
+
+#[test]
+fn map_fields_round_trip_as_entry_lists() {
+    let rows = books();
+    let derived = rows.as_slice().to_dataframe().unwrap();
+    let schema: Schema = Book::schema()
+        .unwrap()
+        .into_iter()
+        .map(|(name, dtype)| (name.into(), dtype))
+        .collect();
+
+    let bridged = to_dataframe_with_schema(&rows, &schema).unwrap();
+    assert_eq!(bridged.schema(), derived.schema());
+    assert!(bridged.equals_missing(&derived), "{bridged}\n{derived}");
+
+    // Rows with different keys still share one column per map field.
+    let inferred = to_dataframe(&rows).unwrap();
+    assert_eq!(inferred.get_column_names(), ["id", "marks", "levels"]);
+    assert!(inferred.equals_missing(&derived), "{inferred}\n{derived}");
+
+    assert_eq!(from_dataframe::<Book>(&derived).unwrap(), rows);
+}
+
+#[derive(Serialize)]
+struct Tagged {
+    id: u32,
+    #[serde(flatten)]
+    extra: BTreeMap<String, i64>,
+    kind: Kind,
+}
+
+#[derive(Serialize)]
+enum Kind {
+    Buy,
+    #[allow(dead_code)]
+    Limit(f64),
+}
+
+#[test]
+fn maps_flatten_into_columns_and_unit_variants_into_strings() {
+    let rows = vec![
+        Tagged {
+            id: 1,
+            extra: BTreeMap::from([("a".to_owned(), 10)]),
+            kind: Kind::Buy,
+        },
+        Tagged {
+            id: 2,
+            extra: BTreeMap::from([("a".to_owned(), 20), ("b".to_owned(), 30)]),
+            kind: Kind::Buy,
+        },
+    ];
+    let df = to_dataframe(&rows).unwrap();
+    assert_eq!(df.get_column_names(), ["id", "a", "kind", "b"]);
+    assert_eq!(df.column("b").unwrap().null_count(), 1);
+    assert_eq!(
+        df.column("kind").unwrap().str().unwrap().get(0),
+        Some("Buy")
+    );
+}
+
+#[test]
+fn unsupported_rows_report_the_row() {
+    let rows = vec![
+        Tagged {
+            id: 1,
+            extra: BTreeMap::new(),
+            kind: Kind::Buy,
+        },
+        Tagged {
+            id: 2,
+            extra: BTreeMap::new(),
+            kind: Kind::Limit(1.0),
+        },
+    ];
+    let err = to_dataframe(&rows).unwrap_err();
+    assert!(matches!(err, PolarsError::ComputeError(_)), "{err}");
+    assert!(
+        err.to_string()
+            .contains("row 1: enum variant `Kind::Limit` carries data"),
+        "{err}"
+    );
+
+    let err = to_dataframe(&[1_u32, 2]).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("row 0: serializes as a scalar, expected a struct, tuple or map"),
+        "{err}"
+    );
+
+    let schema: Schema = std::iter::once(("id".into(), DataType::UInt32)).collect();
+    let rows = vec![(1_u32, 2_u32)];
+    let err = to_dataframe_with_schema(&rows, &schema).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("row 0: column `field_0` is not in the schema"),
+        "{err}"
+    );
+}
@@ -0,0 +1,188 @@
+use crate::core::dataframe::ToDataFrameVec;
+use crate::core::dataframe::serde::{FrameDeserializer, from_dataframe};
+use chrono::{DateTime, NaiveDate, Utc};
+use df_derive::ToDataFrame;
+use polars::prelude::{DataFrame, DataType, NamedFrom, PolarsError, Series, TimeUnit};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+#[derive(ToDataFrame, Deserialize, Debug, PartialEq)]
+struct Quote {
+    bid: f64,
+    ask: Option<f64>,
+}
+
+#[derive(ToDataFrame, Deserialize, Debug, PartialEq)]
+struct Trade {
+    symbol: String,
+    size: Option<u64>,
+    tags: Vec<String>,
+    spread: (i32, String),
+    best: Option<Quote>,
+    quotes: Vec<Quote>,
+    at: DateTime<Utc>,
+    day: NaiveDate,
+    #[df_derive(decimal(precision = 12, scale = 2))]
+    fee: Decimal,
+}
+
+fn trades() -> Vec<Trade> {
+    let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    let day = NaiveDate::from_ymd_opt(2023, 11, 14).unwrap();
+    vec![
+        Trade {
+            symbol: "AAPL".to_owned(),
+            size: Some(100),
+            tags: vec!["tech".to_owned(), "us".to_owned()],
+            spread: (1, "a".to_owned()),
+            best: None,
+            quotes: vec![],
+            at,
+            day,
+            fee: Decimal::new(125, 2),
+        },
+        Trade {
+            symbol: "MSFT".to_owned(),
+            size: None,
+            tags: vec![],
+            spread: (2, "b".to_owned()),
+            best: Some(Quote {
+                bid: 1.5,
+                ask: None,
+            }),
+            quotes: vec![
+                Quote {
+                    bid: 1.0,
+                    ask: Some(1.25),
+                },
+                Quote {
+                    bid: 2.0,
+                    ask: None,
+                },
+            ],
+            at,
+            day,
+            fee: Decimal::new(-5, 2),
+        },
+    ]
+}
+
+#[test]
+fn derived_frames_deserialize_back_into_rows() {
+    let rows = trades();
+    let df = rows.as_slice().to_dataframe().unwrap();
+    // Derived `DateTime<Utc>` columns hold UTC instants without a zone.
+    let frame = FrameDeserializer::new(&df)
+        .unwrap()
+        .naive_datetimes_as_utc();
+    let back = Vec::<Trade>::deserialize(frame).unwrap();
+    assert_eq!(back, rows);
+
+    let err = from_dataframe::<Trade>(&df).unwrap_err().to_string();
+    assert!(err.starts_with("row 0, column `at`:"), "{err}");
+}
+
+#[derive(Deserialize)]
+struct Symbol<'a> {
+    symbol: &'a str,
+    spread: (i32, &'a str),
+}
+
+#[derive(ToDataFrame, Deserialize, Debug, PartialEq)]
+struct Tick(u64, bool);
+
+#[test]
+fn strings_borrow_from_the_frame_and_tuples_read_field_columns() {
+    let df = trades().as_slice().to_dataframe().unwrap();
+    let symbols: Vec<Symbol<'_>> = from_dataframe(&df).unwrap();
+    assert_eq!(symbols[1].symbol, "MSFT");
+    assert_eq!(symbols[1].spread, (2, "b"));
+    let stored = df.column("symbol").unwrap().str().unwrap().get(1).unwrap();
+    assert!(std::ptr::eq(symbols[1].symbol, stored));
+
+    let ticks = vec![Tick(1, true), Tick(2, false)];
+    let df = ticks.as_slice().to_dataframe().unwrap();
+    assert_eq!(from_dataframe::<Tick>(&df).unwrap(), ticks);
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct Priced {
+    symbol: String,
+    price: f64,
+}
+
+#[test]
+fn errors_name_the_row_and_column() {
+    let df = DataFrame::new_infer_height(vec![
+        Series::new("symbol".into(), ["AAPL", "MSFT"]).into(),
+        Series::new("price".into(), [Some(1.5), None]).into(),
+    ])
+    .unwrap();
+
+    let err = Vec::<Priced>::deserialize(FrameDeserializer::new(&df).unwrap()).unwrap_err();
+    assert_eq!(err.row(), Some(1));
+    assert_eq!(err.column(), Some("price"));
+    assert!(
+        err.to_string()
+            .starts_with("row 1, column `price`: invalid type: unit value"),
+        "{err}"
+    );
+
+    let df = df.drop("price").unwrap();
+    let err = from_dataframe::<Priced>(&df).unwrap_err();
+    assert!(matches!(err, PolarsError::ComputeError(_)), "{err}");
+    assert!(
+        err.to_string().contains("row 0: missing field `price`"),
+        "{err}"
+    );
+
+    let df = DataFrame::new_infer_height(vec![
+        Series::new("a".into(), [1_i32]).into(),
+        Series::new("a.b".into(), [1_i32]).into(),
+    ])
+    .unwrap();
+    let err = from_dataframe::<Priced>(&df).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("column `a.b` is nested under column `a`"),
+        "{err}"
+    );
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct Stamped {
+    at: chrono::NaiveDateTime,
+    day: NaiveDate,
+}
+
+#[test]
+fn out_of_range_temporal_values_are_errors() {
+    let at = |value: i64| {
+        Series::new("at".into(), [value])
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+            .unwrap()
+            .into()
+    };
+    let day = |value: i32| {
+        Series::new("day".into(), [value])
+            .cast(&DataType::Date)
+            .unwrap()
+            .into()
+    };
+
+    let df = DataFrame::new_infer_height(vec![at(i64::MAX), day(0)]).unwrap();
+    let err = Vec::<Stamped>::deserialize(FrameDeserializer::new(&df).unwrap()).unwrap_err();
+    assert_eq!(err.row(), Some(0));
+    assert_eq!(err.column(), Some("at"));
+    assert!(err.to_string().contains("out of range"), "{err}");
+
+    let df = DataFrame::new_infer_height(vec![at(0), day(i32::MAX)]).unwrap();
+    let err = from_dataframe::<Stamped>(&df).unwrap_err();
+    assert!(
+        err.to_string().contains("row 0, column `day`: date"),
+        "{err}"
+    );
+    assert!(err.to_string().contains("out of range"), "{err}");
+}
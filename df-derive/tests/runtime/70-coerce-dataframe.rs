@@ -0,0 +1,137 @@
+use crate::core::dataframe::coerce::Adjustment;
+use crate::core::dataframe::{CoercePolicy, ToDataFrame, ToDataFrameVec};
+use chrono::{DateTime, Utc};
+use df_derive::ToDataFrame;
+use polars::prelude::{DataFrame, DataType, NamedFrom, PolarsError, Series, TimeUnit};
+
+#[derive(ToDataFrame)]
+struct Fill {
+    symbol: String,
+    size: i64,
+    price: f64,
+    at: DateTime<Utc>,
+    venue: Option<String>,
+}
+
+fn foreign_frame() -> DataFrame {
+    DataFrame::new_infer_height(vec![
+        Series::new("at".into(), [1_700_000_000_000_i64, 1_700_000_001_000]).into(),
+        Series::new("price".into(), [1.5_f32, 2.25]).into(),
+        Series::new("symbol".into(), ["AAPL", "MSFT"]).into(),
+        Series::new("size".into(), [100_i32, 200]).into(),
+        Series::new("source".into(), ["a", "b"]).into(),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn coercion_reorders_casts_fills_and_drops() {
+    let coerced = Fill::coerce_dataframe(&foreign_frame(), CoercePolicy::DropExtraColumns).unwrap();
+
+    let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    let expected = [
+        Fill {
+            symbol: "AAPL".to_owned(),
+            size: 100,
+            price: 1.5,
+            at,
+            venue: None,
+        },
+        Fill {
+            symbol: "MSFT".to_owned(),
+            size: 200,
+            price: 2.25,
+            at: at + chrono::Duration::seconds(1),
+            venue: None,
+        },
+    ]
+    .as_slice()
+    .to_dataframe()
+    .unwrap();
+    assert!(coerced.frame().equals_missing(&expected), "{coerced}");
+
+    let datetime = DataType::Datetime(TimeUnit::Milliseconds, None);
+    assert_eq!(
+        coerced.adjustments(),
+        [
+            Adjustment::Cast {
+                name: "size".to_owned(),
+                from: DataType::Int32,
+                to: DataType::Int64,
+            },
+            Adjustment::Cast {
+                name: "price".to_owned(),
+                from: DataType::Float32,
+                to: DataType::Float64,
+            },
+            Adjustment::Cast {
+                name: "at".to_owned(),
+                from: DataType::Int64,
+                to: datetime,
+            },
+            Adjustment::FilledNulls {
+                name: "venue".to_owned(),
+                dtype: DataType::String,
+            },
+            Adjustment::Dropped {
+                name: "source".to_owned(),
+                dtype: DataType::String,
+            },
+            Adjustment::Reordered,
+        ]
+    );
+    assert!(
+        coerced
+            .to_string()
+            .contains("\n  filled missing `venue` (String) with nulls"),
+        "{coerced}"
+    );
+
+    let again = Fill::coerce_dataframe(coerced.frame(), CoercePolicy::default()).unwrap();
+    assert!(again.is_unchanged(), "{again}");
+}
+
+#[test]
+fn unsafe_coercions_are_all_reported() {
+    let mut df = foreign_frame();
+    df.replace("size", Series::new("size".into(), [1.0_f64, 2.0]).into())
+        .unwrap();
+    let df = df.drop("symbol").unwrap();
+
+    let err = Fill::coerce_dataframe(&df, CoercePolicy::RejectExtraColumns).unwrap_err();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)), "{err}");
+    let message = err.to_string();
+    for problem in [
+        "missing required column `symbol` (String)",
+        "column `size` is Float64, which does not cast safely to Int64",
+        "unexpected column `source` (String)",
+    ] {
+        assert!(message.contains(problem), "{message}");
+    }
+    assert!(!message.contains("venue"), "{message}");
+}
+
+#[test]
+fn nulls_in_required_columns_are_reported() {
+    let mut df = foreign_frame();
+    df.replace(
+        "size",
+        Series::new("size".into(), [Some(100_i32), None]).into(),
+    )
+    .unwrap();
+    df.replace(
+        "symbol",
+        Series::new("symbol".into(), [None, Some("MSFT")]).into(),
+    )
+    .unwrap();
+
+    let err = Fill::coerce_dataframe(&df, CoercePolicy::DropExtraColumns).unwrap_err();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)), "{err}");
+    let message = err.to_string();
+    for problem in [
+        "column `symbol` of a non-`Option` field has 1 null(s)",
+        "column `size` of a non-`Option` field has 1 null(s)",
+    ] {
+        assert!(message.contains(problem), "{message}");
+    }
+}
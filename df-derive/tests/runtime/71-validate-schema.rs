@@ -0,0 +1,113 @@
+use crate::core::dataframe::arrow::{ParquetCompression, write_ipc, write_parquet};
+use crate::core::dataframe::validate::{Mismatch, read_ipc_schema, read_parquet_schema};
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{DataType, PolarsError, Schema};
+use rust_decimal::Decimal;
+use std::io::Cursor;
+
+#[derive(ToDataFrame)]
+struct Quote {
+    bid: f64,
+    ask: Option<f64>,
+}
+
+#[derive(ToDataFrame)]
+struct Book {
+    symbol: String,
+    levels: Vec<Vec<u32>>,
+    #[df_derive(decimal(precision = 12, scale = 2))]
+    fee: Decimal,
+    quotes: Vec<Quote>,
+}
+
+fn books() -> Vec<Book> {
+    vec![Book {
+        symbol: "AAPL".to_owned(),
+        levels: vec![vec![1, 2], vec![]],
+        fee: Decimal::new(125, 2),
+        quotes: vec![Quote {
+            bid: 1.0,
+            ask: None,
+        }],
+    }]
+}
+
+fn list(dtype: DataType) -> DataType {
+    DataType::List(Box::new(dtype))
+}
+
+#[test]
+fn derived_frames_and_files_validate() {
+    let mut df = books().as_slice().to_dataframe().unwrap();
+    Book::validate_schema(df.schema()).unwrap();
+
+    let mut parquet = Vec::new();
+    write_parquet::<Book, _>(&mut df, &mut parquet, ParquetCompression::Uncompressed).unwrap();
+    let schema = read_parquet_schema(&mut Cursor::new(&parquet)).unwrap();
+    Book::validate_schema(&schema).unwrap();
+
+    let mut ipc = Vec::new();
+    write_ipc::<Book, _>(&mut df, &mut ipc).unwrap();
+    let schema = read_ipc_schema(&mut Cursor::new(&ipc)).unwrap();
+    Book::validate_schema(&schema).unwrap();
+
+    let err = read_parquet_schema(&mut Cursor::new(&ipc)).unwrap_err();
+    assert!(!err.to_string().is_empty());
+}
+
+#[test]
+fn every_mismatch_is_reported() {
+    let schema = Schema::from_iter([
+        ("levels".into(), list(DataType::UInt32)),
+        ("fee".into(), DataType::Decimal(10, 2)),
+        ("quotes.bid".into(), list(DataType::Float32)),
+        ("quotes.ask".into(), list(DataType::Float64)),
+        ("source".into(), DataType::String),
+    ]);
+
+    let err = Book::validate_schema(&schema).unwrap_err();
+    assert!(err.type_name().ends_with("Book"), "{err}");
+    assert_eq!(
+        err.mismatches(),
+        [
+            Mismatch::MissingColumn {
+                name: "symbol".to_owned(),
+                expected: DataType::String,
+            },
+            Mismatch::ListDepth {
+                name: "levels".to_owned(),
+                expected: 2,
+                found: 1,
+            },
+            Mismatch::Decimal {
+                name: "fee".to_owned(),
+                expected: (12, 2),
+                found: (10, 2),
+            },
+            Mismatch::Dtype {
+                name: "quotes.bid".to_owned(),
+                expected: list(DataType::Float64),
+                found: list(DataType::Float32),
+            },
+            Mismatch::UnexpectedColumn {
+                name: "source".to_owned(),
+                found: DataType::String,
+            },
+        ]
+    );
+    assert_eq!(err.mismatches()[1].column(), Some("levels"));
+
+    let message = err.to_string();
+    assert!(
+        message.contains("\n  column `fee` is Decimal(10, 2), expected Decimal(12, 2)"),
+        "{message}"
+    );
+    assert!(
+        message.contains("\n  column `levels` is nested in 1 list(s), expected 2"),
+        "{message}"
+    );
+
+    let err = PolarsError::from(err);
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)), "{err}");
+}
@@ -0,0 +1,149 @@
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use chrono::{DateTime, NaiveDate, Utc};
+use df_derive::ToDataFrame;
+use polars::prelude::{DataType, TimeUnit};
+use rust_decimal::Decimal;
+
+#[derive(ToDataFrame, Clone)]
+struct Tagged<T> {
+    tag: String,
+    payload: T,
+    maybe: Option<T>,
+    history: Vec<T>,
+}
+
+#[derive(ToDataFrame)]
+struct Concrete {
+    tag: String,
+    payload: f64,
+    maybe: Option<f64>,
+    history: Vec<f64>,
+}
+
+#[derive(ToDataFrame)]
+struct Outer {
+    id: u32,
+    inner: Tagged<String>,
+    inners: Vec<Tagged<i64>>,
+}
+
+fn tagged<T: Clone>(payload: T, maybe: Option<T>) -> Tagged<T> {
+    Tagged {
+        tag: "t".to_owned(),
+        history: vec![payload.clone(); 2],
+        payload,
+        maybe,
+    }
+}
+
+#[test]
+fn primitive_payloads_match_concrete_fields() {
+    let rows = vec![tagged(1.5, Some(2.5)), tagged(3.0, None)];
+    let df = rows.as_slice().to_dataframe().unwrap();
+    let concrete: Vec<Concrete> = rows
+        .iter()
+        .map(|row| Concrete {
+            tag: row.tag.clone(),
+            payload: row.payload,
+            maybe: row.maybe,
+            history: row.history.clone(),
+        })
+        .collect();
+    let expected = concrete.as_slice().to_dataframe().unwrap();
+
+    assert_eq!(
+        df.get_column_names(),
+        ["tag", "payload", "maybe", "history"]
+    );
+    assert!(df.equals_missing(&expected), "{df}\n{expected}");
+    assert_eq!(
+        Tagged::<f64>::schema().unwrap(),
+        Concrete::schema().unwrap()
+    );
+    assert_eq!(
+        Tagged::<f64>::arrow_schema().unwrap(),
+        Concrete::arrow_schema().unwrap()
+    );
+    assert_eq!(rows[0].to_dataframe().unwrap().height(), 1);
+    assert_eq!(
+        Tagged::<f64>::empty_dataframe().unwrap().schema(),
+        expected.schema()
+    );
+}
+
+#[test]
+fn temporal_and_decimal_payloads_use_default_dtypes() {
+    let at = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+    let df = tagged(at, None).to_dataframe().unwrap();
+    assert_eq!(
+        df.column("payload").unwrap().dtype(),
+        &DataType::Datetime(TimeUnit::Milliseconds, None)
+    );
+    assert_eq!(
+        df.column("history").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::Datetime(TimeUnit::Milliseconds, None)))
+    );
+
+    let day = NaiveDate::from_ymd_opt(2023, 11, 14).unwrap();
+    let df = tagged(day, Some(day)).to_dataframe().unwrap();
+    assert_eq!(df.column("maybe").unwrap().dtype(), &DataType::Date);
+
+    let df = tagged(Decimal::new(125, 2), None).to_dataframe().unwrap();
+    assert_eq!(
+        df.column("payload").unwrap().dtype(),
+        &DataType::Decimal(38, 10)
+    );
+    assert_eq!(
+        df.column("payload").unwrap().get(0).unwrap().to_string(),
+        "1.2500000000"
+    );
+}
+
+#[test]
+fn nested_primitive_payloads_are_named_after_the_field() {
+    let outer = Outer {
+        id: 7,
+        inner: tagged("x".to_owned(), None),
+        inners: vec![tagged(1, Some(2))],
+    };
+    let df = outer.to_dataframe().unwrap();
+    assert_eq!(
+        df.get_column_names(),
+        [
+            "id",
+            "inner.tag",
+            "inner.payload",
+            "inner.maybe",
+            "inner.history",
+            "inners.tag",
+            "inners.payload",
+            "inners.maybe",
+            "inners.history",
+        ]
+    );
+    assert_eq!(
+        df.column("inners.payload").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::Int64))
+    );
+    let schema: Vec<String> = Outer::schema()
+        .unwrap()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let names: Vec<&str> = df
+        .get_column_names()
+        .into_iter()
+        .map(|name| name.as_str())
+        .collect();
+    assert_eq!(names, schema);
+}
+
+#[test]
+fn primitive_payloads_have_column_handles() {
+    let cols = Tagged::<f64>::cols();
+    assert_eq!(cols.payload().name(), "payload");
+    assert_eq!(cols.maybe().name(), "maybe");
+    assert_eq!(Tagged::<String>::cols().history().name(), "history");
+    assert_eq!(Outer::cols().inners().payload().name(), "inners.payload");
+    assert_eq!(Tagged::<DateTime<Utc>>::cols().payload().name(), "payload");
+}
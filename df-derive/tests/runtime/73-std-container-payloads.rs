@@ -0,0 +1,241 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::core::dataframe::tuples::{schema_with_prefixes, to_dataframe_with_prefixes};
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::DataType;
+
+#[derive(ToDataFrame, Clone)]
+struct Trade {
+    /// Ticker symbol.
+    symbol: String,
+    price: f64,
+}
+
+#[derive(ToDataFrame, Clone)]
+struct Quote {
+    bid: f64,
+    ask: Option<f64>,
+}
+
+#[derive(ToDataFrame)]
+struct Wrapper<T> {
+    id: u32,
+    payload: T,
+}
+
+#[derive(ToDataFrame)]
+struct Empty {}
+
+fn trades() -> Vec<Trade> {
+    vec![
+        Trade {
+            symbol: "A".to_owned(),
+            price: 1.0,
+        },
+        Trade {
+            symbol: "B".to_owned(),
+            price: 2.0,
+        },
+    ]
+}
+
+#[test]
+fn pointer_rows_match_owned_rows() {
+    let expected = trades().as_slice().to_dataframe().unwrap();
+
+    let boxed: Vec<Box<Trade>> = trades().into_iter().map(Box::new).collect();
+    let arced: Vec<Arc<Trade>> = trades().into_iter().map(Arc::new).collect();
+    let rced: Vec<Rc<Trade>> = trades().into_iter().map(Rc::new).collect();
+    let owned = trades();
+    let borrowed: Vec<&Trade> = owned.iter().collect();
+
+    for df in [
+        boxed.to_dataframe().unwrap(),
+        arced.to_dataframe().unwrap(),
+        rced.to_dataframe().unwrap(),
+        borrowed.to_dataframe().unwrap(),
+    ] {
+        assert!(df.equals_missing(&expected), "{df}\n{expected}");
+    }
+    assert_eq!(Arc::<Trade>::schema().unwrap(), Trade::schema().unwrap());
+    assert_eq!(
+        Box::<Trade>::column_metadata().unwrap(),
+        Trade::column_metadata().unwrap()
+    );
+    assert!(
+        Arc::new(owned[0].clone())
+            .to_dataframe()
+            .unwrap()
+            .equals(&owned[0].to_dataframe().unwrap())
+    );
+}
+
+#[test]
+fn option_rows_fill_none_with_nulls() {
+    let rows = [
+        None,
+        Some(Quote {
+            bid: 1.0,
+            ask: Some(1.5),
+        }),
+        None,
+        Some(Quote {
+            bid: 2.0,
+            ask: None,
+        }),
+    ];
+    let df = rows.to_dataframe().unwrap();
+
+    assert_eq!(df.height(), 4);
+    assert_eq!(df.get_column_names(), ["bid", "ask"]);
+    let bid: Vec<Option<f64>> = df
+        .column("bid")
+        .unwrap()
+        .f64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(bid, [None, Some(1.0), None, Some(2.0)]);
+    let ask: Vec<Option<f64>> = df
+        .column("ask")
+        .unwrap()
+        .f64()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(ask, [None, Some(1.5), None, None]);
+
+    let nulls = None::<Quote>.to_dataframe().unwrap();
+    assert_eq!(nulls.height(), 1);
+    assert_eq!(nulls.column("bid").unwrap().null_count(), 1);
+    assert_eq!(nulls.column("bid").unwrap().dtype(), &DataType::Float64);
+
+    let all_none: Vec<Option<Quote>> = vec![None, None];
+    let df = all_none.to_dataframe().unwrap();
+    assert_eq!(df.shape(), (2, 2));
+    assert_eq!(df.column("ask").unwrap().null_count(), 2);
+
+    let empty: Vec<Option<Empty>> = vec![None, Some(Empty {})];
+    assert_eq!(empty.to_dataframe().unwrap().shape(), (2, 0));
+}
+
+#[test]
+fn option_arrow_schema_is_nullable() {
+    let required = Quote::arrow_schema().unwrap();
+    assert!(!required.get("bid").unwrap().is_nullable);
+
+    let schema = Option::<Quote>::arrow_schema().unwrap();
+    assert!(schema.iter_values().all(|field| field.is_nullable));
+    assert_eq!(Option::<Quote>::schema().unwrap(), Quote::schema().unwrap());
+}
+
+#[test]
+fn container_payloads_nest_in_generic_fields() {
+    let rows = [
+        Wrapper {
+            id: 1,
+            payload: Some(Arc::new(trades()[0].clone())),
+        },
+        Wrapper {
+            id: 2,
+            payload: None,
+        },
+    ];
+    let df = rows.to_dataframe().unwrap();
+
+    assert_eq!(
+        df.get_column_names(),
+        ["id", "payload.symbol", "payload.price"]
+    );
+    let symbol: Vec<Option<&str>> = df
+        .column("payload.symbol")
+        .unwrap()
+        .str()
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(symbol, [Some("A"), None]);
+}
+
+#[test]
+fn tuple_rows_concatenate_prefixed_columns() {
+    let quote = Quote {
+        bid: 1.0,
+        ask: Some(1.5),
+    };
+    let pairs: Vec<(Trade, Quote)> = trades()
+        .into_iter()
+        .map(|trade| (trade, quote.clone()))
+        .collect();
+
+    let df = pairs.to_dataframe().unwrap();
+    assert_eq!(
+        df.get_column_names(),
+        [
+            "field_0.symbol",
+            "field_0.price",
+            "field_1.bid",
+            "field_1.ask"
+        ]
+    );
+    assert_eq!(df.height(), 2);
+    assert_eq!(
+        <(Trade, Quote)>::schema().unwrap(),
+        [
+            ("field_0.symbol".to_owned(), DataType::String),
+            ("field_0.price".to_owned(), DataType::Float64),
+            ("field_1.bid".to_owned(), DataType::Float64),
+            ("field_1.ask".to_owned(), DataType::Float64),
+        ]
+    );
+    let arrow = <(Trade, Quote)>::arrow_schema().unwrap();
+    assert!(!arrow.get("field_1.bid").unwrap().is_nullable);
+    assert!(arrow.get("field_1.ask").unwrap().is_nullable);
+    assert_eq!(
+        <(Trade, Quote)>::column_metadata().unwrap()[0].0,
+        "field_0.symbol"
+    );
+
+    let named = to_dataframe_with_prefixes(&pairs, &["trade", ""]).unwrap();
+    assert_eq!(
+        named.get_column_names(),
+        ["trade.symbol", "trade.price", "bid", "ask"]
+    );
+    assert_eq!(
+        schema_with_prefixes::<(Trade, Quote)>(&["trade", ""])
+            .unwrap()
+            .len(),
+        4
+    );
+
+    let empty: Vec<(Trade, Quote)> = Vec::new();
+    assert_eq!(empty.to_dataframe().unwrap().shape(), (0, 4));
+    assert_eq!(
+        to_dataframe_with_prefixes(&empty, &["a", "b"])
+            .unwrap()
+            .shape(),
+        (0, 4)
+    );
+}
+
+#[test]
+fn tuple_prefix_errors() {
+    let pairs = vec![(trades()[0].clone(), 1_i64)];
+    let df = pairs.to_dataframe().unwrap();
+    assert_eq!(
+        df.get_column_names(),
+        ["field_0.symbol", "field_0.price", "field_1"]
+    );
+
+    let err = to_dataframe_with_prefixes(&pairs, &["trade"]).unwrap_err();
+    assert!(
+        err.to_string().contains("expected 2 tuple prefixes, got 1"),
+        "{err}"
+    );
+    assert!(
+        to_dataframe_with_prefixes(&[(trades()[0].clone(), trades()[1].clone())], &["", ""])
+            .is_err()
+    );
+}
@@ -0,0 +1,56 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::core::dataframe::{Columnar, ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+
+#[derive(ToDataFrame, Clone)]
+struct Order {
+    id: u64,
+    side: String,
+    qty: Option<f64>,
+}
+
+fn orders() -> Vec<Order> {
+    (0..3)
+        .map(|id| Order {
+            id,
+            side: if id % 2 == 0 { "buy" } else { "sell" }.to_owned(),
+            qty: (id > 0).then_some(1.5),
+        })
+        .collect()
+}
+
+#[test]
+fn cow_rows_and_ref_slices_match_owned_rows() {
+    let owned = orders();
+    let expected = owned.as_slice().to_dataframe().unwrap();
+
+    let borrowed: Vec<&Order> = owned.iter().collect();
+    let cows: Vec<Cow<'_, Order>> = vec![
+        Cow::Borrowed(&owned[0]),
+        Cow::Owned(owned[1].clone()),
+        Cow::Borrowed(&owned[2]),
+    ];
+
+    for df in [
+        cows.to_dataframe().unwrap(),
+        <&Order>::columnar_to_dataframe(&borrowed).unwrap(),
+    ] {
+        assert!(df.equals_missing(&expected), "{df}\n{expected}");
+    }
+}
+
+#[test]
+fn empty_pointer_slices_keep_the_schema() {
+    let empty: Vec<Arc<Order>> = Vec::new();
+    let df = empty.to_dataframe().unwrap();
+    assert_eq!(df.shape(), (0, 3));
+    assert_eq!(
+        Cow::<'_, Order>::schema().unwrap(),
+        Order::schema().unwrap()
+    );
+
+    let empty: Vec<Cow<'_, Order>> = Vec::new();
+    assert_eq!(empty.to_dataframe().unwrap().shape(), (0, 3));
+}
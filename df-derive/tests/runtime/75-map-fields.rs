@@ -0,0 +1,159 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataType, Field};
+
+#[derive(ToDataFrame, Clone)]
+struct Limit {
+    max: f64,
+    note: Option<String>,
+}
+
+#[derive(ToDataFrame)]
+struct Account {
+    id: u32,
+    balances: BTreeMap<String, i64>,
+    #[df_derive(sort_keys)]
+    tags: HashMap<String, u8>,
+    #[df_derive(split_map)]
+    limits: BTreeMap<u16, Limit>,
+    history: Option<Vec<BTreeMap<String, f64>>>,
+}
+
+#[derive(ToDataFrame)]
+struct Wrapper<T> {
+    payload: T,
+}
+
+fn accounts() -> Vec<Account> {
+    vec![
+        Account {
+            id: 1,
+            balances: BTreeMap::from([("usd".to_owned(), 10), ("eur".to_owned(), 20)]),
+            tags: HashMap::from([
+                ("z".to_owned(), 1),
+                ("a".to_owned(), 2),
+                ("m".to_owned(), 3),
+            ]),
+            limits: BTreeMap::from([(
+                7,
+                Limit {
+                    max: 1.5,
+                    note: None,
+                },
+            )]),
+            history: Some(vec![
+                BTreeMap::from([("x".to_owned(), 0.5)]),
+                BTreeMap::new(),
+            ]),
+        },
+        Account {
+            id: 2,
+            balances: BTreeMap::new(),
+            tags: HashMap::new(),
+            limits: BTreeMap::new(),
+            history: None,
+        },
+    ]
+}
+
+fn entry_dtype(key: DataType, value: DataType) -> DataType {
+    DataType::List(Box::new(DataType::Struct(vec![
+        Field::new("key".into(), key),
+        Field::new("value".into(), value),
+    ])))
+}
+
+fn keys_of(df: &polars::prelude::DataFrame, column: &str, row: usize) -> Vec<String> {
+    let AnyValue::List(entries) = df.column(column).unwrap().get(row).unwrap() else {
+        panic!("expected a list");
+    };
+    let entries = entries.struct_().unwrap().clone().unnest();
+    entries
+        .column("key")
+        .unwrap()
+        .str()
+        .unwrap()
+        .into_iter()
+        .map(|key| key.unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn map_fields_become_entry_lists() {
+    let df = accounts().to_dataframe().unwrap();
+    let limit = DataType::Struct(vec![
+        Field::new("max".into(), DataType::Float64),
+        Field::new("note".into(), DataType::String),
+    ]);
+    let expected = [
+        ("id".to_owned(), DataType::UInt32),
+        (
+            "balances".to_owned(),
+            entry_dtype(DataType::String, DataType::Int64),
+        ),
+        (
+            "tags".to_owned(),
+            entry_dtype(DataType::String, DataType::UInt8),
+        ),
+        (
+            "limits.keys".to_owned(),
+            DataType::List(Box::new(DataType::UInt16)),
+        ),
+        ("limits.values".to_owned(), DataType::List(Box::new(limit))),
+        (
+            "history".to_owned(),
+            DataType::List(Box::new(entry_dtype(DataType::String, DataType::Float64))),
+        ),
+    ];
+    assert_eq!(Account::schema().unwrap(), expected);
+    let actual: Vec<(String, DataType)> = df
+        .columns()
+        .iter()
+        .map(|column| (column.name().to_string(), column.dtype().clone()))
+        .collect();
+    assert_eq!(actual, expected);
+
+    assert_eq!(keys_of(&df, "balances", 0), ["eur", "usd"]);
+    assert_eq!(keys_of(&df, "tags", 0), ["a", "m", "z"]);
+    assert!(keys_of(&df, "tags", 1).is_empty());
+    assert_eq!(df.column("history").unwrap().null_count(), 1);
+
+    let empty = Vec::<Account>::new().to_dataframe().unwrap();
+    assert_eq!(empty.shape(), (0, 6));
+    assert_eq!(Account::empty_dataframe().unwrap().schema(), df.schema());
+}
+
+#[test]
+fn map_arrow_schema_keeps_value_nullability() {
+    let schema = Account::arrow_schema().unwrap();
+    let field = schema.get("limits.values").unwrap();
+    assert!(!field.is_nullable);
+    let polars::prelude::ArrowDataType::LargeList(item) = &field.dtype else {
+        panic!("expected a list field, got {:?}", field.dtype);
+    };
+    let polars::prelude::ArrowDataType::Struct(fields) = &item.dtype else {
+        panic!("expected struct values, got {:?}", item.dtype);
+    };
+    assert_eq!(fields[1].name.as_str(), "note");
+    assert!(fields[1].is_nullable);
+    assert!(schema.get("history").unwrap().is_nullable);
+}
+
+#[test]
+fn maps_work_as_generic_payloads_and_handles() {
+    let rows = [Wrapper {
+        payload: BTreeMap::from([(1_i32, true)]),
+    }];
+    let df = rows.to_dataframe().unwrap();
+    assert_eq!(df.get_column_names(), ["payload"]);
+    assert_eq!(
+        df.column("payload").unwrap().dtype(),
+        &entry_dtype(DataType::Int32, DataType::Boolean)
+    );
+
+    assert_eq!(Account::cols().balances().name(), "balances");
+    assert_eq!(Account::cols().limits().keys().name(), "limits.keys");
+    assert_eq!(Account::cols().limits().values().name(), "limits.values");
+}
@@ -0,0 +1,212 @@
+// Sets and deques are list layers; the test also covers `LinkedList` and a
+// boxed set on purpose.
+#![allow(clippy::box_collection, clippy::linkedlist)]
+
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataFrame, DataType};
+
+#[derive(ToDataFrame, Clone)]
+struct Point {
+    x: i32,
+    label: Option<String>,
+}
+
+#[derive(ToDataFrame)]
+struct Inventory {
+    #[df_derive(sort_keys)]
+    tags: HashSet<String>,
+    ids: BTreeSet<i64>,
+    queue: VecDeque<u8>,
+    samples: LinkedList<f64>,
+    aliases: Option<BTreeSet<String>>,
+    path: VecDeque<Point>,
+    #[df_derive(sort_keys)]
+    buckets: Vec<HashSet<u16>>,
+    #[df_derive(sort_keys)]
+    codes: Option<Box<HashSet<i32>>>,
+    #[df_derive(sort_keys)]
+    flags: HashSet<bool>,
+    pairs: BTreeSet<(i32, String)>,
+    #[df_derive(as_binary)]
+    frames: VecDeque<Vec<u8>>,
+}
+
+#[derive(ToDataFrame)]
+struct Tagged<T> {
+    #[df_derive(sort_keys)]
+    tags: HashSet<T>,
+    seen: HashSet<T>,
+}
+
+fn inventory() -> Inventory {
+    Inventory {
+        tags: HashSet::from(["z".to_owned(), "a".to_owned(), "m".to_owned()]),
+        ids: BTreeSet::from([3, 1, 2]),
+        queue: VecDeque::from([4, 5]),
+        samples: LinkedList::from([0.5, 1.5]),
+        aliases: None,
+        path: VecDeque::from([
+            Point { x: 1, label: None },
+            Point {
+                x: 2,
+                label: Some("b".to_owned()),
+            },
+        ]),
+        buckets: vec![HashSet::from([9, 3, 6]), HashSet::new()],
+        codes: Some(Box::new(HashSet::from([30, 10, 20]))),
+        flags: HashSet::from([true, false]),
+        pairs: BTreeSet::from([(2, "b".to_owned()), (1, "a".to_owned())]),
+        frames: VecDeque::from([vec![1, 2], vec![]]),
+    }
+}
+
+fn list_at(df: &DataFrame, column: &str, row: usize) -> polars::prelude::Series {
+    let AnyValue::List(values) = df.column(column).unwrap().get(row).unwrap() else {
+        panic!("expected a list in `{column}`");
+    };
+    values
+}
+
+#[test]
+fn collections_become_list_columns() {
+    let df = [inventory()].to_dataframe().unwrap();
+    let list = |dtype: DataType| DataType::List(Box::new(dtype));
+    let expected = [
+        ("tags".to_owned(), list(DataType::String)),
+        ("ids".to_owned(), list(DataType::Int64)),
+        ("queue".to_owned(), list(DataType::UInt8)),
+        ("samples".to_owned(), list(DataType::Float64)),
+        ("aliases".to_owned(), list(DataType::String)),
+        ("path.x".to_owned(), list(DataType::Int32)),
+        ("path.label".to_owned(), list(DataType::String)),
+        ("buckets".to_owned(), list(list(DataType::UInt16))),
+        ("codes".to_owned(), list(DataType::Int32)),
+        ("flags".to_owned(), list(DataType::Boolean)),
+        ("pairs.field_0".to_owned(), list(DataType::Int32)),
+        ("pairs.field_1".to_owned(), list(DataType::String)),
+        ("frames".to_owned(), list(DataType::Binary)),
+    ];
+    assert_eq!(Inventory::schema().unwrap(), expected);
+    let actual: Vec<(String, DataType)> = df
+        .columns()
+        .iter()
+        .map(|column| (column.name().to_string(), column.dtype().clone()))
+        .collect();
+    assert_eq!(actual, expected);
+
+    let tags = list_at(&df, "tags", 0);
+    let tags: Vec<&str> = tags.str().unwrap().into_no_null_iter().collect();
+    assert_eq!(tags, ["a", "m", "z"]);
+    let ids = list_at(&df, "ids", 0);
+    assert_eq!(
+        ids.i64().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    let queue = list_at(&df, "queue", 0);
+    assert_eq!(
+        queue.u8().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [4, 5]
+    );
+    let samples = list_at(&df, "samples", 0);
+    assert_eq!(
+        samples
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [0.5, 1.5]
+    );
+    assert_eq!(df.column("aliases").unwrap().null_count(), 1);
+    let labels = list_at(&df, "path.label", 0);
+    assert_eq!(
+        labels.str().unwrap().into_iter().collect::<Vec<_>>(),
+        [None, Some("b")]
+    );
+    let AnyValue::List(buckets) = df.column("buckets").unwrap().get(0).unwrap() else {
+        panic!("expected nested lists");
+    };
+    let AnyValue::List(first) = buckets.get(0).unwrap() else {
+        panic!("expected an inner list");
+    };
+    assert_eq!(
+        first.u16().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [3, 6, 9]
+    );
+    let codes = list_at(&df, "codes", 0);
+    assert_eq!(
+        codes.i32().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [10, 20, 30]
+    );
+    let flags = list_at(&df, "flags", 0);
+    assert_eq!(
+        flags
+            .bool()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [false, true]
+    );
+    let pairs = list_at(&df, "pairs.field_1", 0);
+    assert_eq!(
+        pairs.str().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+    let frames = list_at(&df, "frames", 0);
+    assert_eq!(
+        frames
+            .binary()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [&[1_u8, 2][..], &[][..]]
+    );
+}
+
+#[test]
+fn missing_and_empty_collections() {
+    let mut empty = inventory();
+    empty.tags.clear();
+    empty.codes = None;
+    empty.aliases = Some(BTreeSet::from(["y".to_owned(), "x".to_owned()]));
+    let df = [inventory(), empty].to_dataframe().unwrap();
+
+    assert_eq!(df.height(), 2);
+    assert!(list_at(&df, "tags", 1).is_empty());
+    assert_eq!(df.column("codes").unwrap().null_count(), 1);
+    let aliases = list_at(&df, "aliases", 1);
+    assert_eq!(
+        aliases
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        ["x", "y"]
+    );
+
+    let arrow = Inventory::arrow_schema().unwrap();
+    assert!(arrow.get("codes").unwrap().is_nullable);
+    assert!(!arrow.get("tags").unwrap().is_nullable);
+    assert_eq!(Vec::<Inventory>::new().to_dataframe().unwrap().width(), 13);
+}
+
+#[test]
+fn generic_sorted_sets_add_ord_bound() {
+    let rows = [Tagged {
+        tags: HashSet::from([5_u32, 1, 3]),
+        seen: HashSet::from([7_u32]),
+    }];
+    let df = rows.to_dataframe().unwrap();
+    assert_eq!(
+        df.column("tags").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::UInt32))
+    );
+    let tags = list_at(&df, "tags", 0);
+    assert_eq!(
+        tags.u32().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [1, 3, 5]
+    );
+    assert_eq!(list_at(&df, "seen", 0).len(), 1);
+}
@@ -0,0 +1,310 @@
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec, TypedDataFrame};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, ArrowDataType, DataFrame, DataType, Series};
+
+#[derive(ToDataFrame, Clone)]
+struct Point {
+    x: i32,
+    label: Option<String>,
+}
+
+#[derive(ToDataFrame)]
+struct Body {
+    coords: [f64; 3],
+    #[df_derive(as_binary)]
+    hash: [u8; 4],
+    raw: [u8; 2],
+    corners: [Point; 2],
+    offset: Option<[i32; 2]>,
+    readings: [Option<i64>; 2],
+    track: Vec<[f32; 2]>,
+    matrix: [[i16; 2]; 2],
+    #[df_derive(as_binary)]
+    keys: Vec<[u8; 2]>,
+    spare: Option<[Point; 2]>,
+    pairs: [(i32, String); 2],
+}
+
+#[derive(ToDataFrame)]
+struct Scene {
+    bodies: Vec<Body>,
+}
+
+#[derive(ToDataFrame)]
+struct Window<const N: usize> {
+    values: [u32; N],
+}
+
+fn body(seed: i32) -> Body {
+    Body {
+        coords: [1.0, 2.0, f64::from(seed)],
+        hash: [0xde, 0xad, 0xbe, 0xef],
+        raw: [1, 2],
+        corners: [
+            Point {
+                x: seed,
+                label: None,
+            },
+            Point {
+                x: seed + 1,
+                label: Some("b".to_owned()),
+            },
+        ],
+        offset: Some([seed, -seed]),
+        readings: [Some(7), None],
+        track: vec![[0.5, 1.5], [2.5, 3.5]],
+        matrix: [[1, 2], [3, 4]],
+        keys: vec![[9, 8]],
+        spare: None,
+        pairs: [(1, "a".to_owned()), (2, "b".to_owned())],
+    }
+}
+
+fn array(dtype: DataType, len: usize) -> DataType {
+    DataType::Array(Box::new(dtype), len)
+}
+
+fn array_at(df: &DataFrame, column: &str, row: usize) -> Series {
+    let AnyValue::Array(values, _) = df.column(column).unwrap().get(row).unwrap() else {
+        panic!("expected an array in `{column}`");
+    };
+    values
+}
+
+#[test]
+fn arrays_become_fixed_width_columns() {
+    let expected = vec![
+        ("coords".to_owned(), array(DataType::Float64, 3)),
+        ("hash".to_owned(), DataType::Binary),
+        ("raw".to_owned(), array(DataType::UInt8, 2)),
+        ("corners.x".to_owned(), array(DataType::Int32, 2)),
+        ("corners.label".to_owned(), array(DataType::String, 2)),
+        ("offset".to_owned(), array(DataType::Int32, 2)),
+        ("readings".to_owned(), array(DataType::Int64, 2)),
+        (
+            "track".to_owned(),
+            DataType::List(Box::new(array(DataType::Float32, 2))),
+        ),
+        ("matrix".to_owned(), array(array(DataType::Int16, 2), 2)),
+        (
+            "keys".to_owned(),
+            DataType::List(Box::new(DataType::Binary)),
+        ),
+        ("spare.x".to_owned(), array(DataType::Int32, 2)),
+        ("spare.label".to_owned(), array(DataType::String, 2)),
+        ("pairs.field_0".to_owned(), array(DataType::Int32, 2)),
+        ("pairs.field_1".to_owned(), array(DataType::String, 2)),
+    ];
+    assert_eq!(Body::schema().unwrap(), expected);
+
+    let df = [body(3), body(4)].to_dataframe().unwrap();
+    let actual: Vec<(String, DataType)> = df
+        .columns()
+        .iter()
+        .map(|column| (column.name().to_string(), column.dtype().clone()))
+        .collect();
+    assert_eq!(actual, expected);
+    let empty = Vec::<Body>::new().to_dataframe().unwrap();
+    assert_eq!(empty.schema(), df.schema());
+
+    let coords = array_at(&df, "coords", 1);
+    assert_eq!(
+        coords
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [1.0, 2.0, 4.0]
+    );
+    assert_eq!(
+        df.column("hash").unwrap().get(0).unwrap(),
+        AnyValue::Binary(&[0xde, 0xad, 0xbe, 0xef])
+    );
+    let pairs = array_at(&df, "pairs.field_1", 0);
+    assert_eq!(
+        pairs.str().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+    let labels = array_at(&df, "corners.label", 0);
+    assert_eq!(
+        labels.str().unwrap().into_iter().collect::<Vec<_>>(),
+        [None, Some("b")]
+    );
+    let readings = array_at(&df, "readings", 0);
+    assert_eq!(
+        readings.i64().unwrap().into_iter().collect::<Vec<_>>(),
+        [Some(7), None]
+    );
+    let matrix = array_at(&df, "matrix", 0);
+    let AnyValue::Array(second, 2) = matrix.get(1).unwrap() else {
+        panic!("expected an inner array");
+    };
+    assert_eq!(
+        second
+            .i16()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [3, 4]
+    );
+    let AnyValue::List(track) = df.column("track").unwrap().get(0).unwrap() else {
+        panic!("expected a list of arrays");
+    };
+    assert_eq!(track.len(), 2);
+}
+
+#[test]
+fn optional_arrays_are_null() {
+    let mut missing = body(1);
+    missing.offset = None;
+    let mut present = body(0);
+    present.spare = Some(present.corners.clone());
+    let df = [present, missing].to_dataframe().unwrap();
+
+    assert_eq!(df.column("spare.x").unwrap().null_count(), 1);
+    assert_eq!(
+        array_at(&df, "spare.x", 0)
+            .i32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [0, 1]
+    );
+
+    let offset = df.column("offset").unwrap();
+    assert_eq!(offset.null_count(), 1);
+    assert_eq!(offset.get(1).unwrap(), AnyValue::Null);
+    assert_eq!(
+        array_at(&df, "offset", 0)
+            .i32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [0, 0]
+    );
+
+    let arrow = Body::arrow_schema().unwrap();
+    let offset = arrow.get("offset").unwrap();
+    assert!(offset.is_nullable);
+    let ArrowDataType::FixedSizeList(item, 2) = &offset.dtype else {
+        panic!("expected a fixed-size list, got {:?}", offset.dtype);
+    };
+    assert!(!item.is_nullable);
+    let ArrowDataType::FixedSizeList(item, 2) = &arrow.get("readings").unwrap().dtype else {
+        panic!("expected a fixed-size list");
+    };
+    assert!(item.is_nullable);
+    let ArrowDataType::LargeList(item) = &arrow.get("track").unwrap().dtype else {
+        panic!("expected a list");
+    };
+    assert!(matches!(item.dtype, ArrowDataType::FixedSizeList(_, 2)));
+}
+
+#[test]
+fn arrays_nest_inside_lists_and_const_generics() {
+    let scene = Scene {
+        bodies: vec![body(1), body(2)],
+    };
+    let df = scene.to_dataframe().unwrap();
+    assert_eq!(
+        df.column("bodies.coords").unwrap().dtype(),
+        &DataType::List(Box::new(array(DataType::Float64, 3)))
+    );
+    assert_eq!(
+        df.column("bodies.corners.x").unwrap().dtype(),
+        &DataType::List(Box::new(array(DataType::Int32, 2)))
+    );
+    assert_eq!(
+        Scene::schema().unwrap()[0],
+        (
+            "bodies.coords".to_owned(),
+            DataType::List(Box::new(array(DataType::Float64, 3)))
+        )
+    );
+
+    let windows = [Window { values: [1, 2, 3] }, Window { values: [4, 5, 6] }];
+    let df = windows.to_dataframe().unwrap();
+    assert_eq!(
+        Window::<3>::schema().unwrap(),
+        [("values".to_owned(), array(DataType::UInt32, 3))]
+    );
+    let typed = TypedDataFrame::<Window<3>>::try_from(df).unwrap();
+    let values = typed.values();
+    assert_eq!(values.width(), 3);
+    assert_eq!(values.len(), 2);
+}
+
+#[derive(ToDataFrame)]
+struct Route {
+    legs: Vec<Option<[i32; 2]>>,
+    ends: Option<[String; 2]>,
+}
+
+fn rows(column: &Series) -> Vec<Option<Vec<i32>>> {
+    (0..column.len())
+        .map(|row| match column.get(row).unwrap() {
+            AnyValue::Array(values, 2) => Some(values.i32().unwrap().into_no_null_iter().collect()),
+            AnyValue::Null => None,
+            other => panic!("expected an array or null, got {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn null_arrays_between_values_keep_their_neighbours() {
+    let ends = |from: &str, to: &str| Some([from.to_owned(), to.to_owned()]);
+    let routes = [
+        Route {
+            legs: vec![None, Some([1, 2]), Some([3, 4]), None, Some([5, 6])],
+            ends: None,
+        },
+        Route {
+            legs: vec![],
+            ends: ends("a", "b"),
+        },
+        Route {
+            legs: vec![Some([7, 8]), None],
+            ends: None,
+        },
+        Route {
+            legs: vec![None],
+            ends: ends("c", "d"),
+        },
+    ];
+    let df = routes.to_dataframe().unwrap();
+
+    let legs = df.column("legs").unwrap();
+    let legs = (0..legs.len())
+        .map(|row| {
+            let AnyValue::List(legs) = legs.get(row).unwrap() else {
+                panic!("expected a list of arrays");
+            };
+            rows(&legs)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        legs,
+        [
+            vec![
+                None,
+                Some(vec![1, 2]),
+                Some(vec![3, 4]),
+                None,
+                Some(vec![5, 6])
+            ],
+            vec![],
+            vec![Some(vec![7, 8]), None],
+            vec![None],
+        ]
+    );
+
+    let ends = df.column("ends").unwrap();
+    assert_eq!(ends.null_count(), 2);
+    let AnyValue::Array(last, 2) = ends.get(3).unwrap() else {
+        panic!("expected an array");
+    };
+    assert_eq!(
+        last.str().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        ["c", "d"]
+    );
+}
@@ -0,0 +1,158 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataType};
+
+#[derive(ToDataFrame, Clone)]
+struct Point {
+    x: i32,
+    label: Option<String>,
+}
+
+#[derive(ToDataFrame)]
+struct Shared<'a> {
+    name: Box<str>,
+    venue: Arc<str>,
+    #[df_derive(as_str)]
+    desk: Rc<str>,
+    alias: Option<Arc<str>>,
+    tags: Vec<Box<str>>,
+    ids: Box<[i32]>,
+    path: Arc<[Point]>,
+    readings: Rc<[Option<f64>]>,
+    window: &'a [i64],
+    notes: Cow<'a, [String]>,
+    spare: Option<Box<[u16]>>,
+    raw: Box<[u8]>,
+    #[df_derive(as_binary)]
+    blob: Box<[u8]>,
+    #[df_derive(as_binary)]
+    maybe_blob: Option<Arc<[u8]>>,
+    #[df_derive(as_binary)]
+    chunks: Vec<Rc<[u8]>>,
+    #[df_derive(as_string)]
+    codes: &'a [u16],
+}
+
+#[derive(ToDataFrame)]
+struct Owned {
+    name: String,
+    venue: String,
+    desk: String,
+    alias: Option<String>,
+    tags: Vec<String>,
+    ids: Vec<i32>,
+    path: Vec<Point>,
+    readings: Vec<Option<f64>>,
+    window: Vec<i64>,
+    notes: Vec<String>,
+    spare: Option<Vec<u16>>,
+    raw: Vec<u8>,
+    #[df_derive(as_binary)]
+    blob: Vec<u8>,
+    #[df_derive(as_binary)]
+    maybe_blob: Option<Vec<u8>>,
+    #[df_derive(as_binary)]
+    chunks: Vec<Vec<u8>>,
+    #[df_derive(as_string)]
+    codes: Vec<u16>,
+}
+
+fn owned(seed: i32) -> Owned {
+    Owned {
+        name: format!("row-{seed}"),
+        venue: "XNAS".to_owned(),
+        desk: "rates".to_owned(),
+        alias: (seed % 2 == 0).then(|| format!("alias-{seed}")),
+        tags: vec!["a".to_owned(), format!("t{seed}")],
+        ids: vec![seed, seed + 1],
+        path: vec![Point {
+            x: seed,
+            label: None,
+        }],
+        readings: vec![Some(0.5), None],
+        window: vec![i64::from(seed); 3],
+        notes: vec!["note".to_owned()],
+        spare: (seed % 2 == 1).then(|| vec![7, 8]),
+        raw: vec![1, 2],
+        blob: vec![0xca, 0xfe],
+        maybe_blob: (seed % 2 == 0).then(|| vec![0xbe, 0xef]),
+        chunks: vec![vec![1], vec![2, 3]],
+        codes: vec![10, 20],
+    }
+}
+
+fn shared(row: &Owned) -> Shared<'_> {
+    Shared {
+        name: row.name.as_str().into(),
+        venue: row.venue.as_str().into(),
+        desk: row.desk.as_str().into(),
+        alias: row.alias.as_deref().map(Arc::from),
+        tags: row.tags.iter().map(|tag| tag.as_str().into()).collect(),
+        ids: row.ids.clone().into_boxed_slice(),
+        path: row.path.clone().into(),
+        readings: row.readings.clone().into(),
+        window: &row.window,
+        notes: Cow::Borrowed(&row.notes),
+        spare: row.spare.clone().map(Vec::into_boxed_slice),
+        raw: row.raw.clone().into_boxed_slice(),
+        blob: row.blob.clone().into_boxed_slice(),
+        maybe_blob: row.maybe_blob.as_deref().map(Arc::from),
+        chunks: row
+            .chunks
+            .iter()
+            .map(|chunk| Rc::from(&chunk[..]))
+            .collect(),
+        codes: &row.codes,
+    }
+}
+
+#[test]
+fn pointer_wrapped_unsized_fields_match_owned_equivalents() {
+    let rows = [owned(1), owned(2)];
+    let expected = rows.to_dataframe().unwrap();
+    let actual = rows.iter().map(shared).collect::<Vec<_>>();
+    let df = actual.to_dataframe().unwrap();
+
+    assert!(df.equals_missing(&expected));
+    assert_eq!(Shared::schema().unwrap(), Owned::schema().unwrap());
+    assert_eq!(
+        Vec::<Shared<'_>>::new().to_dataframe().unwrap().schema(),
+        df.schema()
+    );
+
+    assert_eq!(df.column("name").unwrap().dtype(), &DataType::String);
+    assert_eq!(
+        df.column("ids").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::Int32))
+    );
+    assert_eq!(
+        df.column("raw").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::UInt8))
+    );
+    assert_eq!(
+        df.column("blob").unwrap().get(0).unwrap(),
+        AnyValue::Binary(&[0xca, 0xfe])
+    );
+    assert_eq!(
+        df.column("codes").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::String))
+    );
+}
+
+#[test]
+fn single_unsized_row_converts() {
+    let row = owned(3);
+    let df = shared(&row).to_dataframe().unwrap();
+    assert_eq!(df.height(), 1);
+    assert_eq!(
+        df.column("venue").unwrap().get(0).unwrap(),
+        AnyValue::String("XNAS")
+    );
+    assert_eq!(df.column("alias").unwrap().null_count(), 1);
+    assert_eq!(df.column("maybe_blob").unwrap().null_count(), 1);
+    assert_eq!(df.column("spare").unwrap().null_count(), 0);
+}
@@ -0,0 +1,139 @@
+use arrayvec::ArrayVec;
+use smallvec::{SmallVec, smallvec};
+use tinyvec::{TinyVec, tiny_vec};
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::DataType;
+
+#[derive(ToDataFrame, Clone)]
+struct Point {
+    x: i32,
+    label: Option<String>,
+}
+
+/// Keeps only its even values, so its iterator cannot report an exact size.
+struct Evens<T>(Vec<T>);
+
+impl<'a> IntoIterator for &'a Evens<i32> {
+    type Item = &'a i32;
+    type IntoIter = std::iter::Filter<std::slice::Iter<'a, i32>, fn(&&i32) -> bool>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().filter(|value| *value % 2 == 0)
+    }
+}
+
+#[derive(ToDataFrame)]
+struct Inline {
+    #[df_derive(as_list)]
+    ids: SmallVec<[i32; 4]>,
+    #[df_derive(as_list)]
+    tags: Option<SmallVec<[String; 2]>>,
+    #[df_derive(as_list)]
+    path: ArrayVec<Point, 4>,
+    #[df_derive(as_list)]
+    weights: TinyVec<[f64; 2]>,
+    #[df_derive(as_list)]
+    groups: Vec<SmallVec<[Option<i64>; 2]>>,
+    #[df_derive(as_list, as_string)]
+    codes: SmallVec<[u16; 2]>,
+    #[df_derive(as_list)]
+    evens: Evens<i32>,
+}
+
+#[derive(ToDataFrame)]
+struct Owned {
+    ids: Vec<i32>,
+    tags: Option<Vec<String>>,
+    path: Vec<Point>,
+    weights: Vec<f64>,
+    groups: Vec<Vec<Option<i64>>>,
+    #[df_derive(as_string)]
+    codes: Vec<u16>,
+    evens: Vec<i32>,
+}
+
+#[derive(ToDataFrame)]
+struct Bag<T> {
+    #[df_derive(as_list)]
+    items: SmallVec<[T; 2]>,
+}
+
+fn point(x: i32) -> Point {
+    Point {
+        x,
+        label: (x > 0).then(|| format!("p{x}")),
+    }
+}
+
+fn inline(seed: i32) -> Inline {
+    Inline {
+        ids: smallvec![seed, seed + 1, seed + 2],
+        tags: (seed % 2 == 0).then(|| smallvec!["a".to_owned(), format!("t{seed}")]),
+        path: [point(seed), point(-seed)].into_iter().collect(),
+        weights: tiny_vec![0.5, 1.5, f64::from(seed)],
+        groups: vec![smallvec![Some(1), None], SmallVec::new()],
+        codes: smallvec![7, 8],
+        evens: Evens((0..=seed).collect()),
+    }
+}
+
+fn owned(seed: i32) -> Owned {
+    Owned {
+        ids: vec![seed, seed + 1, seed + 2],
+        tags: (seed % 2 == 0).then(|| vec!["a".to_owned(), format!("t{seed}")]),
+        path: vec![point(seed), point(-seed)],
+        weights: vec![0.5, 1.5, f64::from(seed)],
+        groups: vec![vec![Some(1), None], Vec::new()],
+        codes: vec![7, 8],
+        evens: (0..=seed).filter(|value| value % 2 == 0).collect(),
+    }
+}
+
+#[test]
+fn as_list_collections_match_vec_columns() {
+    let df = [inline(3), inline(4)].to_dataframe().unwrap();
+    let expected = [owned(3), owned(4)].to_dataframe().unwrap();
+    assert!(df.equals_missing(&expected));
+    assert_eq!(Inline::schema().unwrap(), Owned::schema().unwrap());
+    assert_eq!(
+        Vec::<Inline>::new().to_dataframe().unwrap().schema(),
+        expected.schema()
+    );
+
+    let single = inline(5).to_dataframe().unwrap();
+    assert!(single.equals_missing(&owned(5).to_dataframe().unwrap()));
+    assert_eq!(
+        single.column("evens").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::Int32))
+    );
+    assert_eq!(
+        single.column("groups").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::List(Box::new(DataType::Int64))))
+    );
+}
+
+#[test]
+fn as_list_elements_can_be_generic_payloads() {
+    let bags = [
+        Bag {
+            items: smallvec![1_u32, 2],
+        },
+        Bag {
+            items: SmallVec::new(),
+        },
+    ];
+    let df = bags.to_dataframe().unwrap();
+    assert_eq!(
+        df.column("items").unwrap().dtype(),
+        &DataType::List(Box::new(DataType::UInt32))
+    );
+
+    let nested = [Bag {
+        items: smallvec![point(1)],
+    }]
+    .to_dataframe()
+    .unwrap();
+    assert_eq!(nested.get_column_names(), ["items.x", "items.label"]);
+}
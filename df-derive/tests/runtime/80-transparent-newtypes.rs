@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec, TypedDataFrame};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataType};
+
+#[derive(ToDataFrame, Clone, Copy)]
+#[df_derive(transparent)]
+struct Price(f64);
+
+#[derive(ToDataFrame, Clone)]
+#[df_derive(transparent)]
+struct Ticker {
+    #[df_derive(as_str)]
+    symbol: String,
+    #[df_derive(skip)]
+    _cached_hash: u64,
+}
+
+#[derive(ToDataFrame, Clone)]
+struct Point {
+    x: i32,
+    label: Option<String>,
+}
+
+#[derive(ToDataFrame, Clone)]
+#[df_derive(transparent)]
+struct Anchor(Point);
+
+#[derive(ToDataFrame, Clone)]
+#[df_derive(transparent)]
+struct Span((i32, i32));
+
+#[derive(ToDataFrame, Clone)]
+#[df_derive(transparent)]
+struct Limits(BTreeMap<String, i64>);
+
+#[derive(ToDataFrame)]
+#[df_derive(transparent)]
+struct Tagged<T>(T);
+
+#[derive(ToDataFrame)]
+struct Wrapper<T> {
+    payload: T,
+}
+
+#[derive(ToDataFrame)]
+struct Quote {
+    ticker: Ticker,
+    price: Price,
+    bid: Option<Price>,
+    fills: Vec<Price>,
+    anchor: Anchor,
+    track: Vec<Anchor>,
+    span: Span,
+    limits: Limits,
+}
+
+#[derive(ToDataFrame)]
+struct PlainQuote {
+    #[df_derive(as_str)]
+    ticker: String,
+    price: f64,
+    bid: Option<f64>,
+    fills: Vec<f64>,
+    anchor: Point,
+    track: Vec<Point>,
+    span: (i32, i32),
+    limits: BTreeMap<String, i64>,
+}
+
+fn point(x: i32) -> Point {
+    Point {
+        x,
+        label: (x > 0).then(|| format!("p{x}")),
+    }
+}
+
+fn plain(seed: i32) -> PlainQuote {
+    PlainQuote {
+        ticker: format!("T{seed}"),
+        price: f64::from(seed) + 0.5,
+        bid: (seed % 2 == 0).then_some(f64::from(seed)),
+        fills: vec![1.0, f64::from(seed)],
+        anchor: point(seed),
+        track: vec![point(-seed), point(seed)],
+        span: (seed, seed + 1),
+        limits: BTreeMap::from([("a".to_owned(), i64::from(seed))]),
+    }
+}
+
+fn quote(row: &PlainQuote) -> Quote {
+    Quote {
+        ticker: Ticker {
+            symbol: row.ticker.clone(),
+            _cached_hash: 7,
+        },
+        price: Price(row.price),
+        bid: row.bid.map(Price),
+        fills: row.fills.iter().copied().map(Price).collect(),
+        anchor: Anchor(row.anchor.clone()),
+        track: row.track.iter().cloned().map(Anchor).collect(),
+        span: Span(row.span),
+        limits: Limits(row.limits.clone()),
+    }
+}
+
+#[test]
+fn transparent_fields_match_their_inner_types() {
+    let rows = [plain(1), plain(2)];
+    let expected = rows.to_dataframe().unwrap();
+    let df = rows
+        .iter()
+        .map(quote)
+        .collect::<Vec<_>>()
+        .to_dataframe()
+        .unwrap();
+
+    assert!(df.equals_missing(&expected));
+    assert_eq!(Quote::schema().unwrap(), PlainQuote::schema().unwrap());
+    assert_eq!(
+        Quote::arrow_schema().unwrap(),
+        PlainQuote::arrow_schema().unwrap()
+    );
+    assert_eq!(
+        Vec::<Quote>::new().to_dataframe().unwrap().schema(),
+        expected.schema()
+    );
+    assert!(
+        quote(&plain(3))
+            .to_dataframe()
+            .unwrap()
+            .equals_missing(&plain(3).to_dataframe().unwrap())
+    );
+    assert_eq!(df.column("price").unwrap().dtype(), &DataType::Float64);
+    assert_eq!(
+        df.get_column_names()[..7],
+        [
+            "ticker",
+            "price",
+            "bid",
+            "fills",
+            "anchor.x",
+            "anchor.label",
+            "track.x"
+        ]
+    );
+}
+
+#[test]
+fn transparent_structs_convert_as_their_field() {
+    assert_eq!(
+        Price::schema().unwrap(),
+        [(String::new(), DataType::Float64)]
+    );
+    assert_eq!(Price::COLUMN_NAMES, [""]);
+    assert_eq!(Anchor::schema().unwrap(), Point::schema().unwrap());
+    assert_eq!(
+        Span::schema().unwrap(),
+        [
+            ("field_0".to_owned(), DataType::Int32),
+            ("field_1".to_owned(), DataType::Int32)
+        ]
+    );
+
+    let df = [Price(1.5), Price(2.5)].to_dataframe().unwrap();
+    assert_eq!(df.shape(), (2, 1));
+    assert_eq!(df.columns()[0].get(1).unwrap(), AnyValue::Float64(2.5));
+    let typed = TypedDataFrame::<Price>::try_from(df).unwrap();
+    assert_eq!(typed.height(), 2);
+}
+
+#[test]
+fn transparent_generics_and_payloads() {
+    let tagged = [Tagged(Price(1.0)), Tagged(Price(2.0))];
+    assert_eq!(
+        tagged.to_dataframe().unwrap().schema(),
+        [Price(0.0)].to_dataframe().unwrap().schema()
+    );
+
+    let wrapped = [
+        Wrapper {
+            payload: Price(1.0),
+        },
+        Wrapper {
+            payload: Price(2.0),
+        },
+    ];
+    let df = wrapped.to_dataframe().unwrap();
+    assert_eq!(df.column("payload").unwrap().dtype(), &DataType::Float64);
+
+    let anchors = Wrapper {
+        payload: Tagged(Anchor(point(4))),
+    };
+    assert_eq!(
+        anchors.to_dataframe().unwrap().get_column_names(),
+        ["payload.x", "payload.label"]
+    );
+}
+
+#[test]
+fn transparent_column_handles_name_the_outer_field() {
+    assert_eq!(Price::cols().name(), "");
+    assert_eq!(Quote::cols().price().name(), "price");
+    assert_eq!(Quote::cols().fills().name(), "fills");
+    assert_eq!(Quote::cols().ticker().name(), "ticker");
+    assert_eq!(Quote::cols().anchor().label().name(), "anchor.label");
+    assert_eq!(Quote::cols().span().field_1().name(), "span.field_1");
+    assert_eq!(
+        Wrapper::<Tagged<Anchor>>::cols().payload().x().name(),
+        "payload.x"
+    );
+}
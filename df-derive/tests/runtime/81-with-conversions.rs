@@ -0,0 +1,206 @@
+use smallvec::{SmallVec, smallvec};
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataType};
+
+/// Stands in for a type from another crate that cannot derive or implement
+/// the dataframe traits.
+#[derive(Clone, Copy)]
+struct Money {
+    units: i64,
+    nanos: i32,
+}
+
+impl Money {
+    const fn cents(&self) -> i64 {
+        self.units * 100 + (self.nanos / 10_000_000) as i64
+    }
+}
+
+struct Rate(f64);
+
+fn to_bps(rate: &Rate) -> i64 {
+    (rate.0 * 10_000.0).round() as i64
+}
+
+fn port(addr: &str) -> Option<u16> {
+    addr.rsplit_once(':')?.1.parse().ok()
+}
+
+#[derive(ToDataFrame)]
+struct Range {
+    low: i64,
+    high: i64,
+}
+
+fn range(money: &Money) -> Range {
+    Range {
+        low: money.units,
+        high: money.units + 1,
+    }
+}
+
+#[derive(ToDataFrame)]
+struct Order {
+    #[df_derive(with = "Money::cents")]
+    price: Money,
+    #[df_derive(with = "to_bps")]
+    fee: Rate,
+    #[df_derive(with = "port")]
+    endpoint: Box<str>,
+    #[df_derive(with = "Money::cents")]
+    bid: Option<Money>,
+    #[df_derive(with = "Money::cents")]
+    fills: Vec<Option<Money>>,
+    #[df_derive(with = "range")]
+    band: Money,
+    #[df_derive(with = "range")]
+    history: Vec<Money>,
+    #[df_derive(as_list, with = "Money::cents")]
+    legs: SmallVec<[Money; 2]>,
+}
+
+#[derive(ToDataFrame)]
+struct PlainOrder {
+    price: i64,
+    fee: i64,
+    endpoint: Option<u16>,
+    bid: Option<i64>,
+    fills: Vec<Option<i64>>,
+    band: Range,
+    history: Vec<Range>,
+    legs: Vec<i64>,
+}
+
+const fn money(units: i64) -> Money {
+    Money {
+        units,
+        nanos: 250_000_000,
+    }
+}
+
+fn order(seed: i64) -> Order {
+    Order {
+        price: money(seed),
+        fee: Rate(0.0015),
+        endpoint: if seed % 2 == 0 {
+            "localhost:8080".into()
+        } else {
+            "localhost".into()
+        },
+        bid: (seed % 2 == 0).then(|| money(seed - 1)),
+        fills: vec![Some(money(seed)), None],
+        band: money(seed),
+        history: vec![money(1), money(seed)],
+        legs: smallvec![money(seed), money(-seed)],
+    }
+}
+
+fn plain(order: &Order) -> PlainOrder {
+    PlainOrder {
+        price: order.price.cents(),
+        fee: to_bps(&order.fee),
+        endpoint: port(&order.endpoint),
+        bid: order.bid.as_ref().map(Money::cents),
+        fills: order
+            .fills
+            .iter()
+            .map(|fill| fill.as_ref().map(Money::cents))
+            .collect(),
+        band: range(&order.band),
+        history: order.history.iter().map(range).collect(),
+        legs: order.legs.iter().map(Money::cents).collect(),
+    }
+}
+
+#[test]
+fn with_fields_write_the_function_results() {
+    let orders = [order(2), order(3)];
+    let expected = orders.iter().map(plain).collect::<Vec<_>>();
+    let df = orders.to_dataframe().unwrap();
+
+    assert!(df.equals_missing(&expected.to_dataframe().unwrap()));
+    assert_eq!(Order::schema().unwrap(), PlainOrder::schema().unwrap());
+    assert_eq!(
+        Order::arrow_schema().unwrap(),
+        PlainOrder::arrow_schema().unwrap()
+    );
+    assert_eq!(
+        Vec::<Order>::new().to_dataframe().unwrap().schema(),
+        df.schema()
+    );
+    assert!(
+        order(4)
+            .to_dataframe()
+            .unwrap()
+            .equals_missing(&plain(&order(4)).to_dataframe().unwrap())
+    );
+
+    assert_eq!(
+        df.column("price").unwrap().get(0).unwrap(),
+        AnyValue::Int64(225)
+    );
+    assert_eq!(
+        df.column("fee").unwrap().get(0).unwrap(),
+        AnyValue::Int64(15)
+    );
+    assert_eq!(df.column("endpoint").unwrap().dtype(), &DataType::UInt16);
+    assert_eq!(
+        df.column("endpoint").unwrap().get(1).unwrap(),
+        AnyValue::Null
+    );
+    assert_eq!(
+        df.get_column_names()[5..],
+        [
+            "band.low",
+            "band.high",
+            "history.low",
+            "history.high",
+            "legs"
+        ]
+    );
+}
+
+struct Bucket<T> {
+    items: Vec<T>,
+}
+
+impl<T> Bucket<T> {
+    fn len(&self) -> u32 {
+        u32::try_from(self.items.len()).unwrap()
+    }
+}
+
+#[derive(ToDataFrame)]
+struct Tagged<'a, T> {
+    #[df_derive(with = "Bucket::len")]
+    bucket: &'a Bucket<T>,
+    label: &'a str,
+}
+
+#[test]
+fn with_sources_may_use_struct_generics() {
+    let small = Bucket { items: vec!['a'] };
+    let large = Bucket {
+        items: vec!['a', 'b', 'c'],
+    };
+    let rows = [
+        Tagged {
+            bucket: &small,
+            label: "small",
+        },
+        Tagged {
+            bucket: &large,
+            label: "large",
+        },
+    ];
+    let df = rows.to_dataframe().unwrap();
+
+    assert_eq!(df.column("bucket").unwrap().dtype(), &DataType::UInt32);
+    assert_eq!(
+        df.column("bucket").unwrap().get(1).unwrap(),
+        AnyValue::UInt32(3)
+    );
+    assert_eq!(Tagged::<char>::cols().bucket().name(), "bucket");
+}
@@ -0,0 +1,216 @@
+use chrono::{DateTime, NaiveDateTime};
+use smallvec::{SmallVec, smallvec};
+
+use crate::core::dataframe::{DfLeaf, ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataType, PolarsError, PolarsResult, TimeUnit, polars_bail};
+
+/// Price in exchange ticks.
+struct Ticks(i64);
+
+impl DfLeaf for Ticks {
+    type Value = i64;
+
+    fn dtype() -> DataType {
+        DataType::Int64
+    }
+
+    fn encode(&self) -> PolarsResult<i64> {
+        Ok(self.0)
+    }
+}
+
+/// Milliseconds since the epoch, written as a `Datetime` column.
+struct Stamp(i64);
+
+impl DfLeaf for Stamp {
+    type Value = i64;
+
+    fn dtype() -> DataType {
+        DataType::Datetime(TimeUnit::Milliseconds, None)
+    }
+
+    fn encode(&self) -> PolarsResult<i64> {
+        Ok(self.0)
+    }
+}
+
+struct Symbol(&'static str);
+
+impl DfLeaf for Symbol {
+    type Value = String;
+
+    fn dtype() -> DataType {
+        DataType::String
+    }
+
+    fn encode(&self) -> PolarsResult<String> {
+        Ok(self.0.to_ascii_uppercase())
+    }
+}
+
+#[derive(ToDataFrame)]
+struct Quote {
+    #[df_derive(leaf)]
+    bid: Ticks,
+    #[df_derive(leaf)]
+    ask: Option<Ticks>,
+    #[df_derive(leaf)]
+    at: Stamp,
+    #[df_derive(leaf)]
+    symbol: Box<Symbol>,
+    #[df_derive(leaf)]
+    fills: Vec<Option<Ticks>>,
+    #[df_derive(as_list, leaf)]
+    legs: SmallVec<[Ticks; 2]>,
+}
+
+#[derive(ToDataFrame)]
+struct PlainQuote {
+    bid: i64,
+    ask: Option<i64>,
+    #[df_derive(time_unit = "ms")]
+    at: NaiveDateTime,
+    symbol: String,
+    fills: Vec<Option<i64>>,
+    legs: Vec<i64>,
+}
+
+fn quote(seed: i64) -> Quote {
+    Quote {
+        bid: Ticks(seed),
+        ask: (seed % 2 == 0).then_some(Ticks(seed + 1)),
+        at: Stamp(seed * 1_000),
+        symbol: Box::new(Symbol("abc")),
+        fills: vec![Some(Ticks(seed)), None],
+        legs: smallvec![Ticks(seed), Ticks(-seed)],
+    }
+}
+
+fn plain(quote: &Quote) -> PlainQuote {
+    PlainQuote {
+        bid: quote.bid.0,
+        ask: quote.ask.as_ref().map(|ask| ask.0),
+        at: DateTime::from_timestamp_millis(quote.at.0)
+            .unwrap()
+            .naive_utc(),
+        symbol: quote.symbol.0.to_ascii_uppercase(),
+        fills: quote
+            .fills
+            .iter()
+            .map(|fill| fill.as_ref().map(|fill| fill.0))
+            .collect(),
+        legs: quote.legs.iter().map(|leg| leg.0).collect(),
+    }
+}
+
+#[test]
+fn leaf_fields_write_encoded_values() {
+    let quotes = [quote(2), quote(3)];
+    let expected = quotes.iter().map(plain).collect::<Vec<_>>();
+    let df = quotes.to_dataframe().unwrap();
+
+    assert!(df.equals_missing(&expected.to_dataframe().unwrap()));
+    assert_eq!(Quote::schema().unwrap(), PlainQuote::schema().unwrap());
+    assert_eq!(
+        Quote::arrow_schema().unwrap(),
+        PlainQuote::arrow_schema().unwrap()
+    );
+    assert_eq!(
+        Vec::<Quote>::new().to_dataframe().unwrap().schema(),
+        df.schema()
+    );
+    assert!(
+        quote(4)
+            .to_dataframe()
+            .unwrap()
+            .equals_missing(&plain(&quote(4)).to_dataframe().unwrap())
+    );
+
+    assert_eq!(
+        df.column("at").unwrap().dtype(),
+        &DataType::Datetime(TimeUnit::Milliseconds, None)
+    );
+    assert_eq!(
+        df.column("symbol").unwrap().get(0).unwrap(),
+        AnyValue::String("ABC")
+    );
+    assert_eq!(Quote::cols().bid().name(), "bid");
+}
+
+/// Percentage that must stay within `0..=100`.
+struct Percent(u32);
+
+impl DfLeaf for Percent {
+    type Value = u32;
+
+    fn dtype() -> DataType {
+        DataType::UInt8
+    }
+
+    fn encode(&self) -> PolarsResult<u32> {
+        if self.0 > 100 {
+            polars_bail!(ComputeError: "percentage {} is out of range", self.0);
+        }
+        Ok(self.0)
+    }
+}
+
+#[derive(ToDataFrame)]
+struct Progress {
+    #[df_derive(leaf)]
+    done: Percent,
+}
+
+#[test]
+fn leaf_values_are_cast_to_the_leaf_dtype() {
+    let df = [Progress { done: Percent(40) }].to_dataframe().unwrap();
+
+    assert_eq!(df.column("done").unwrap().dtype(), &DataType::UInt8);
+    assert_eq!(
+        df.column("done").unwrap().get(0).unwrap(),
+        AnyValue::UInt8(40)
+    );
+}
+
+#[test]
+fn leaf_encode_errors_fail_the_conversion() {
+    let err = [
+        Progress { done: Percent(40) },
+        Progress { done: Percent(140) },
+    ]
+    .to_dataframe()
+    .unwrap_err();
+
+    assert!(matches!(err, PolarsError::ComputeError(_)));
+    assert!(err.to_string().contains("percentage 140 is out of range"));
+}
+
+#[derive(ToDataFrame)]
+struct Labeled<T> {
+    #[df_derive(leaf)]
+    value: Option<T>,
+    label: String,
+}
+
+#[test]
+fn leaf_fields_may_use_struct_generics() {
+    let rows = [
+        Labeled {
+            value: Some(Ticks(7)),
+            label: "a".to_owned(),
+        },
+        Labeled {
+            value: None,
+            label: "b".to_owned(),
+        },
+    ];
+    let df = rows.to_dataframe().unwrap();
+
+    assert_eq!(
+        Labeled::<Symbol>::schema().unwrap()[0],
+        ("value".to_owned(), DataType::String)
+    );
+    assert_eq!(df.column("value").unwrap().dtype(), &DataType::Int64);
+    assert_eq!(df.column("value").unwrap().get(1).unwrap(), AnyValue::Null);
+}
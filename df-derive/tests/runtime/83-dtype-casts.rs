@@ -0,0 +1,396 @@
+use std::str::FromStr;
+
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use rust_decimal::Decimal;
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataType, PolarsError, TimeUnit};
+
+#[derive(ToDataFrame)]
+struct Reading {
+    #[df_derive(dtype = "Int64")]
+    id: u64,
+    #[df_derive(dtype = "Float32")]
+    ratio: f64,
+    #[df_derive(dtype = "UInt32")]
+    count: usize,
+    #[df_derive(dtype = "Int64")]
+    parent: Option<u64>,
+    #[df_derive(dtype = "Int64")]
+    children: Vec<Option<u64>>,
+    #[df_derive(dtype = "Int32")]
+    window: [u64; 2],
+    #[df_derive(dtype = "Datetime(us)")]
+    at: NaiveDateTime,
+    #[df_derive(dtype = "Duration(ms)")]
+    took: TimeDelta,
+    #[df_derive(dtype = "Decimal(18, 4)")]
+    price: Decimal,
+    #[df_derive(dtype = "Decimal(10, 2)")]
+    units: u32,
+    #[df_derive(dtype = "Float64")]
+    pair: (u32, f32),
+}
+
+#[derive(ToDataFrame)]
+struct PlainReading {
+    id: i64,
+    ratio: f32,
+    count: u32,
+    parent: Option<i64>,
+    children: Vec<Option<i64>>,
+    window: [i32; 2],
+    #[df_derive(time_unit = "us")]
+    at: NaiveDateTime,
+    #[df_derive(time_unit = "ms")]
+    took: TimeDelta,
+    #[df_derive(decimal(precision = 18, scale = 4))]
+    price: Decimal,
+    #[df_derive(decimal(precision = 10, scale = 2))]
+    units: Decimal,
+    pair: (f64, f64),
+}
+
+fn at(seed: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 1, seed)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+}
+
+fn reading(seed: u32) -> Reading {
+    Reading {
+        id: u64::from(seed) * 10,
+        ratio: 0.25,
+        count: seed as usize,
+        parent: seed.is_multiple_of(2).then_some(7),
+        children: vec![Some(1), None, Some(u64::from(seed))],
+        window: [1, u64::from(seed)],
+        at: at(seed),
+        took: TimeDelta::milliseconds(i64::from(seed) * 1_500),
+        price: Decimal::from_str("12.3456").unwrap(),
+        units: seed,
+        pair: (seed, 0.5),
+    }
+}
+
+fn plain(reading: &Reading) -> PlainReading {
+    PlainReading {
+        id: i64::try_from(reading.id).unwrap(),
+        ratio: reading.ratio as f32,
+        count: u32::try_from(reading.count).unwrap(),
+        parent: reading.parent.map(|parent| i64::try_from(parent).unwrap()),
+        children: reading
+            .children
+            .iter()
+            .map(|child| child.map(|child| i64::try_from(child).unwrap()))
+            .collect(),
+        window: reading.window.map(|value| i32::try_from(value).unwrap()),
+        at: reading.at,
+        took: reading.took,
+        price: reading.price,
+        units: Decimal::from(reading.units),
+        pair: (f64::from(reading.pair.0), f64::from(reading.pair.1)),
+    }
+}
+
+#[test]
+fn dtype_fields_write_the_target_dtype() {
+    let readings = [reading(2), reading(3)];
+    let expected = readings.iter().map(plain).collect::<Vec<_>>();
+    let df = readings.to_dataframe().unwrap();
+
+    assert!(df.equals_missing(&expected.to_dataframe().unwrap()));
+    assert_eq!(Reading::schema().unwrap(), PlainReading::schema().unwrap());
+    assert_eq!(
+        Reading::arrow_schema().unwrap(),
+        PlainReading::arrow_schema().unwrap()
+    );
+    assert_eq!(
+        Vec::<Reading>::new().to_dataframe().unwrap().schema(),
+        df.schema()
+    );
+    assert!(
+        reading(4)
+            .to_dataframe()
+            .unwrap()
+            .equals_missing(&plain(&reading(4)).to_dataframe().unwrap())
+    );
+
+    assert_eq!(df.column("id").unwrap().dtype(), &DataType::Int64);
+    assert_eq!(
+        df.column("at").unwrap().dtype(),
+        &DataType::Datetime(TimeUnit::Microseconds, None)
+    );
+    assert_eq!(
+        df.column("window").unwrap().dtype(),
+        &DataType::Array(Box::new(DataType::Int32), 2)
+    );
+}
+
+#[derive(ToDataFrame)]
+struct Id {
+    #[df_derive(dtype = "Int64")]
+    id: Vec<u64>,
+}
+
+#[derive(ToDataFrame)]
+struct Ratio {
+    #[df_derive(dtype = "Float32")]
+    ratio: Option<f64>,
+}
+
+#[derive(ToDataFrame)]
+struct NullableRatio {
+    #[df_derive(dtype = "Float32", on_overflow = "null")]
+    ratio: f64,
+    #[df_derive(dtype = "Float32", on_overflow = "null")]
+    history: Vec<f64>,
+}
+
+#[derive(ToDataFrame)]
+struct Amount {
+    #[df_derive(dtype = "Decimal(4, 2)")]
+    amount: u32,
+}
+
+#[test]
+fn values_that_do_not_fit_fail_the_conversion() {
+    let err = [
+        Id { id: vec![1] },
+        Id {
+            id: vec![2, u64::MAX],
+        },
+    ]
+    .to_dataframe()
+    .unwrap_err();
+    assert!(matches!(err, PolarsError::ComputeError(_)));
+    assert!(err.to_string().contains("column `id`"));
+
+    let err = [Ratio { ratio: Some(1e300) }].to_dataframe().unwrap_err();
+    assert!(err.to_string().contains("column `ratio`"));
+    let df = [NullableRatio {
+        ratio: 1e300,
+        history: vec![0.5, -1e300, f64::INFINITY],
+    }]
+    .to_dataframe()
+    .unwrap();
+    assert_eq!(df.column("ratio").unwrap().get(0).unwrap(), AnyValue::Null);
+    let history = df
+        .column("history")
+        .unwrap()
+        .list()
+        .unwrap()
+        .get_as_series(0);
+    let history: Vec<Option<f32>> = history.unwrap().f32().unwrap().iter().collect();
+    assert_eq!(history, [Some(0.5), None, Some(f32::INFINITY)]);
+
+    assert!([Amount { amount: 99 }].to_dataframe().is_ok());
+    let err = [Amount { amount: 100 }].to_dataframe().unwrap_err();
+    assert!(err.to_string().contains("column `amount`"));
+
+    let df = [Ratio { ratio: None }, Ratio { ratio: Some(0.5) }]
+        .to_dataframe()
+        .unwrap();
+    assert_eq!(df.column("ratio").unwrap().get(0).unwrap(), AnyValue::Null);
+}
+
+#[derive(ToDataFrame)]
+struct Level {
+    #[df_derive(dtype = "Int8", on_overflow = "null")]
+    level: i64,
+    #[df_derive(dtype = "UInt8", on_overflow = "error")]
+    checked: u16,
+    #[df_derive(dtype = "Int32")]
+    whole: f64,
+}
+
+#[test]
+fn overflowing_values_may_become_nulls() {
+    let df = [
+        Level {
+            level: 3,
+            checked: 1,
+            whole: 2.7,
+        },
+        Level {
+            level: 300,
+            checked: 2,
+            whole: -2.7,
+        },
+    ]
+    .to_dataframe()
+    .unwrap();
+
+    assert_eq!(df.column("level").unwrap().dtype(), &DataType::Int8);
+    assert_eq!(
+        df.column("level").unwrap().get(0).unwrap(),
+        AnyValue::Int8(3)
+    );
+    assert_eq!(df.column("level").unwrap().get(1).unwrap(), AnyValue::Null);
+    assert_eq!(
+        df.column("whole").unwrap().get(0).unwrap(),
+        AnyValue::Int32(2)
+    );
+    assert_eq!(
+        df.column("whole").unwrap().get(1).unwrap(),
+        AnyValue::Int32(-2)
+    );
+
+    let arrow = Level::arrow_schema().unwrap();
+    let fields = arrow.iter_values().collect::<Vec<_>>();
+    assert!(fields[0].is_nullable);
+    assert!(!fields[1].is_nullable);
+}
+
+#[derive(ToDataFrame)]
+struct Lossy {
+    #[df_derive(dtype = "Decimal(10, 2)")]
+    price: Decimal,
+    #[df_derive(dtype = "Int64")]
+    units: Decimal,
+    #[df_derive(dtype = "Float32")]
+    id: u64,
+}
+
+#[test]
+fn only_range_overflow_is_checked() {
+    let df = [("12.345", "2.5"), ("12.355", "3.5")]
+        .map(|(price, units)| Lossy {
+            price: Decimal::from_str(price).unwrap(),
+            units: Decimal::from_str(units).unwrap(),
+            id: 16_777_217,
+        })
+        .to_dataframe()
+        .unwrap();
+
+    let price = df.column("price").unwrap();
+    assert_eq!(price.get(0).unwrap(), AnyValue::Decimal(1_234, 10, 2));
+    assert_eq!(price.get(1).unwrap(), AnyValue::Decimal(1_236, 10, 2));
+    let units = df.column("units").unwrap();
+    assert_eq!(units.get(0).unwrap(), AnyValue::Int64(2));
+    assert_eq!(units.get(1).unwrap(), AnyValue::Int64(4));
+    assert_eq!(
+        df.column("id").unwrap().get(0).unwrap(),
+        AnyValue::Float32(16_777_216.0)
+    );
+}
+
+/// Stands in for a type from another crate.
+struct Ticks(u64);
+
+const fn ticks(value: &Ticks) -> u64 {
+    value.0
+}
+
+#[derive(ToDataFrame)]
+struct Fee {
+    #[df_derive(with = "ticks", dtype = "Int64")]
+    fee: Ticks,
+    #[df_derive(with = "ticks", dtype = "Int32")]
+    fees: Vec<Option<Ticks>>,
+    #[df_derive(with = "ticks", dtype = "Int8", on_overflow = "null")]
+    small: Ticks,
+}
+
+#[derive(ToDataFrame)]
+struct PlainFee {
+    fee: i64,
+    fees: Vec<Option<i32>>,
+    small: Option<i8>,
+}
+
+#[test]
+fn with_results_are_cast() {
+    let fees = [
+        Fee {
+            fee: Ticks(15),
+            fees: vec![Some(Ticks(100)), None],
+            small: Ticks(10),
+        },
+        Fee {
+            fee: Ticks(2_500),
+            fees: vec![],
+            small: Ticks(5_000),
+        },
+    ];
+    let expected = [
+        PlainFee {
+            fee: 15,
+            fees: vec![Some(100), None],
+            small: Some(10),
+        },
+        PlainFee {
+            fee: 2_500,
+            fees: vec![],
+            small: None,
+        },
+    ];
+    let df = fees.to_dataframe().unwrap();
+
+    assert!(df.equals_missing(&expected.to_dataframe().unwrap()));
+    assert_eq!(Fee::schema().unwrap(), PlainFee::schema().unwrap());
+    assert_eq!(
+        Fee::arrow_schema().unwrap(),
+        PlainFee::arrow_schema().unwrap()
+    );
+    assert_eq!(
+        Vec::<Fee>::new().to_dataframe().unwrap().schema(),
+        df.schema()
+    );
+
+    let err = [Fee {
+        fee: Ticks(u64::MAX),
+        fees: vec![],
+        small: Ticks(0),
+    }]
+    .to_dataframe()
+    .unwrap_err();
+    assert!(matches!(err, PolarsError::ComputeError(_)));
+    assert!(err.to_string().contains("column `fee`"));
+}
+
+#[derive(ToDataFrame)]
+struct Band {
+    low: u64,
+    high: u64,
+}
+
+const fn band(value: &Ticks) -> Band {
+    Band {
+        low: value.0,
+        high: value.0 + 1,
+    }
+}
+
+fn label(value: &Ticks) -> String {
+    value.0.to_string()
+}
+
+#[derive(ToDataFrame)]
+struct Wide {
+    #[df_derive(with = "band", dtype = "Int64")]
+    band: Ticks,
+}
+
+#[derive(ToDataFrame)]
+struct Label {
+    #[df_derive(with = "label", dtype = "Int64")]
+    label: Ticks,
+}
+
+#[test]
+fn with_results_must_be_one_castable_column() {
+    let err = Wide::schema().unwrap_err();
+    assert!(err.to_string().contains("must return one column, got 2"));
+    let err = [Wide { band: Ticks(1) }].to_dataframe().unwrap_err();
+    assert!(err.to_string().contains("must return one column, got 2"));
+
+    let err = Label::schema().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("must return a numeric, temporal, or decimal value")
+    );
+    assert!([Label { label: Ticks(1) }].to_dataframe().is_err());
+}
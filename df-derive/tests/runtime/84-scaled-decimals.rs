@@ -0,0 +1,146 @@
+use std::num::NonZeroU64;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::core::dataframe::{ToDataFrame, ToDataFrameVec};
+use df_derive::ToDataFrame;
+use polars::prelude::{AnyValue, DataType, PolarsError};
+
+#[derive(ToDataFrame)]
+struct Tick {
+    #[df_derive(decimal(precision = 18, scale = 4, mantissa))]
+    price: i64,
+    #[df_derive(decimal(precision = 10, scale = 2, mantissa))]
+    fee: Option<u32>,
+    #[df_derive(decimal(precision = 18, scale = 4, mantissa))]
+    lots: NonZeroU64,
+    #[df_derive(decimal(precision = 12, scale = 3))]
+    rate: f64,
+    #[df_derive(decimal(precision = 6, scale = 2))]
+    levels: Vec<Option<f32>>,
+}
+
+#[derive(ToDataFrame)]
+struct PlainTick {
+    #[df_derive(decimal(precision = 18, scale = 4))]
+    price: Decimal,
+    #[df_derive(decimal(precision = 10, scale = 2))]
+    fee: Option<Decimal>,
+    #[df_derive(decimal(precision = 18, scale = 4))]
+    lots: Decimal,
+    #[df_derive(decimal(precision = 12, scale = 3))]
+    rate: Decimal,
+    #[df_derive(decimal(precision = 6, scale = 2))]
+    levels: Vec<Option<Decimal>>,
+}
+
+fn tick(seed: i64) -> Tick {
+    Tick {
+        price: 1_234_567 * seed,
+        fee: (seed % 2 == 0).then_some(250),
+        lots: NonZeroU64::new(seed.unsigned_abs() * 10_000).unwrap(),
+        rate: -2.125,
+        levels: vec![Some(1.5), None, Some(0.25)],
+    }
+}
+
+fn plain(tick: &Tick) -> PlainTick {
+    let scale = |value: f64| Decimal::from_str(&value.to_string()).unwrap();
+    PlainTick {
+        price: Decimal::new(tick.price, 4),
+        fee: tick.fee.map(|fee| Decimal::new(i64::from(fee), 2)),
+        lots: Decimal::new(i64::try_from(tick.lots.get()).unwrap(), 4),
+        rate: scale(tick.rate),
+        levels: tick
+            .levels
+            .iter()
+            .map(|level| level.map(|level| scale(f64::from(level))))
+            .collect(),
+    }
+}
+
+#[test]
+fn integer_and_float_fields_write_decimal_columns() {
+    let ticks = [tick(2), tick(3)];
+    let expected = ticks.iter().map(plain).collect::<Vec<_>>();
+    let df = ticks.to_dataframe().unwrap();
+
+    assert!(df.equals_missing(&expected.to_dataframe().unwrap()));
+    assert_eq!(Tick::schema().unwrap(), PlainTick::schema().unwrap());
+    assert_eq!(
+        Tick::arrow_schema().unwrap(),
+        PlainTick::arrow_schema().unwrap()
+    );
+    assert_eq!(
+        Vec::<Tick>::new().to_dataframe().unwrap().schema(),
+        df.schema()
+    );
+    assert!(
+        tick(4)
+            .to_dataframe()
+            .unwrap()
+            .equals_missing(&plain(&tick(4)).to_dataframe().unwrap())
+    );
+
+    assert_eq!(
+        df.column("price").unwrap().dtype(),
+        &DataType::Decimal(18, 4)
+    );
+    assert_eq!(
+        df.column("price").unwrap().get(0).unwrap(),
+        AnyValue::Decimal(2_469_134, 18, 4)
+    );
+}
+
+#[derive(ToDataFrame)]
+struct Rate {
+    #[df_derive(decimal(precision = 10, scale = 1))]
+    rate: f64,
+}
+
+fn mantissa(value: &AnyValue<'_>) -> i128 {
+    match value {
+        AnyValue::Decimal(value, _precision, _scale) => *value,
+        other => panic!("expected a decimal, got {other:?}"),
+    }
+}
+
+#[test]
+fn floats_round_half_to_even_at_the_scale() {
+    let df = [0.25, 0.75, -0.25, 0.04, 12.0]
+        .map(|rate| Rate { rate })
+        .to_dataframe()
+        .unwrap();
+    let rates = df.column("rate").unwrap();
+    let mantissas = (0..rates.len())
+        .map(|idx| mantissa(&rates.get(idx).unwrap()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(mantissas, [2, 8, -2, 0, 120]);
+}
+
+#[derive(ToDataFrame)]
+struct Fee {
+    #[df_derive(decimal(precision = 4, scale = 2, mantissa))]
+    fee: i32,
+}
+
+#[test]
+fn values_outside_the_decimal_fail_the_conversion() {
+    assert!([Fee { fee: 9_999 }].to_dataframe().is_ok());
+    let err = [Fee { fee: -10_000 }].to_dataframe().unwrap_err();
+    assert!(matches!(err, PolarsError::ComputeError(_)));
+    assert!(err.to_string().contains("exceeds declared precision"));
+
+    let err = [Rate { rate: 1e9 }].to_dataframe().unwrap_err();
+    assert!(err.to_string().contains("exceeds declared precision"));
+
+    for rate in [f64::NAN, f64::INFINITY, 1e300] {
+        let err = [Rate { rate }].to_dataframe().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("cannot be written as a decimal at scale 1")
+        );
+    }
+}
@@ -87,3 +87,78 @@ mod mixed_wrapper_shape_row;
 
 #[path = "runtime/59-tuple-vector-projection.rs"]
 mod tuple_vector_projection;
+
+#[path = "runtime/60-arrow-schema-nullability.rs"]
+mod arrow_schema_nullability;
+
+#[path = "runtime/61-column-metadata.rs"]
+mod column_metadata;
+
+#[path = "runtime/62-schema-export.rs"]
+mod schema_export;
+
+#[path = "runtime/63-schema-lock.rs"]
+mod schema_lock;
+
+#[path = "runtime/64-schema-diff.rs"]
+mod schema_diff;
+
+#[path = "runtime/65-column-handles.rs"]
+mod column_handles;
+
+#[path = "runtime/66-typed-dataframe.rs"]
+mod typed_dataframe;
+
+#[path = "runtime/67-row-views.rs"]
+mod row_views;
+
+#[path = "runtime/68-serde-bridge.rs"]
+mod serde_bridge;
+
+#[path = "runtime/69-serde-deserializer.rs"]
+mod serde_deserializer;
+
+#[path = "runtime/70-coerce-dataframe.rs"]
+mod coerce_dataframe;
+
+#[path = "runtime/71-validate-schema.rs"]
+mod validate_schema;
+
+#[path = "runtime/72-primitive-generic-payloads.rs"]
+mod primitive_generic_payloads;
+
+#[path = "runtime/73-std-container-payloads.rs"]
+mod std_container_payloads;
+
+#[path = "runtime/74-pointer-slices.rs"]
+mod pointer_slices;
+
+#[path = "runtime/75-map-fields.rs"]
+mod map_fields;
+
+#[path = "runtime/76-collection-fields.rs"]
+mod collection_fields;
+
+#[path = "runtime/77-array-fields.rs"]
+mod array_fields;
+
+#[path = "runtime/78-unsized-pointer-fields.rs"]
+mod unsized_pointer_fields;
+
+#[path = "runtime/79-as-list-fields.rs"]
+mod as_list_fields;
+
+#[path = "runtime/80-transparent-newtypes.rs"]
+mod transparent_newtypes;
+
+#[path = "runtime/81-with-conversions.rs"]
+mod with_conversions;
+
+#[path = "runtime/82-df-leaf-fields.rs"]
+mod df_leaf_fields;
+
+#[path = "runtime/83-dtype-casts.rs"]
+mod dtype_casts;
+
+#[path = "runtime/84-scaled-decimals.rs"]
+mod scaled_decimals;